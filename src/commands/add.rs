@@ -0,0 +1,296 @@
+//
+//  Copyright (C) 2022-2025  Chase Ruskin
+//
+//  This program is free software: you can redistribute it and/or modify
+//  it under the terms of the GNU General Public License as published by
+//  the Free Software Foundation, either version 3 of the License, or
+//  (at your option) any later version.
+//
+//  This program is distributed in the hope that it will be useful,
+//  but WITHOUT ANY WARRANTY; without even the implied warranty of
+//  MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+//  GNU General Public License for more details.
+//
+//  You should have received a copy of the GNU General Public License
+//  along with this program.  If not, see <http://www.gnu.org/licenses/>.
+//
+
+use super::helps::add;
+use super::lock::Lock;
+use crate::core::catalog::{Catalog, PkgName};
+use crate::core::context::Context;
+use crate::core::ip::{Ip, PartialIpSpec};
+use crate::core::lang::cache::UnitCache;
+use crate::core::lockfile::IP_LOCK_FILE;
+use crate::core::manifest::{DepVersion, FromFile, ManifestDocument, IP_MANIFEST_FILE};
+use crate::core::uuid::Uuid;
+use crate::core::version::Version;
+use crate::error::{Error, Hint};
+use crate::util::anyerror::Fault;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+
+use cliproc::{cli, proc, stage::*};
+use cliproc::{Arg, Cli, Help, Subcommand};
+
+#[derive(Debug, PartialEq)]
+pub struct Add {
+    ip: PartialIpSpec,
+    dev: bool,
+    path: Option<PathBuf>,
+    uuid: Option<String>,
+}
+
+impl Subcommand<Context> for Add {
+    fn interpret<'c>(cli: &'c mut Cli<Memory>) -> cli::Result<Self> {
+        cli.help(Help::with(add::HELP))?;
+        Ok(Add {
+            // flags
+            dev: cli.check(Arg::flag("dev"))?,
+            // options
+            path: cli.get(Arg::option("path"))?,
+            uuid: cli.get(Arg::option("uuid").value("id"))?,
+            ip: cli.require(Arg::positional("ip"))?,
+        })
+    }
+
+    fn execute(self, c: &Context) -> proc::Result {
+        if self.path.is_some() && self.uuid.is_some() {
+            return Err(Error::AddUuidWithPath)?;
+        }
+        // the path is given from the directory where the command was entered
+        let invoked_dir = std::env::current_dir()?;
+
+        // check that user is in an IP directory
+        c.jump_to_working_ip()?;
+
+        let ip_path = c.get_ip_path().unwrap().clone();
+
+        let dep_path = match &self.path {
+            Some(p) => Some(Self::relative_to(&ip_path, &invoked_dir.join(p))?),
+            None => None,
+        };
+
+        // store the working ip struct
+        let working_ip = Ip::load(ip_path.clone(), true)?;
+
+        let name = self.ip.get_name();
+        if name == working_ip.get_man().get_ip().get_name() {
+            return Err(Error::CyclicDependencyIp(name.clone()))?;
+        }
+
+        let uuid = match &self.uuid {
+            Some(id) => Some(Uuid::from_str(id)?),
+            None => self.ip.as_uuid().clone(),
+        };
+
+        // determine the version to require for the dependency
+        let version = match &dep_path {
+            Some(path) => {
+                let dep = Ip::relate(path.clone(), &ip_path)?;
+                // verify the ip loaded has the correct name assigned by the user
+                let dep_name = dep.get_man().get_ip().get_name();
+                if dep_name != name {
+                    return Err(Error::DependencyIpRelativeBadName(
+                        name.clone(),
+                        dep_name.clone(),
+                    ))?;
+                }
+                let dep_version = dep.get_man().get_ip().get_version();
+                if self.ip.get_version().matches(dep_version) == false {
+                    return Err(Error::VersionNotFound(
+                        self.ip.get_version().clone(),
+                        Hint::ShowVersions,
+                    ))?;
+                }
+                dep_version.clone()
+            }
+            None => {
+                // assemble the catalog
+                let catalog = Catalog::new()
+                    .installations(c.get_cache_path())?
+                    .shared_installations(c.get_shared_cache_paths())?
+                    .downloads(c.get_downloads_path())?
+                    .available(&c.get_config().get_channels())?;
+
+                let lvl = match catalog.translate_name(&PkgName::new(name, uuid.as_ref()))? {
+                    Some(lvl) => lvl,
+                    None => {
+                        return Err(Error::IpNotFoundAnywhere(
+                            name.to_string(),
+                            Hint::CatalogList,
+                        ))?
+                    }
+                };
+                match lvl.get(true, true, self.ip.get_version()) {
+                    Some(ip) => ip.get_man().get_ip().get_version().clone(),
+                    None => {
+                        return Err(Error::VersionNotFound(
+                            self.ip.get_version().clone(),
+                            Hint::ShowVersions,
+                        ))?
+                    }
+                }
+            }
+        };
+
+        // only write the uuid when it was explicitly given
+        let uuid = match dep_path.is_some() {
+            true => None,
+            false => uuid,
+        };
+
+        let requirement = Self::into_requirement(&version);
+        let path = dep_path.as_ref().map(|p| p.to_string_lossy().to_string());
+
+        let man_path = ip_path.join(IP_MANIFEST_FILE);
+        let mut doc = ManifestDocument::from_file(&man_path)?;
+        doc.add_dependency(name, &requirement, path.as_deref(), uuid.as_ref(), self.dev);
+        Self::write_and_lock(c, &ip_path, &mut doc)?;
+        println!(
+            "info: added {} = \"{}\" to [{}]",
+            name,
+            requirement,
+            match self.dev {
+                true => "dev-dependencies",
+                false => "dependencies",
+            }
+        );
+        Ok(())
+    }
+}
+
+impl Add {
+    /// Creates the version requirement to write for the selected `version`.
+    ///
+    /// Releases are written as "major.minor" to accept any newer micro version,
+    /// while labeled versions are pinned exactly.
+    pub fn into_requirement(version: &Version) -> DepVersion {
+        let text = match version.has_label() {
+            true => format!("={}", version),
+            false => format!("{}.{}", version.get_major(), version.get_minor()),
+        };
+        DepVersion::from_str(&text).unwrap()
+    }
+
+    /// Expresses the existing `path` relative to the local ip's root directory
+    /// `root`.
+    pub fn relative_to(root: &Path, path: &Path) -> Result<PathBuf, Fault> {
+        let root = root.canonicalize()?;
+        let path = match path.canonicalize() {
+            Ok(p) => p,
+            Err(e) => {
+                return Err(Error::Custom(format!(
+                    "failed to read path \"{}\": {}",
+                    path.display(),
+                    e
+                )))?
+            }
+        };
+        let common = root
+            .components()
+            .zip(path.components())
+            .take_while(|(a, b)| a == b)
+            .count();
+        let mut result = PathBuf::new();
+        for _ in root.components().skip(common) {
+            result.push("..");
+        }
+        for part in path.components().skip(common) {
+            result.push(part);
+        }
+        if result.as_os_str().is_empty() == true {
+            result.push(".");
+        }
+        Ok(result)
+    }
+
+    /// Writes the edited manifest `doc` for the local ip at `ip_path` and updates
+    /// its lockfile.
+    ///
+    /// The manifest and lockfile are restored to their previous contents if the
+    /// lockfile cannot be updated.
+    pub fn write_and_lock(
+        c: &Context,
+        ip_path: &Path,
+        doc: &mut ManifestDocument,
+    ) -> Result<(), Fault> {
+        let man_path = ip_path.join(IP_MANIFEST_FILE);
+        let lock_path = ip_path.join(IP_LOCK_FILE);
+        let prev_man = std::fs::read(&man_path)?;
+        let prev_lock = std::fs::read(&lock_path).ok();
+
+        let result = doc
+            .write(&man_path)
+            .and_then(|_| Self::refresh_lock(c, ip_path));
+        if result.is_err() == true {
+            std::fs::write(&man_path, prev_man)?;
+            match prev_lock {
+                Some(contents) => std::fs::write(&lock_path, contents)?,
+                None => {
+                    if lock_path.exists() == true {
+                        std::fs::remove_file(&lock_path)?;
+                    }
+                }
+            }
+        }
+        result
+    }
+
+    /// Reloads the local ip at `ip_path` and rewrites its lockfile to capture
+    /// the edited manifest.
+    pub fn refresh_lock(c: &Context, ip_path: &Path) -> Result<(), Fault> {
+        let working_ip = Ip::load(ip_path.to_path_buf(), true)?;
+
+        let catalog = Catalog::new()
+            .installations(c.get_cache_path())?
+            .shared_installations(c.get_shared_cache_paths())?
+            .downloads(c.get_downloads_path())?
+            .available(&c.get_config().get_channels())?;
+
+        // reuse the design units of any unchanged files from previous runs
+        let mut cache = UnitCache::open(&working_ip.get_root().join(c.get_target_dir()));
+
+        Lock::run(&working_ip, &catalog, false, None, &mut cache)?;
+        cache.save()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn ut_into_requirement() {
+        let req = Add::into_requirement(&Version::from_str("2.1.4").unwrap());
+        assert_eq!(req.to_string(), "2.1");
+        assert_eq!(req.matches(&Version::from_str("2.1.9").unwrap()), true);
+        assert_eq!(req.matches(&Version::from_str("2.2.0").unwrap()), false);
+
+        let req = Add::into_requirement(&Version::from_str("1.3.0-beta").unwrap());
+        assert_eq!(req.to_string(), "=1.3.0-beta");
+        assert_eq!(req.matches(&Version::from_str("1.3.0-beta").unwrap()), true);
+    }
+
+    #[test]
+    fn ut_relative_to() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path().join("ip");
+        std::fs::create_dir_all(root.join("deps/uart")).unwrap();
+        std::fs::create_dir_all(dir.path().join("lib/gates")).unwrap();
+
+        assert_eq!(
+            Add::relative_to(&root, &root.join("deps/uart")).unwrap(),
+            PathBuf::from("deps/uart")
+        );
+        assert_eq!(
+            Add::relative_to(&root, &root.join("deps/uart/../../../lib/gates")).unwrap(),
+            PathBuf::from("../lib/gates")
+        );
+        assert_eq!(Add::relative_to(&root, &root).unwrap(), PathBuf::from("."));
+        assert_eq!(
+            Add::relative_to(&root, &root.join("missing")).is_err(),
+            true
+        );
+    }
+}
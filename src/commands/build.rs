@@ -16,13 +16,14 @@
 //
 
 use super::plan;
-use super::plan::Plan;
+use super::plan::{Plan, PlanOptions};
 use crate::commands::helps::build;
 use crate::core::blueprint::Scheme;
-use crate::core::catalog::Catalog;
 use crate::core::context::Context;
 use crate::core::fileset::Fileset;
 use crate::core::ip::Ip;
+use crate::core::lang::cache::UnitCache;
+use crate::core::lang::verilog::preprocess::Define;
 use crate::core::lang::vhdl::token::Identifier;
 use crate::core::swap::StrSwapTable;
 use crate::core::target::Process;
@@ -53,6 +54,8 @@ pub struct Build {
     args: Vec<String>,
     verbose: bool,
     filesets: Option<Vec<Fileset>>,
+    defines: Option<Vec<Define>>,
+    add_missing: bool,
 }
 
 impl Subcommand<Context> for Build {
@@ -65,6 +68,7 @@ impl Subcommand<Context> for Build {
             force: cli.check(Arg::flag("force"))?,
             all: cli.check(Arg::flag("all"))?,
             dirty: cli.check(Arg::flag("no-clean"))?,
+            add_missing: cli.check(Arg::flag("add-missing"))?,
             // Options
             top: cli.get(Arg::option("top").value("unit"))?,
             plan: cli.get(Arg::option("plan").value("format"))?,
@@ -72,6 +76,7 @@ impl Subcommand<Context> for Build {
             target_dir: cli.get(Arg::option("target-dir").value("dir"))?,
             command: cli.get(Arg::option("command").value("path"))?,
             filesets: cli.get_all(Arg::option("fileset").value("key=glob"))?,
+            defines: cli.get_all(Arg::option("define").value("macro"))?,
             // Remaining args
             args: cli.remainder()?,
         })
@@ -116,28 +121,45 @@ impl Subcommand<Context> for Build {
 
         let output_path = working_ip.get_root().join(target_dir).join(out_dir);
 
+        // reuse the design units of any unchanged files from previous plans
+        let mut cache = UnitCache::open(&working_ip.get_root().join(target_dir));
+
+        // depend on the installed ips that provide any missing units before planning
+        let working_ip = match self.add_missing {
+            true => Plan::add_missing_deps(
+                c,
+                working_ip,
+                target,
+                &self.defines,
+                self.force,
+                &mut cache,
+            )?,
+            false => working_ip,
+        };
+
         // gather the catalog and resolve any missing dependencies
-        let catalog = Catalog::new()
-            .installations(c.get_cache_path())?
-            .downloads(c.get_downloads_path())?;
-        let catalog = plan::resolve_missing_deps(c, &working_ip, catalog, self.force)?;
+        let catalog = plan::gather_catalog(c, &working_ip, self.force)?;
 
         // plan for the provided target
         let blueprint_name = Plan::run(
             &working_ip,
-            target_dir,
             target,
             catalog,
-            self.dirty == false,
-            self.force,
-            false,
-            self.all,
-            &None,
-            &self.top,
-            &self.filesets,
-            &plan,
-            false,
-            false,
+            &PlanOptions {
+                target_dir: target_dir,
+                clean: self.dirty == false,
+                force: self.force,
+                only_lock: false,
+                all: self.all,
+                bench: &None,
+                top: &self.top,
+                filesets: &self.filesets,
+                defines: &self.defines,
+                scheme: &plan,
+                require_bench: false,
+                allow_bench: false,
+            },
+            &mut cache,
         )?
         .unwrap_or_default();
 
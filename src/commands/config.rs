@@ -22,6 +22,7 @@ use crate::commands::helps::config;
 use crate::core;
 use crate::core::config::ConfigDocument;
 use crate::core::config::Locality;
+use crate::core::config::{EDITABLE_KEYS, ENV_KEY};
 use crate::core::context::Context;
 use crate::core::manifest::FromFile;
 use crate::error::Error;
@@ -33,6 +34,7 @@ use crate::util::filesystem;
 
 use cliproc::{cli, proc, stage::*};
 use cliproc::{Arg, Cli, Help, Subcommand};
+use colored::Colorize;
 
 #[derive(Debug, PartialEq)]
 pub struct Entry(String, String);
@@ -150,6 +152,31 @@ impl Config {
         self.push.is_empty() && self.pop.is_empty() && self.set.is_empty() && self.unset.is_empty()
     }
 
+    /// Splits the full `key` into its table and field names.
+    ///
+    /// Errors if the key is not of the form "table.field". Warns if the key is
+    /// not a known field that can be edited through the command-line.
+    fn split_key(key: &str) -> Result<(&str, &str), Fault> {
+        let (table, field) = match key.split_once('.') {
+            Some(pair) => pair,
+            None => {
+                return Err(Error::ConfigUnsupportedKey(
+                    key.to_string(),
+                    Hint::did_you_mean(key, &EDITABLE_KEYS, Hint::ShowConfigData),
+                ))?
+            }
+        };
+        if table != ENV_KEY && EDITABLE_KEYS.contains(&key) == false {
+            println!(
+                "{} unknown key \"{}\" is not a recognized setting{}",
+                "warning:".yellow(),
+                key,
+                Hint::did_you_mean(key, &EDITABLE_KEYS, Hint::ShowConfigData)
+            );
+        }
+        Ok((table, field))
+    }
+
     fn run_all(&self, mut configs: Vec<(ConfigDocument, PathBuf, Locality)>) -> Result<(), Fault> {
         // check for list appending
         for entry in &self.push {
@@ -185,38 +212,29 @@ impl Config {
         }
 
         for entry in &self.set {
-            // split by dots to get table.key (silently ignores improper parsing)
-            if let Some((table, key)) = entry.0.split_once('.') {
-                let cfg = match configs
-                    .iter_mut()
-                    .find(|(c, _, _)| c.is_set(Some(table), &key))
-                {
-                    Some(cfg) => cfg,
-                    None => configs.last_mut().unwrap(),
-                };
-                cfg.0.set(table, key, &entry.1);
-            } else {
-                return Err(AnyError(format!(
-                    "unsupported key {:?} cannot be set",
-                    entry.0
-                )))?;
-            }
+            // split by dots to get table.key
+            let (table, key) = Self::split_key(&entry.0)?;
+            let cfg = match configs
+                .iter_mut()
+                .find(|(c, _, _)| c.is_set(Some(table), key))
+            {
+                Some(cfg) => cfg,
+                None => configs.last_mut().unwrap(),
+            };
+            cfg.0.set(table, key, &entry.1);
         }
 
         for key in &self.unset {
-            // split by dots to get table.key (silently ignores improper parsing)
-            if let Some((table, key)) = key.split_once('.') {
-                let cfg = match configs
-                    .iter_mut()
-                    .find(|(c, _, _)| c.is_set(Some(table), &key))
-                {
-                    Some(cfg) => cfg,
-                    None => configs.last_mut().unwrap(),
-                };
-                cfg.0.unset(table, key)?;
-            } else {
-                return Err(AnyError(format!("unsupported key {:?} cannot be set", key)))?;
-            }
+            // split by dots to get table.key
+            let (table, key) = Self::split_key(key)?;
+            let cfg = match configs
+                .iter_mut()
+                .find(|(c, _, _)| c.is_set(Some(table), key))
+            {
+                Some(cfg) => cfg,
+                None => configs.last_mut().unwrap(),
+            };
+            cfg.0.unset(table, key)?;
         }
 
         // verify all configs
@@ -266,24 +284,15 @@ impl Config {
         }
 
         for entry in &self.set {
-            // split by dots to get table.key (silently ignores improper parsing)
-            if let Some((table, key)) = entry.0.split_once('.') {
-                config.0.set(table, key, &entry.1)
-            } else {
-                return Err(AnyError(format!(
-                    "unsupported key {:?} cannot be set",
-                    entry.0
-                )))?;
-            }
+            // split by dots to get table.key
+            let (table, key) = Self::split_key(&entry.0)?;
+            config.0.set(table, key, &entry.1)
         }
 
         for key in &self.unset {
-            // split by dots to get table.key (silently ignores improper parsing)
-            if let Some((table, key)) = key.split_once('.') {
-                config.0.unset(table, key)?
-            } else {
-                return Err(AnyError(format!("unsupported key {:?} cannot be set", key)))?;
-            }
+            // split by dots to get table.key
+            let (table, key) = Self::split_key(key)?;
+            config.0.unset(table, key)?
         }
 
         // is the config file okay?
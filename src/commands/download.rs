@@ -17,6 +17,7 @@
 
 use crate::core::catalog::Catalog;
 use crate::core::catalog::DownloadSlot;
+use crate::core::config::DownloadSettings;
 use crate::core::ip::Ip;
 use crate::core::ip::IpSpec;
 use crate::core::ip::PartialIpSpec;
@@ -29,6 +30,7 @@ use crate::core::protocol::Protocol;
 use crate::core::source::Source;
 use crate::core::swap::StrSwapTable;
 use crate::core::target::Process;
+use crate::core::version::AnyVersion;
 use crate::error::Error;
 use crate::error::Hint;
 use crate::error::LastError;
@@ -76,7 +78,7 @@ impl Download {
                 let spec = f.to_ip_spec();
                 vtable.add("orbit.ip.name", spec.get_name().as_ref());
                 vtable.add("orbit.ip.version", &spec.get_version().to_string());
-                let processed_src = f.get_source().unwrap().clone().replace_vars_in_url(&vtable);
+                let processed_src = f.get_pinned_source().unwrap().replace_vars_in_url(&vtable);
                 (spec, processed_src)
             })
             .collect()
@@ -91,6 +93,7 @@ impl Download {
         queue: Option<&PathBuf>,
         download_dir: &PathBuf,
         protocols: &HashMap<&str, &Protocol>,
+        settings: &DownloadSettings,
         verbose: bool,
         _force: bool,
     ) -> Result<(IpSpec, Vec<u8>), Fault> {
@@ -103,6 +106,8 @@ impl Download {
             None => TempDir::into_path(TempDir::new()?),
         };
 
+        // the commit checked out when using the built-in "git" protocol
+        let mut commit = None;
         // access the protocol
         if let Some(proto) = src.get_protocol() {
             match protocols.get(proto.as_str()) {
@@ -145,6 +150,41 @@ impl Download {
                         return Err(Error::ProtocolProcFailed(LastError(err.to_string())))?;
                     }
                 }
+                // fall back to the built-in protocol when the user did not define their own
+                None if src.is_git() == true => {
+                    if let Some(ip_spec) = spec {
+                        if verbose == true {
+                            println!(
+                                "info: downloading ip {} over \"{}\" protocol ...",
+                                ip_spec, &proto
+                            );
+                        }
+                        vtable.add("orbit.ip.name", ip_spec.get_name().as_ref());
+                        vtable.add("orbit.ip.version", &ip_spec.get_version().to_string());
+                    } else {
+                        if verbose == true {
+                            println!("info: downloading ip over \"{}\" protocol ...", &proto);
+                        }
+                    }
+
+                    // perform string swap on source url
+                    let processed_src = src
+                        .clone()
+                        .replace_vars_in_url(vtable)
+                        .replace_vars_in_tag(vtable);
+
+                    match Protocol::git_download(
+                        processed_src.get_url(),
+                        processed_src.get_tag().as_deref(),
+                        &queue,
+                    ) {
+                        Ok(c) => commit = Some(c),
+                        Err(err) => {
+                            fs::remove_dir_all(queue)?;
+                            return Err(err);
+                        }
+                    }
+                }
                 None => {
                     // potential to use --force here to avoid this error and try with default but not currently implemented that way
                     fs::remove_dir_all(queue)?;
@@ -172,13 +212,15 @@ impl Download {
                 .replace_vars_in_url(&vtable)
                 .replace_vars_in_tag(&vtable);
 
-            if let Err(err) = Protocol::single_download(processed_src.get_url(), &queue) {
+            if let Err(err) =
+                Protocol::single_download(processed_src.get_url(), &queue, download_dir, settings)
+            {
                 fs::remove_dir_all(queue)?;
                 return Err(err);
             }
         }
         // move the IP to the downloads folder
-        match Self::move_to_download_dir(&queue, download_dir, spec, verbose) {
+        match Self::move_to_download_dir(&queue, download_dir, spec, commit.as_deref(), verbose) {
             Ok((name, bytes)) => {
                 // clean up temporary directory
                 fs::remove_dir_all(queue)?;
@@ -191,10 +233,15 @@ impl Download {
         }
     }
 
+    /// Archives the ip found in the `queue` into the `downloads` directory.
+    ///
+    /// The `commit` the ip was fetched from is stored alongside its files when
+    /// it is provided.
     pub fn move_to_download_dir(
         queue: &PathBuf,
         downloads: &PathBuf,
         spec: Option<&PartialIpSpec>,
+        commit: Option<&str>,
         verbose: bool,
     ) -> Result<(IpSpec, Vec<u8>), Fault> {
         // code is in the queue now, move it to the downloads/ folder
@@ -232,12 +279,17 @@ impl Download {
                     // move to downloads
                     let detected_it = if let Some(provided) = spec {
                         manifest_name == provided.get_name()
-                            && manifest_version.in_domain(
-                                provided
-                                    .get_version()
-                                    .as_specific()
-                                    .unwrap_or(&manifest_version),
-                            )
+                            && match provided.get_version() {
+                                AnyVersion::Requirement(r) => {
+                                    r.matches(temp.get_man().get_ip().get_version())
+                                }
+                                _ => manifest_version.in_domain(
+                                    provided
+                                        .get_version()
+                                        .as_specific()
+                                        .unwrap_or(&manifest_version),
+                                ),
+                            }
                     } else {
                         true
                     };
@@ -249,6 +301,9 @@ impl Download {
                         }
                         // verify the ip is okay
                         Ip::load(temp.get_root().to_path_buf(), false)?;
+                        if let Some(c) = commit {
+                            temp.write_cache_commit(c)?;
+                        }
                         // zip the project to the downloads directory
                         let download_slot_name = DownloadSlot::new(
                             manifest_name,
@@ -273,6 +328,7 @@ impl Download {
     pub fn download_all(
         downloads: &Vec<(IpSpec, Source)>,
         proto_map: &HashMap<&str, &Protocol>,
+        settings: &DownloadSettings,
         vtable: StrSwapTable,
         verbose: bool,
         queue: Option<&PathBuf>,
@@ -300,6 +356,7 @@ impl Download {
                 queue,
                 &download_dir,
                 &proto_map,
+                settings,
                 verbose,
                 force,
             ) {
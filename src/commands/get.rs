@@ -22,12 +22,20 @@ use crate::core::ip::Ip;
 use crate::core::ip::IpSpec;
 use crate::core::ip::PartialIpSpec;
 use crate::core::lang::sv::format::SystemVerilogFormat;
+use crate::core::lang::sv::symbols::package::{
+    DeclarationKind as SvDeclarationKind, Package as SvPackage,
+};
+use crate::core::lang::testbench;
+use crate::core::lang::translate;
 use crate::core::lang::verilog::symbols::module::Module;
 use crate::core::lang::vhdl::format::VhdlFormat;
 use crate::core::lang::vhdl::interface;
 use crate::core::lang::vhdl::interface::Architectures;
 use crate::core::lang::vhdl::symbols::entity::Entity;
+use crate::core::lang::vhdl::symbols::package::{DeclarationKind as VhdlDeclarationKind, Package};
+use crate::core::lang::vhdl::token::keyword::Keyword as VhdlKeyword;
 use crate::core::lang::vhdl::token::Identifier as VhdlIdentifier;
+use crate::core::lang::vhdl::token::ToColor;
 use crate::core::lang::Lang;
 use crate::core::lang::LangIdentifier;
 use crate::core::lang::LangUnit;
@@ -35,6 +43,7 @@ use crate::error::Error;
 use crate::error::Hint;
 use crate::util::anyerror::{AnyError, Fault};
 use colored::Colorize;
+use std::collections::HashMap;
 use std::env;
 
 use cliproc::{cli, proc, stage::*};
@@ -50,12 +59,14 @@ pub struct Get {
     library: bool,
     architectures: bool,
     json: bool,
+    testbench: bool,
     signal_prefix: String,
     signal_suffix: String,
     // NOTE: not done yet... requires more work with detecting generics in the datatype of the signals
     // const_prefix: String,
     // const_suffix: String,
     name: Option<VhdlIdentifier>,
+    lang: Option<Lang>,
 }
 
 impl Subcommand<Context> for Get {
@@ -68,6 +79,7 @@ impl Subcommand<Context> for Get {
             library: cli.check(Arg::flag("library").switch('l'))?,
             architectures: cli.check(Arg::flag("architecture").switch('a'))?,
             json: cli.check(Arg::flag("json"))?,
+            testbench: cli.check(Arg::flag("testbench"))?,
             signal_prefix: cli
                 .get(Arg::option("signal-prefix").value("str"))?
                 .unwrap_or_default(),
@@ -82,17 +94,19 @@ impl Subcommand<Context> for Get {
             //     .unwrap_or_default(),
             ip: cli.get(Arg::option("ip").value("spec"))?,
             name: cli.get(Arg::option("name").value("identifier"))?,
+            lang: cli.get(Arg::option("lang").value("hdl"))?,
             unit: cli.require(Arg::positional("unit"))?,
         })
     }
 
     fn execute(self, c: &Context) -> proc::Result {
-        // --name can only be used with --instance is set
-        if self.name.is_some() && self.instance == false {
+        // --name can only be used with --instance or --testbench is set
+        if self.name.is_some() && self.instance == false && self.testbench == false {
             return Err(AnyError(format!(
-                "'{}' can only be used with '{}'",
+                "'{}' can only be used with '{}' or '{}'",
                 "--name".yellow(),
-                "--instance".yellow()
+                "--instance".yellow(),
+                "--testbench".yellow()
             )))?;
         }
 
@@ -100,7 +114,8 @@ impl Subcommand<Context> for Get {
         let catalog = Catalog::new()
             // .store(c.get_store_path())
             // .development(c.get_development_path().unwrap())?
-            .installations(c.get_cache_path())?;
+            .installations(c.get_cache_path())?
+            .shared_installations(c.get_shared_cache_paths())?;
 
         let mut is_local_ip = false;
         // try to auto-determine the ip (check if in a working ip)
@@ -135,7 +150,8 @@ impl Subcommand<Context> for Get {
 impl Get {
     fn run(&self, ip: &Ip, is_local: bool, c: &Context) -> Result<(), Fault> {
         // collect all hdl files and parse them
-        let selected_unit = Self::fetch_entity(&ip, &LangIdentifier::Vhdl(self.unit.clone()))?;
+        let mut units = ip.collect_units(true, false)?;
+        let selected_unit = units.remove(&LangIdentifier::Vhdl(self.unit.clone()));
         let unit = match selected_unit {
             Some(lu) => {
                 // verify the unit is only set to public visibility when outside of ip
@@ -150,6 +166,12 @@ impl Get {
                 // check to make sure it is a component
                 if lu.is_component() {
                     lu
+                // display the declarations of a package
+                } else if let Some(pkg) = lu.get_vhdl_symbol().and_then(|s| s.as_package()) {
+                    return self.display_vhdl_package(&ip, pkg, is_local, &c.get_vhdl_format());
+                } else if let Some(pkg) = lu.get_systemverilog_symbol().and_then(|s| s.as_package())
+                {
+                    return self.display_systemverilog_package(pkg, &c.get_sv_format());
                 } else {
                     let hint = match is_local {
                         true => Hint::ShowAvailableUnitsLocal,
@@ -165,30 +187,44 @@ impl Get {
                     true => Hint::ShowAvailableUnitsLocal,
                     false => Hint::ShowAvailableUnitsExternal(ip.get_man().get_ip().into_ip_spec()),
                 };
-                return Err(Error::GetUnitNotFound(self.unit.to_string(), hint))?;
+                let names = Self::list_unit_names(&units, is_local);
+                return Err(Error::GetUnitNotFound(
+                    self.unit.to_string(),
+                    Hint::did_you_mean(&self.unit.to_string(), &names, hint),
+                ))?;
             }
         };
+        let module = match unit.get_lang() {
+            Lang::Vhdl => None,
+            Lang::Verilog => unit.get_verilog_symbol().unwrap().as_module(),
+            Lang::SystemVerilog => unit.get_systemverilog_symbol().unwrap().as_module(),
+        };
         // determine how to handle unit display
-        match unit.get_lang() {
-            Lang::Vhdl => self.display_vhdl_entity(
+        match (module, self.lang.as_ref().unwrap_or(&unit.get_lang())) {
+            (None, Lang::Vhdl) => self.display_vhdl_entity(
                 &ip,
                 unit.get_vhdl_symbol().unwrap().as_entity().unwrap(),
                 is_local,
+                false,
                 &c.get_vhdl_format(),
             ),
-            Lang::Verilog => self.display_verilog_module(
-                &ip,
-                unit.get_verilog_symbol().unwrap().as_module().unwrap(),
-                &c.get_sv_format(),
-            ),
-            Lang::SystemVerilog => self.display_verilog_module(
-                &ip,
-                unit.get_systemverilog_symbol()
-                    .unwrap()
-                    .as_module()
-                    .unwrap(),
-                &c.get_sv_format(),
-            ),
+            (None, lang) => {
+                let entity = unit.get_vhdl_symbol().unwrap().as_entity().unwrap();
+                let module = translate::into_module(entity, lang).ok_or(AnyError(format!(
+                    "failed to translate entity {} into {}",
+                    entity.get_name(),
+                    lang
+                )))?;
+                self.display_verilog_module(&ip, &module, &c.get_sv_format())
+            }
+            (Some(module), Lang::Vhdl) => {
+                let entity = translate::into_vhdl_entity(module).ok_or(AnyError(format!(
+                    "failed to translate module {} into vhdl",
+                    module.get_name()
+                )))?;
+                self.display_vhdl_entity(&ip, &entity, is_local, true, &c.get_vhdl_format())
+            }
+            (Some(module), _) => self.display_verilog_module(&ip, module, &c.get_sv_format()),
         }?;
 
         Ok(())
@@ -199,12 +235,14 @@ impl Get {
         ip: &Ip,
         entity: &Entity,
         is_local: bool,
+        is_foreign: bool,
         fmt: &VhdlFormat,
     ) -> Result<(), Fault> {
         // determine if default print should appear
         let default_output = self.architectures == false
             && self.instance == false
             && self.json == false
+            && self.testbench == false
             && self.signals == false
             && self.component == false
             && self.library == false;
@@ -236,7 +274,10 @@ impl Get {
         }
 
         // display component declaration
-        if self.component == true || default_output == true {
+        if self.component == true
+            || default_output == true
+            || (is_foreign == true && self.instance == true)
+        {
             println!("{}", entity.into_component(&fmt));
         }
 
@@ -252,8 +293,9 @@ impl Get {
             }
         }
 
-        // only display the direct entity instantiation code if not providing component code
-        let lib = if self.component == true {
+        // only display the direct entity instantiation code if not providing component code,
+        // and a unit from another language can only be instantiated as a component
+        let lib = if self.component == true || is_foreign == true {
             None
         } else {
             Some(lib)
@@ -275,6 +317,22 @@ impl Get {
             );
         }
 
+        // display testbench code
+        if self.testbench == true {
+            colored::control::set_override(false);
+            println!(
+                "{}",
+                testbench::into_vhdl_testbench(
+                    &entity,
+                    &lib,
+                    &self.name,
+                    &fmt,
+                    &self.signal_prefix,
+                    &self.signal_suffix
+                )
+            );
+        }
+
         // print as json data
         if self.json == true {
             println!("{}", serde_json::to_string(&entity)?);
@@ -292,6 +350,7 @@ impl Get {
         let default_output = self.architectures == false
             && self.instance == false
             && self.json == false
+            && self.testbench == false
             && self.signals == false
             && self.component == false
             && self.library == false;
@@ -319,6 +378,19 @@ impl Get {
             );
         }
 
+        if self.testbench == true {
+            println!(
+                "{}",
+                testbench::into_verilog_testbench(
+                    &module,
+                    &self.name,
+                    &fmt,
+                    &self.signal_prefix,
+                    &self.signal_suffix
+                )
+            );
+        }
+
         // print as json data
         if self.json == true {
             println!("{}", serde_json::to_string(&module)?);
@@ -327,10 +399,167 @@ impl Get {
         Ok(())
     }
 
-    fn fetch_entity(ip: &Ip, name: &LangIdentifier) -> Result<Option<LangUnit>, Fault> {
-        let mut files = ip.collect_units(true, false)?;
-        let result = files.remove(name);
-        Ok(result)
+    /// Verifies no options are set that require a unit that can be instantiated.
+    fn check_package_options(&self, name: &str) -> Result<(), Fault> {
+        let options = [
+            (self.component, "--component"),
+            (self.signals, "--signals"),
+            (self.instance, "--instance"),
+            (self.architectures, "--architecture"),
+            (self.testbench, "--testbench"),
+            (self.lang.is_some(), "--lang"),
+        ];
+        match options.iter().find(|(is_set, _)| *is_set == true) {
+            Some((_, flag)) => Err(AnyError(format!(
+                "'{}' cannot be used with package \"{}\"; only '{}' and '{}' are supported for packages",
+                flag.yellow(),
+                name,
+                "--library".yellow(),
+                "--json".yellow()
+            )))?,
+            None => Ok(()),
+        }
+    }
+
+    fn display_vhdl_package(
+        &self,
+        ip: &Ip,
+        pkg: &Package,
+        is_local: bool,
+        fmt: &VhdlFormat,
+    ) -> Result<(), Fault> {
+        self.check_package_options(&pkg.get_name().to_string())?;
+        // determine if default print should appear
+        let default_output = self.json == false && self.library == false;
+
+        if fmt.is_syntax_highlighted() == false {
+            // force turn off coloring output
+            colored::control::set_override(false);
+        }
+
+        // display library declaration and use clause for the package
+        if self.library == true {
+            let lib = match is_local {
+                true => VhdlIdentifier::new_working(),
+                false => ip
+                    .get_man()
+                    .get_hdl_library()
+                    .as_vhdl_name()
+                    .unwrap()
+                    .clone(),
+            };
+            println!(
+                "{}{}",
+                interface::library_statement(&lib),
+                interface::use_all_statement(&lib, pkg.get_name())
+            );
+        }
+
+        // display the package's declarations
+        if default_output == true {
+            let tab = " ".repeat(fmt.get_tab_size() as usize);
+            println!(
+                "{} {} {}",
+                VhdlKeyword::Package.to_color(),
+                pkg.get_name(),
+                VhdlKeyword::Is.to_color()
+            );
+            if pkg.get_generics().0.len() > 0 {
+                println!(
+                    "{}{}{}",
+                    tab,
+                    VhdlKeyword::Generic.to_color(),
+                    pkg.get_generics().0.to_interface_part_string(&fmt, 2)
+                );
+            }
+            for kind in [
+                VhdlDeclarationKind::Constant,
+                VhdlDeclarationKind::Type,
+                VhdlDeclarationKind::Subtype,
+                VhdlDeclarationKind::Function,
+                VhdlDeclarationKind::Procedure,
+                VhdlDeclarationKind::Component,
+            ] {
+                let decls: Vec<String> = pkg
+                    .get_declarations()
+                    .iter()
+                    .filter(|d| d.get_kind() == &kind)
+                    .map(|d| format!("{}{};", tab, d.to_code(fmt.is_syntax_highlighted())))
+                    .collect();
+                if decls.is_empty() == false {
+                    println!("{}-- {}\n{}", tab, kind, decls.join("\n"));
+                }
+            }
+            println!(
+                "{} {};\n",
+                VhdlKeyword::End.to_color(),
+                VhdlKeyword::Package.to_color()
+            );
+        }
+
+        // print as json data
+        if self.json == true {
+            println!("{}", serde_json::to_string(&pkg)?);
+        }
+        Ok(())
+    }
+
+    fn display_systemverilog_package(
+        &self,
+        pkg: &SvPackage,
+        fmt: &SystemVerilogFormat,
+    ) -> Result<(), Fault> {
+        self.check_package_options(&pkg.get_name().to_string())?;
+        // determine if default print should appear
+        let default_output = self.json == false && self.library == false;
+
+        // display the import statement for the package
+        if self.library == true {
+            println!("import {}::*;\n", pkg.get_name());
+        }
+
+        // display the package's declarations
+        if default_output == true {
+            let tab = " ".repeat(fmt.get_tab_size() as usize);
+            println!("package {};", pkg.get_name());
+            for kind in [
+                SvDeclarationKind::Parameter,
+                SvDeclarationKind::Typedef,
+                SvDeclarationKind::Function,
+                SvDeclarationKind::Task,
+                SvDeclarationKind::Class,
+            ] {
+                let decls: Vec<String> = pkg
+                    .get_declarations()
+                    .iter()
+                    .filter(|d| d.get_kind() == &kind)
+                    .map(|d| format!("{}{};", tab, d.get_code()))
+                    .collect();
+                if decls.is_empty() == false {
+                    println!("{}// {}\n{}", tab, kind, decls.join("\n"));
+                }
+            }
+            println!("endpackage\n");
+        }
+
+        // print as json data
+        if self.json == true {
+            println!("{}", serde_json::to_string(&pkg)?);
+        }
+        Ok(())
+    }
+
+    /// Lists the names of the `units` that are accessible from outside of the ip,
+    /// or every unit if `is_local` is `true`.
+    pub fn list_unit_names(
+        units: &HashMap<LangIdentifier, LangUnit>,
+        is_local: bool,
+    ) -> Vec<String> {
+        units
+            .iter()
+            .filter(|(_, u)| is_local == true || u.get_visibility().is_public() == true)
+            .map(|(name, _)| name.to_string())
+            .collect()
     }
 }
 
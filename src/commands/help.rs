@@ -50,9 +50,14 @@ enum Topic {
     Read,
     Get,
     Tree,
+    Add,
+    Rm,
     Lock,
+    Update,
+    Outdated,
     Build,
     Test,
+    Version,
     Publish,
     Search,
     Install,
@@ -64,8 +69,9 @@ enum Topic {
 impl Topic {
     fn list_all() -> String {
         let list = [
-            "new", "init", "view", "read", "get", "tree", "lock", "test", "build", "publish",
-            "search", "install", "env", "config", "remove",
+            "new", "init", "view", "read", "get", "tree", "add", "rm", "lock", "update",
+            "outdated", "test", "build", "version", "publish", "search", "install", "env",
+            "config", "remove",
         ];
         list.into_iter().fold(String::new(), |mut acc, x| {
             acc.push_str(&format!("{}\n", x));
@@ -84,9 +90,14 @@ impl std::str::FromStr for Topic {
             "read" => Self::Read,
             "get" => Self::Get,
             "tree" => Self::Tree,
+            "add" => Self::Add,
+            "rm" => Self::Rm,
             "lock" => Self::Lock,
+            "update" => Self::Update,
+            "outdated" => Self::Outdated,
             "test" => Self::Test,
             "build" => Self::Build,
+            "version" => Self::Version,
             "publish" => Self::Publish,
             "search" => Self::Search,
             "install" => Self::Install,
@@ -109,9 +120,14 @@ impl Topic {
             Read => manuals::read::MANUAL,
             Get => manuals::get::MANUAL,
             Tree => manuals::tree::MANUAL,
+            Add => manuals::add::MANUAL,
+            Rm => manuals::rm::MANUAL,
             Lock => manuals::lock::MANUAL,
+            Update => manuals::update::MANUAL,
+            Outdated => manuals::outdated::MANUAL,
             Test => manuals::test::MANUAL,
             Build => manuals::build::MANUAL,
+            Version => manuals::version::MANUAL,
             Publish => manuals::publish::MANUAL,
             Search => manuals::search::MANUAL,
             Install => manuals::install::MANUAL,
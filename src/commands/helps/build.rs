@@ -31,6 +31,8 @@ Options:
     --all                 include all hdl files of the working ip
     --fileset <key=glob>...
                           a glob-style pattern identified by name to include in the blueprint
+    --define <macro>...   define a Verilog macro as NAME or NAME=VALUE when planning
+    --add-missing         add the ips that provide any missing units as dependencies
     --force               force the target to execute 
     --no-clean            do not clean the target folder before execution
     --verbose             display the command being executed
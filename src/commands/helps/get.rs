@@ -27,7 +27,7 @@ Arguments:
 Options:
     --ip <spec>           ip specification
     --json                export the unit's information as valid json
-    --library, -l         display the unit's library declaration
+    --library, -l         display the unit's library declaration (or package's use clause)
     --component, -c       display the unit's declaration
     --signals, -s         display the constant and signal declarations
     --instance, -i        display the unit's instantiation
@@ -37,5 +37,7 @@ Options:
                           prepend information to the instance's signals
     --signal-suffix <str>
                           append information to the instance's signals
+    --testbench           display a testbench skeleton for the unit
+    --lang <hdl>          translate the code snippets into 'vhdl', 'verilog', or 'sv'
 
 Use 'orbit help get' to read more about the command."#;
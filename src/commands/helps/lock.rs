@@ -23,5 +23,9 @@ Usage:
 
 Options:
     --force               ignore reading the precomputed lock file
+    --warn-unused         report any dependencies that are unused
+    --deny-unused         exit with an error if any dependencies are unused
+    --target, -t <name>   read Verilog files with the macros defined by a target
+    --define <macro>...   define a Verilog macro as NAME or NAME=VALUE
 
 Use 'orbit help lock' to read more about the command."#;
@@ -15,6 +15,7 @@
 //  along with this program.  If not, see <http://www.gnu.org/licenses/>.
 //
 
+pub mod add;
 pub mod build;
 pub mod config;
 pub mod env;
@@ -24,10 +25,14 @@ pub mod install;
 pub mod lock;
 pub mod new;
 pub mod orbit;
+pub mod outdated;
 pub mod publish;
 pub mod read;
 pub mod remove;
+pub mod rm;
 pub mod search;
 pub mod test;
 pub mod tree;
+pub mod update;
+pub mod version;
 pub mod view;
@@ -29,6 +29,8 @@ Commands:
     get                   fetch an hdl unit for code integration
     tree                  show the dependency graph
     lock                  save the world state of an ip
+    update                upgrade locked dependencies to newer versions
+    outdated              report dependencies with newer versions
     test, t               run a test
     build, b              plan and execute a target
     publish               post an ip to a channel
@@ -24,6 +24,8 @@ Usage:
 Options:
     --ready, -y           run the operation to completion
     --no-install          do not install the ip for future use
+    --allow-breaking      warn instead of error on breaking interface changes
     --list                view available channels and exit
+    --yank <version>      mark a published version as yanked and exit
 
 Use 'orbit help publish' to read more about the command."#;
@@ -32,6 +32,8 @@ Options:
     --all                 include all hdl files of the working ip
     --fileset <key=glob>...
                           a glob-style pattern identified by name to include in the blueprint
+    --define <macro>...   define a Verilog macro as NAME or NAME=VALUE when planning
+    --add-missing         add the ips that provide any missing units as dependencies
     --no-clean            do not clean the target folder before execution
     --force               force the target to execute 
     --verbose             display the command being executed
@@ -23,8 +23,14 @@ Usage:
 
 Options:
     <unit>...             uppermost hdl unit of the dependency tree
+    --compress            collapse repeated branches into a single line
+    --depth <n>           limit the number of levels displayed below the root
     --format <fmt>        determine how to display nodes ('long', 'short')
     --ascii               limit the textual tree characters to the 128 ASCII set
     --ip                  switch to the ip dependency graph
+    --invert <unit>       show the units (or ips) that depend on <unit>
+    --output <fmt>        export the graph as 'dot', 'graphml', or 'json'
+    --target, -t <name>   read Verilog files with the macros defined by a target
+    --define <macro>...   define a Verilog macro as NAME or NAME=VALUE
 
 Use 'orbit help tree' to read more about the command."#;
@@ -49,13 +49,14 @@ use crate::core::ip::Ip;
 use crate::core::ip::IpSpec;
 use crate::core::ip::PartialIpSpec;
 use crate::core::iparchive::IpArchive;
+use crate::core::lang::cache::UnitCache;
 use crate::core::lockfile::LockEntry;
 use crate::core::manifest::IP_MANIFEST_FILE;
 use crate::core::protocol::Protocol;
 use crate::core::protocol::ProtocolError;
+use crate::core::protocol::GIT_PROTOCOL;
 use crate::core::source::Source;
 use crate::core::swap::StrSwapTable;
-use crate::core::version;
 use crate::core::version::AnyVersion;
 use crate::error::Error;
 use crate::error::Hint;
@@ -111,6 +112,7 @@ impl Subcommand<Context> for Install {
             // verify the plugin alias matches
             Some(name) => match c.get_config().get_protocols().get(name.as_str()) {
                 Some(&p) => Some(p),
+                None if name == GIT_PROTOCOL => None,
                 None => return Err(ProtocolError::Missing(name.to_string()))?,
             },
             None => None,
@@ -147,6 +149,7 @@ impl Subcommand<Context> for Install {
         } else {
             Catalog::new()
                 .installations(c.get_cache_path())?
+                .shared_installations(c.get_shared_cache_paths())?
                 .downloads(c.get_downloads_path())?
                 .available(&c.get_config().get_channels())?
         };
@@ -188,11 +191,9 @@ impl Subcommand<Context> for Install {
                     true => {
                         let ip = Ip::load(search_dir.to_path_buf(), true)?;
                         if ip.get_man().get_ip().get_name() == entry.get_name()
-                            && (entry.get_version().is_latest()
-                                || version::is_compatible(
-                                    entry.get_version().as_specific().unwrap(),
-                                    ip.get_man().get_ip().get_version(),
-                                ))
+                            && entry
+                                .get_version()
+                                .matches(ip.get_man().get_ip().get_version())
                         {
                             ip
                         } else {
@@ -228,6 +229,7 @@ impl Subcommand<Context> for Install {
                         .into_ip_spec()
                         .to_partial_ip_spec(),
                 ),
+                None,
                 true,
             )?;
             provided_spec = Some(spec.to_partial_ip_spec());
@@ -312,6 +314,7 @@ impl Subcommand<Context> for Install {
             Some(t) => t,
             None => return Err(Error::Custom(format!("failed to find an ip to install")))?,
         };
+        target.warn_if_deprecated();
 
         // println!("{:?}", target.get_uuid());
 
@@ -422,6 +425,12 @@ impl Install {
     ) -> Result<Catalog<'c>, Fault> {
         let mut catalog = catalog;
 
+        // only keep a unit cache in the target directory of the working ip
+        let mut cache = match c.get_ip_path() == Some(local_ip.get_root()) {
+            true => UnitCache::open(&local_ip.get_root().join(c.get_target_dir())),
+            false => UnitCache::new(),
+        };
+
         // verify the lock file is generated and up to date
         if force == false {
             println!("info: {}", "verifying lockfile is up to date ...");
@@ -431,7 +440,7 @@ impl Install {
             }
         // create the lockfile
         } else if local_ip.can_use_lock(&catalog) == false {
-            let ip_graph = algo::compute_final_ip_graph(&local_ip, &catalog)?;
+            let ip_graph = algo::compute_final_ip_graph(&local_ip, &catalog, &mut cache)?;
             Plan::write_lockfile(&local_ip, &ip_graph, true, true, &catalog)?;
         }
 
@@ -446,7 +455,14 @@ impl Install {
 
         let lf = local_ip.get_lock().keep_dev_dep_entries(&local_ip, all);
 
-        plan::download_missing_deps(vtable, &lf, &le, &catalog, &c.get_config().get_protocols())?;
+        plan::download_missing_deps(
+            vtable,
+            &lf,
+            &le,
+            &catalog,
+            &c.get_config().get_protocols(),
+            &c.get_config().get_download_settings(),
+        )?;
 
         // recollect the queued items to update the catalog
         catalog = catalog.downloads(c.get_downloads_path())?;
@@ -465,7 +481,9 @@ impl Install {
 
         // verify the graph build with no errors
         println!("info: {}", "verifying hardware graph construction ...");
-        if let Err(e) = Publish::check_graph_builds_okay(&local_ip, &catalog) {
+        let graph = Publish::check_graph_builds_okay(&local_ip, &catalog, &mut cache);
+        cache.save()?;
+        if let Err(e) = graph {
             return Err(Box::new(Error::PublishHdlGraphFailed(LastError(
                 e.to_string(),
             ))))?;
@@ -502,6 +520,7 @@ impl Install {
             None,
             c.get_downloads_path(),
             &protocols,
+            &c.get_config().get_download_settings(),
             verbose,
             force,
         )?;
@@ -530,6 +549,7 @@ impl Install {
             None,
             c.get_downloads_path(),
             &protocols,
+            &c.get_config().get_download_settings(),
             self.verbose,
             self.force,
         )?;
@@ -657,6 +677,10 @@ impl Install {
 
         // write the checksum to the directory (this file is excluded from auditing)
         installed_ip.write_cache_checksum(&checksum)?;
+        // remember the commit the ip was fetched from (this file is excluded from auditing)
+        if let Some(commit) = Ip::read_cache_commit(src.get_root()) {
+            installed_ip.write_cache_commit(&commit)?;
+        }
         // write the metadata
         installed_ip.write_cache_metadata()?;
 
@@ -18,19 +18,29 @@
 use super::plan::{self, Plan};
 use crate::commands::helps::lock;
 use crate::core::algo;
+use crate::core::algo::IpNode;
 use crate::core::catalog::Catalog;
 use crate::core::context::Context;
-use crate::core::ip::Ip;
+use crate::core::ip::{Ip, IpSpec};
+use crate::core::lang::cache::UnitCache;
+use crate::core::lang::verilog::preprocess::{self, Define};
 use crate::core::lockfile::LockEntry;
 use crate::core::swap::StrSwapTable;
+use crate::error::{Error, Hint};
 use crate::util::anyerror::Fault;
 use crate::util::environment::Environment;
+use crate::util::graphmap::GraphMap;
 use cliproc::{cli, proc, stage::*};
 use cliproc::{Arg, Cli, Help, Subcommand};
+use colored::Colorize;
 
 #[derive(Debug, PartialEq)]
 pub struct Lock {
     force: bool,
+    warn_unused: bool,
+    deny_unused: bool,
+    target: Option<String>,
+    defines: Option<Vec<Define>>,
 }
 
 impl Subcommand<Context> for Lock {
@@ -39,6 +49,11 @@ impl Subcommand<Context> for Lock {
         let command = Ok(Lock {
             // flags
             force: cli.check(Arg::flag("force"))?,
+            warn_unused: cli.check(Arg::flag("warn-unused"))?,
+            deny_unused: cli.check(Arg::flag("deny-unused"))?,
+            // options
+            target: cli.get(Arg::option("target").value("name").switch('t'))?,
+            defines: cli.get_all(Arg::option("define").value("macro"))?,
         });
         command
     }
@@ -47,12 +62,28 @@ impl Subcommand<Context> for Lock {
         // check that user is in an IP directory
         c.jump_to_working_ip()?;
 
+        // read verilog files with the target's macros and any macros from the command-line
+        let unused = match self.warn_unused || self.deny_unused {
+            true => {
+                let target = c.select_target(&self.target, false, true)?;
+                Some(UnusedCheck {
+                    deny: self.deny_unused,
+                    defines: preprocess::merge_defines(
+                        target.map_or(&[], |t| t.get_defines()),
+                        self.defines.as_deref().unwrap_or(&[]),
+                    ),
+                })
+            }
+            false => None,
+        };
+
         // store the working ip struct
         let working_ip = Ip::load(c.get_ip_path().unwrap().clone(), true)?;
 
         // assemble the catalog
         let mut catalog = Catalog::new()
             .installations(c.get_cache_path())?
+            .shared_installations(c.get_shared_cache_paths())?
             .downloads(c.get_downloads_path())?;
 
         // TODO: recreate the ip graph from the lockfile, then read each installation
@@ -74,6 +105,7 @@ impl Subcommand<Context> for Lock {
                 &le,
                 &catalog,
                 &c.get_config().get_protocols(),
+                &c.get_config().get_download_settings(),
             )?;
             // recollect the downloaded items to update the catalog for installations
             catalog = catalog.downloads(c.get_downloads_path())?;
@@ -83,31 +115,104 @@ impl Subcommand<Context> for Lock {
             catalog = catalog.installations(c.get_cache_path())?;
         }
 
-        Self::run(&working_ip, &catalog, self.force)
+        // reuse the design units of any unchanged files from previous runs
+        let mut cache = UnitCache::open(&working_ip.get_root().join(c.get_target_dir()));
+
+        let result = Self::run(
+            &working_ip,
+            &catalog,
+            self.force,
+            unused.as_ref(),
+            &mut cache,
+        );
+        cache.save()?;
+        result
     }
 }
 
+/// The check for dependencies whose design units are never used by the local ip.
+#[derive(Debug, PartialEq)]
+pub struct UnusedCheck {
+    /// Report unused dependencies as an error rather than a warning.
+    deny: bool,
+    /// The macros to read Verilog files with.
+    defines: Vec<Define>,
+}
+
 impl Lock {
     /// Performs the backend logic for creating a blueprint file (planning a design).
-    pub fn run(working_ip: &Ip, catalog: &Catalog, force: bool) -> Result<(), Fault> {
+    ///
+    /// Any design units already stored in the `cache` are not parsed again. If
+    /// given, the `unused` check runs before the lockfile is written, so the
+    /// lockfile is left unchanged when the check fails.
+    pub fn run(
+        working_ip: &Ip,
+        catalog: &Catalog,
+        force: bool,
+        unused: Option<&UnusedCheck>,
+        cache: &mut UnitCache,
+    ) -> Result<(), Fault> {
         // build entire ip graph and resolve with dynamic symbol transformation
-        let ip_graph = match algo::compute_final_ip_graph(&working_ip, &catalog) {
+        let ip_graph = match algo::compute_final_ip_graph(&working_ip, &catalog, cache) {
             Ok(g) => g,
             Err(e) => return Err(e)?,
         };
 
+        if let Some(check) = unused {
+            Self::check_unused_deps(working_ip, &ip_graph, check, cache)?;
+        }
+
         // only write lockfile and exit if flag is raised
         Plan::write_lockfile(&working_ip, &ip_graph, force, true, &catalog)?;
         Ok(())
     }
 
+    /// Reports the dependencies of `working_ip` whose units are never used.
+    ///
+    /// Errors if any are found and the `check` denies them.
+    fn check_unused_deps(
+        working_ip: &Ip,
+        ip_graph: &GraphMap<IpSpec, IpNode, ()>,
+        check: &UnusedCheck,
+        cache: &mut UnitCache,
+    ) -> Result<(), Fault> {
+        let files = algo::build_ip_file_list(ip_graph, working_ip);
+        let global_graph = Plan::build_full_graph(&files, &check.defines, cache)?;
+
+        let unused = Plan::find_unused_deps(working_ip, ip_graph, &global_graph);
+        if unused.is_empty() == true {
+            return Ok(());
+        }
+        match check.deny {
+            true => Err(Error::UnusedDependencies(
+                unused
+                    .iter()
+                    .map(|n| format!("\"{}\"", n))
+                    .collect::<Vec<String>>()
+                    .join(", "),
+                Hint::RemoveUnusedDeps,
+            ))?,
+            false => {
+                for name in unused {
+                    println!(
+                        "{} dependency \"{}\" is never used by the local ip",
+                        "warning:".yellow(),
+                        name
+                    );
+                }
+                Ok(())
+            }
+        }
+    }
+
     pub fn write_new_lockfile(local_ip: &Ip) -> Result<(), Fault> {
         // build entire ip graph and resolve with dynamic symbol transformation
         let catalog = Catalog::new();
-        let ip_graph = match algo::compute_final_ip_graph(&local_ip, &catalog) {
-            Ok(g) => g,
-            Err(e) => return Err(e)?,
-        };
+        let ip_graph =
+            match algo::compute_final_ip_graph(&local_ip, &catalog, &mut UnitCache::new()) {
+                Ok(g) => g,
+                Err(e) => return Err(e)?,
+            };
         Plan::write_lockfile(&local_ip, &ip_graph, true, false, &catalog)?;
         Ok(())
     }
@@ -0,0 +1,69 @@
+//
+//  Copyright (C) 2022-2024  Chase Ruskin
+//
+//  This program is free software: you can redistribute it and/or modify
+//  it under the terms of the GNU General Public License as published by
+//  the Free Software Foundation, either version 3 of the License, or
+//  (at your option) any later version.
+//
+//  This program is distributed in the hope that it will be useful,
+//  but WITHOUT ANY WARRANTY; without even the implied warranty of
+//  MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+//  GNU General Public License for more details.
+//
+//  You should have received a copy of the GNU General Public License
+//  along with this program.  If not, see <http://www.gnu.org/licenses/>.
+//
+
+// This manual page was automatically generated from the mangen.py tool.
+pub const MANUAL: &str = r#"NAME
+    add - add a dependency to the manifest
+
+SYNOPSIS
+    orbit add [options] <ip>
+
+DESCRIPTION
+    Adds '<ip>' as a dependency to the local ip's manifest file, "Orbit.toml". The
+    manifest is edited in place, so its existing formatting and comments are
+    kept.
+    
+    The newest version of the ip found in the catalog is selected, including any
+    ip available through the configured channels. A version can be given in the
+    '<ip>' spec to select the newest version that matches it. The entry is written
+    as a requirement on the selected version's major and minor numbers, such as
+    'gates = "2.1"'.
+    
+    By default, the entry is written to the '[dependencies]' table. Use '--dev' to
+    write it to the '[dev-dependencies]' table instead. If the ip is already
+    listed in either table, its entry is replaced.
+    
+    To depend on an ip that exists elsewhere on the file system, use '--path'. The
+    path is resolved from the current directory and written relative to the local
+    ip's root directory. The version is read from the ip found at that path.
+    
+    If more than one ip in the catalog shares the same name, use '--uuid' to
+    select the correct ip. The uuid is also written to the entry. The '--uuid'
+    option cannot be used with '--path'.
+    
+    After editing the manifest, the lock file, "Orbit.lock", is updated. If the
+    lock file cannot be updated, the manifest is left unchanged.
+
+OPTIONS
+    <ip>
+        The spec of the ip to add
+
+    --dev
+        Add the ip to the [dev-dependencies] table
+
+    --path <path>
+        Add the ip found at a relative file system path
+
+    --uuid <id>
+        Select the ip with the matching uuid
+
+EXAMPLES
+    orbit add gates
+    orbit add gates:1.0
+    orbit add testkit --dev
+    orbit add uart --path ../uart
+"#;
@@ -23,7 +23,7 @@ SYNOPSIS
     orbit get [options] <unit>
 
 DESCRIPTION
-    Returns hdl code snippets for the provided design unit to be integrated into
+    Returns hdl code snippets for the provided design unit to be integrated into 
     the current design. The code snippets are returned in the native hdl
     language of the identified design unit. Code snippets are designed to be
     copy and pasted from the console to the current design for quick code 
@@ -60,6 +60,27 @@ DESCRIPTION
     Exporting the unit's declaration information can be accomplished by using the
     '--json' option. The valid json is produced with minimal formatting for
     encouragement to be processed by other programs.
+    
+    To integrate a design unit into a design written in a different language, use
+    the '--lang' option to translate the code snippets into "vhdl", "verilog", or 
+    "sv". Port modes, generics and parameters, and common types (such as 
+    'std_logic_vector' ranges and packed ranges) are mapped to their closest 
+    equivalents. A Verilog module translated into VHDL is instantiated as a 
+    component. Default values that cannot be translated are omitted.
+    
+    To start a new testbench for the design unit, use the '--testbench' option. It
+    displays a complete testbench file that declares the unit's constants and 
+    signals, instantiates the unit as the device under test, drives a clock and
+    reset for ports detected by name (such as "clk" and "rst_n"), and stops the 
+    simulation at the end of a stimulus stub.
+    
+    When the design unit is a VHDL or SystemVerilog package, this command lists the
+    package's public declarations instead. For VHDL, these are its constants, 
+    types, subtypes, function and procedure signatures, and components. For 
+    SystemVerilog, these are its parameters, typedefs, function and task 
+    signatures, and classes. Use '--library' to display the clause for using the
+    package, and '--json' to export its declarations. Options that instantiate a 
+    unit cannot be used with a package.
 
 OPTIONS
     <unit>
@@ -72,7 +93,7 @@ OPTIONS
         Export the unit's information as valid json
 
     --library, -l
-        Display the unit's library declaration
+        Display the unit's library declaration (or package's use clause)
 
     --component, -c
         Display the unit's declaration
@@ -95,9 +116,18 @@ OPTIONS
     --signal-suffix <str>
         Append information to the instance's signals
 
+    --testbench
+        Display a testbench skeleton for the unit
+
+    --lang <hdl>
+        Translate the code snippets into 'vhdl', 'verilog', or 'sv'
+
 EXAMPLES
     orbit get and_gate --ip gates:1.0.0 --component
     orbit get ram --ip mem:2 -csi
     orbit get uart -si --name uart_inst0
     orbit get or_gate --ip gates --json
+    orbit get fifo --lang sv -si
+    orbit get alu --testbench --name dut
+    orbit get math_pkg --ip base2 -l
 "#;
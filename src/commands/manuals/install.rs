@@ -37,6 +37,11 @@ DESCRIPTION
     be supplied to help the protocol with providing any additional information it
     may require. 
     
+    The "git" protocol is built into orbit and does not need to be configured. It
+    clones the repository at '--url' and checks out the tag, branch, or commit given
+    by '--tag', or the repository's default branch when no tag is given. A
+    user-defined protocol named "git" takes precedence over the built-in protocol.
+    
     To remove ip from the catalog, see the 'remove' command.
 
 OPTIONS
@@ -71,5 +76,6 @@ EXAMPLES
     orbit install
     orbit install lcd_driver:2.0
     orbit install adder:1.0.0 --url https://my.adder/project.zip
+    orbit install gates:1.0.0 --url https://my.gates/project.git --protocol git --tag 1.0.0
     orbit install alu:2.3.7 --path ./projects/alu --force
 "#;
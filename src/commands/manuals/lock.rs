@@ -38,12 +38,35 @@ DESCRIPTION
     unresolved ip dependencies. If an installed dependency's computed checksum 
     does not match the checksum stored in the lock file, it assumes the 
     installation is corrupt and will reinstall the dependency to the cache.
+    
+    With '--warn-unused' or '--deny-unused', Orbit reports any dependency listed in
+    the manifest whose design units are never referenced by the local ip before
+    the lock file is written. Every unit of the local ip is considered as a 
+    possible top, so a dependency is only reported if no unit of the local ip 
+    reaches any of its units. Use '--deny-unused' to treat unused dependencies as 
+    an error, such as when running in CI, which leaves the lock file unchanged. 
+    Verilog files are read with the macros of the '--target' option and any 
+    '--define' options, so the same units are found as when building with that 
+    target.
 
 OPTIONS
     --force
         Ignore reading the precomputed lock file
 
+    --warn-unused
+        Report any dependencies that are unused
+
+    --deny-unused
+        Exit with an error if any dependencies are unused
+
+    --target, -t <name>
+        Read verilog files with the macros defined by a target
+
+    --define <macro>...
+        Define a verilog macro as name or name=value
+
 EXAMPLES
     orbit lock
     orbit lock --force
+    orbit lock --deny-unused
 "#;
@@ -0,0 +1,48 @@
+//
+//  Copyright (C) 2022-2024  Chase Ruskin
+//
+//  This program is free software: you can redistribute it and/or modify
+//  it under the terms of the GNU General Public License as published by
+//  the Free Software Foundation, either version 3 of the License, or
+//  (at your option) any later version.
+//
+//  This program is distributed in the hope that it will be useful,
+//  but WITHOUT ANY WARRANTY; without even the implied warranty of
+//  MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+//  GNU General Public License for more details.
+//
+//  You should have received a copy of the GNU General Public License
+//  along with this program.  If not, see <http://www.gnu.org/licenses/>.
+//
+
+// This manual page was automatically generated from the mangen.py tool.
+pub const MANUAL: &str = r#"NAME
+    outdated - report dependencies with newer versions
+
+SYNOPSIS
+    orbit outdated [options]
+
+DESCRIPTION
+    Displays the version drift for each direct and transitive dependency listed in
+    the local ip's lock file, "Orbit.lock".
+    
+    For every dependency, three versions are reported: the version currently 
+    locked, the latest version that is compatible with the version requirements
+    listed in the manifest file, "Orbit.toml", and the absolute latest version.
+    Versions are gathered from the ip's installations, downloads, and any ip 
+    available through the configured channels.
+    
+    A local ip must exist for this command to execute and its lock file must 
+    already be created. See the 'lock' command for creating a lock file.
+    
+    Use '--json' to print the report in a machine-readable format. To upgrade the
+    locked versions to the compatible versions, see the 'update' command.
+
+OPTIONS
+    --json
+        Print the report in json format
+
+EXAMPLES
+    orbit outdated
+    orbit outdated --json
+"#;
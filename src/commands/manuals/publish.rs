@@ -23,15 +23,25 @@ SYNOPSIS
     orbit publish [options]
 
 DESCRIPTION
-    Performs a series of checks for a local ip and then releases it to its
+    Performs a series of checks for a local ip and then releases it to its 
     specified channel(s).
     
     There are multiple checks that are performed before an ip can be published. 
     First, the ip must have an up to date lockfile with no relative dependencies. 
     The ip's manifest must also have a value for the source field. In addition,
     Orbit must be able to construct the hdl source code graph without errors.
-    Finally, the ip is downloaded from its source url and temporarily installed
-    to verify its contents match those of the local ip.
+    Orbit then compares the public design units against the highest version of
+    the ip published before it. A removed unit, a removed or renamed port or
+    generic, a changed port direction, and an added input port or generic without
+    a default value are breaking changes. Breaking changes are an error unless the
+    major version is incremented, or the minor version for versions below 1.0.0.
+    Use '--allow-breaking' to report them as warnings instead. The previous
+    version is read from its installation, from its downloaded archive, or by
+    downloading it from the source listed in its channel. If its source code is
+    not available, the check fails unless '--allow-breaking' is used. Finally, 
+    the ip is downloaded from
+    its source url and temporarily installed to verify its contents match those
+    of the local ip.
     
     Posting an ip to a channel involves copying the ip's manifest file to a path 
     within the channel known as the index. For every publish of an ip, the index 
@@ -42,6 +52,11 @@ DESCRIPTION
     By default, this command performs a dry run, which executes all of the steps 
     in the process except for actually posting the ip to its channel(s). 
     To run the command to completion, use the '--ready' option.
+    
+    Use '--yank' to mark an already published version of the local ip as yanked
+    in each of its channel(s) that hold that version. A yanked version is never selected for new version
+    requirements, but it can still be resolved by existing lockfile entries.
+    Yanking skips the publishing checks and takes effect immediately.
 
 OPTIONS
     --ready, -y
@@ -50,10 +65,17 @@ OPTIONS
     --no-install
         Do not install the ip for future use
 
+    --allow-breaking
+        Warn instead of error on breaking interface changes
+
     --list
         View available channels and exit
 
+    --yank <version>
+        Mark a published version as yanked and exit
+
 EXAMPLES
     orbit publish
     orbit publish --ready
+    orbit publish --yank 1.2.3
 "#;
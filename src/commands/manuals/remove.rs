@@ -25,7 +25,7 @@ SYNOPSIS
 DESCRIPTION
     Deletes save data for a known ip from the catalog. The ip's data for its
     particular version is removed from the catalog's cache and the catalog's
-    archive.
+    archive. An ip installed in a read-only shared cache cannot be removed.
     
     By default, an interactive prompt will appear to confirm with the user if the 
     correct ip is okay to be removed. To skip this interactive prompt and assume
@@ -0,0 +1,45 @@
+//
+//  Copyright (C) 2022-2024  Chase Ruskin
+//
+//  This program is free software: you can redistribute it and/or modify
+//  it under the terms of the GNU General Public License as published by
+//  the Free Software Foundation, either version 3 of the License, or
+//  (at your option) any later version.
+//
+//  This program is distributed in the hope that it will be useful,
+//  but WITHOUT ANY WARRANTY; without even the implied warranty of
+//  MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+//  GNU General Public License for more details.
+//
+//  You should have received a copy of the GNU General Public License
+//  along with this program.  If not, see <http://www.gnu.org/licenses/>.
+//
+
+// This manual page was automatically generated from the mangen.py tool.
+pub const MANUAL: &str = r#"NAME
+    rm - remove a dependency from the manifest
+
+SYNOPSIS
+    orbit rm <ip>...
+
+DESCRIPTION
+    Removes each '<ip>' from the '[dependencies]' and '[dev-dependencies]' tables
+    of the local ip's manifest file, "Orbit.toml". The manifest is edited in place,
+    so its existing formatting and comments are kept.
+    
+    Every '<ip>' must be listed in the manifest. If one is not found, then the
+    manifest is left unchanged.
+    
+    After editing the manifest, the lock file, "Orbit.lock", is updated. If the
+    lock file cannot be updated, the manifest is left unchanged.
+    
+    To delete an ip from the catalog, see the 'remove' command.
+
+OPTIONS
+    <ip>...
+        Name of a dependency to remove
+
+EXAMPLES
+    orbit rm gates
+    orbit rm gates testkit
+"#;
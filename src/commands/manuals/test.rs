@@ -51,6 +51,12 @@ DESCRIPTION
     
     The target's process will spawn from the current working ip's output directory,
     which is $ORBIT_TARGET_DIR/$ORBIT_TARGET.
+    
+    When planning finds a unit that is instantiated but not defined by any ip in
+    the dependency graph, the installed ips are searched for a public unit with
+    the same name. Each ip found is suggested as a dependency to add. Use 
+    '--add-missing' to write the ips found into the local ip's manifest before
+    planning. Ips that are only downloaded are not searched until installed.
 
 OPTIONS
     --target, -t <name>
@@ -80,6 +86,12 @@ OPTIONS
     --fileset <key=glob>...
         A glob-style pattern identified by name to include in the blueprint
 
+    --define <macro>...
+        Define a verilog macro as name or name=value when planning
+
+    --add-missing
+        Add the ips that provide any missing units as dependencies
+
     --no-clean
         Do not clean the target folder before execution
 
@@ -34,24 +34,58 @@ DESCRIPTION
     There are two trees available to view: hdl and ip. By default, the hdl
     dependency graph is displayed. The hdl graph shows the composition of usable 
     entities/modules. To generate this graph, it analyzes each VHDL architecture 
-    and ignores Verilog compiler directives. If an unidentified entity is 
+    and each preprocessed Verilog module. If an unidentified entity is 
     instantiated, it will appear as a leaf in the graph and will be considered as 
     a "black box" denoted by the "?" character next to its position in the tree.
     
+    Verilog and SystemVerilog files are preprocessed with the macros set by the
+    '--target' option's defines and any '--define' options, where a macro from 
+    the command-line replaces a target's macro of the same name. Included header
+    files are expanded while preprocessing.
+    
     Using the '--format' option can alter how much information is displayed for
     each hdl design unit in the tree composition. By default, only the design
     unit's name is displayed for each unit.
     
     To display the ip dependency graph, use the '--ip' option.
     
+    Large designs often reuse the same subtree in many places. Using the 
+    '--compress' option only expands a node's branches at its first appearance 
+    and collapses every later appearance into a single line. Units are labeled 
+    with their total number of instances below the root, such as 
+    "full_adder (x32)". Instances are counted as they are written in the source 
+    code, so an instance within a generate statement is counted once. When using 
+    '--invert', the label is the number of instances of '<unit>' found within 
+    the displayed unit. Use the '--depth' option to hide nodes more than '<n>' 
+    levels below the root, which also applies to the '--output' option.
+    
     If the tree's character output is not displaying properly, then the tree can
     be displayed using a set of standard ASCII characters with the '--ascii'
     option.
+    
+    To find what depends on a particular unit, use the '--invert' option. The 
+    inverted tree starts from '<unit>' and shows every unit that uses it, 
+    transitively, up to the top levels and testbenches of the local ip. Units from
+    the local ip and its dependencies are searched. When used with the '--ip' 
+    option, '<unit>' is the name of an ip and the tree shows every ip that depends
+    on it.
+    
+    To export the graph for other tools, use the '--output' option to print the 
+    graph as "dot", "graphml", or "json" instead of a tree. Each node carries its
+    library, source file, language, and owning ip when known. Each edge is 
+    directed from a unit to a unit that it instantiates or uses. When used with
+    the '--ip' option, nodes are the ips and edges are their dependencies.
 
 OPTIONS
     <unit>...
         Uppermost hdl unit of the dependency tree
 
+    --compress
+        Collapse repeated branches into a single line
+
+    --depth <n>
+        Limit the number of levels displayed below the root
+
     --format <fmt>
         Determine how to display nodes ('long', 'short')
 
@@ -61,8 +95,24 @@ OPTIONS
     --ip
         Switch to the ip dependency graph
 
+    --invert <unit>
+        Show the units (or ips) that depend on <unit>
+
+    --output <fmt>
+        Export the graph as 'dot', 'graphml', or 'json'
+
+    --target, -t <name>
+        Read verilog files with the macros defined by a target
+
+    --define <macro>...
+        Define a verilog macro as name or name=value
+
 EXAMPLES
     orbit tree
     orbit tree top --format long
     orbit tree --ip --ascii
+    orbit tree --compress --depth 3
+    orbit tree top --output dot
+    orbit tree --invert fifo
+    orbit tree --define USE_FIFO --define WIDTH=8
 "#;
@@ -0,0 +1,56 @@
+//
+//  Copyright (C) 2022-2025  Chase Ruskin
+//
+//  This program is free software: you can redistribute it and/or modify
+//  it under the terms of the GNU General Public License as published by
+//  the Free Software Foundation, either version 3 of the License, or
+//  (at your option) any later version.
+//
+//  This program is distributed in the hope that it will be useful,
+//  but WITHOUT ANY WARRANTY; without even the implied warranty of
+//  MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+//  GNU General Public License for more details.
+//
+//  You should have received a copy of the GNU General Public License
+//  along with this program.  If not, see <http://www.gnu.org/licenses/>.
+//
+
+// This manual page was automatically generated from the mangen.py tool.
+pub const MANUAL: &str = r#"NAME
+    update - upgrade locked dependencies to newer versions
+
+SYNOPSIS
+    orbit update [options] [<ip>...]
+
+DESCRIPTION
+    Re-resolves entries of the local ip's lock file, "Orbit.lock", to the newest
+    versions found in the catalog that still satisfy the version requirements
+    listed in the manifest file, "Orbit.toml".
+    
+    If one or more '<ip>' are provided, only the lock entries with those names
+    are updated. If no '<ip>' is provided, then every dependency in the lock file
+    is updated. All other entries in the lock file are left untouched.
+    
+    A local ip must exist for this command to execute and its lock file must 
+    already be created. See the 'lock' command for creating a lock file.
+    
+    Each entry that changes is displayed with its old and new version. Use 
+    '--dry-run' to display the changes without downloading, installing, or 
+    writing any changes to the lock file.
+    
+    If a newer version requires an ip that is not already in the lock file, no
+    changes are written. Use 'orbit lock --force' to resolve the new dependencies
+    instead.
+
+OPTIONS
+    <ip>
+        Name of a dependency to update
+
+    --dry-run
+        Display the changes without modifying the lock file
+
+EXAMPLES
+    orbit update
+    orbit update gates
+    orbit update gates mux --dry-run
+"#;
@@ -0,0 +1,64 @@
+//
+//  Copyright (C) 2022-2024  Chase Ruskin
+//
+//  This program is free software: you can redistribute it and/or modify
+//  it under the terms of the GNU General Public License as published by
+//  the Free Software Foundation, either version 3 of the License, or
+//  (at your option) any later version.
+//
+//  This program is distributed in the hope that it will be useful,
+//  but WITHOUT ANY WARRANTY; without even the implied warranty of
+//  MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+//  GNU General Public License for more details.
+//
+//  You should have received a copy of the GNU General Public License
+//  along with this program.  If not, see <http://www.gnu.org/licenses/>.
+//
+
+// This manual page was automatically generated from the mangen.py tool.
+pub const MANUAL: &str = r#"NAME
+    version - bump the version of an ip
+
+SYNOPSIS
+    orbit version [options] <version>
+
+DESCRIPTION
+    Sets the version of the local ip in its manifest file, "Orbit.toml". The
+    manifest is edited in place, so its existing formatting and comments are
+    kept.
+    
+    The '<version>' can be "major", "minor", or "patch" to increment that part of
+    the current version. Incrementing a part resets the parts after it to 0 and
+    drops any label. If the current version is a pre-release whose parts after
+    the incremented part are already 0, only its label is dropped, so a "patch"
+    of "1.3.0-beta" is "1.3.0". An explicit version, such as "1.4.0", can also be
+    given. The new version must be greater than the current version.
+    
+    Use '--label' to attach a pre-release label to the new version, such as
+    "1.4.0-beta".
+    
+    If the lock file, "Orbit.lock", exists, then its entry for the local ip is
+    updated to the new version.
+    
+    Use '--tag' to run 'git add' and 'git commit' on "Orbit.toml" and "Orbit.lock"
+    and then create an annotated git tag named after the new version in the local
+    ip's repository. Other staged changes are not included in the commit. Nothing
+    is changed if the local ip is not inside a git repository, if either file has
+    uncommitted changes, if git has no user identity to commit with, or if the tag
+    already exists.
+
+OPTIONS
+    <version>
+        The part to increment ('major', 'minor', 'patch') or a version
+
+    --label <label>
+        Attach a pre-release label to the new version
+
+    --tag
+        Run git add and commit on orbit.toml and orbit.lock, then tag
+
+EXAMPLES
+    orbit version patch
+    orbit version minor --label beta
+    orbit version 2.0.0 --tag
+"#;
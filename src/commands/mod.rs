@@ -19,6 +19,7 @@
 pub mod orbit;
 
 // commands
+mod add;
 mod build;
 mod config;
 mod download;
@@ -28,13 +29,17 @@ mod help;
 mod init;
 mod install;
 mod new;
+mod outdated;
 mod plan;
 mod publish;
 mod read;
 mod remove;
+mod rm;
 mod search;
 mod test;
 mod tree;
+mod update;
+mod version;
 mod view;
 
 // informational content for help about commands
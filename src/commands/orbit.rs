@@ -116,7 +116,10 @@ impl Command for Orbit {
         // prioritize upgrade information
         } else if self.upgrade == true {
             println!("info: checking for latest orbit binary...");
-            let info = self.upgrade()?;
+            let context = Context::new()
+                .home(environment::ORBIT_HOME)?
+                .settings(config::CONFIG_FILE)?;
+            let info = self.upgrade(&context.get_config().get_download_settings())?;
             println!("info: {}", info);
             Ok(())
         // run the specified command
@@ -153,6 +156,7 @@ impl Command for Orbit {
     }
 }
 
+use crate::commands::add::Add;
 use crate::commands::build::Build;
 use crate::commands::config::Config;
 use crate::commands::env::Env;
@@ -162,12 +166,16 @@ use crate::commands::init::Init;
 use crate::commands::install::Install;
 use crate::commands::lock::Lock;
 use crate::commands::new::New;
+use crate::commands::outdated::Outdated;
 use crate::commands::publish::Publish;
 use crate::commands::read::Read;
 use crate::commands::remove::Remove;
+use crate::commands::rm::Rm;
 use crate::commands::search::Search;
 use crate::commands::test::Test;
 use crate::commands::tree::Tree;
+use crate::commands::update::Update;
+use crate::commands::version::Bump;
 use crate::commands::view::View;
 
 #[derive(Debug, PartialEq)]
@@ -175,9 +183,14 @@ enum OrbitSubcommand {
     Help(Help),
     New(New),
     Search(Search),
+    Add(Add),
+    Rm(Rm),
     Lock(Lock),
+    Update(Update),
+    Outdated(Outdated),
     Build(Build),
     Test(Test),
+    Bump(Bump),
     Publish(Publish),
     Install(Install),
     Tree(Tree),
@@ -194,8 +207,9 @@ impl Subcommand<Context> for OrbitSubcommand {
     fn interpret<'c>(cli: &'c mut Cli<Memory>) -> cli::Result<Self> {
         match cli
             .select(&[
-                "help", "new", "search", "lock", "build", "test", "t", "publish", "install", "get",
-                "init", "tree", "view", "b", "env", "config", "remove", "read",
+                "help", "new", "search", "add", "rm", "lock", "update", "outdated", "build",
+                "test", "t", "version", "publish", "install", "get", "init", "tree", "view", "b",
+                "env", "config", "remove", "read",
             ])?
             .as_ref()
         {
@@ -203,10 +217,15 @@ impl Subcommand<Context> for OrbitSubcommand {
             "help" => Ok(OrbitSubcommand::Help(Help::interpret(cli)?)),
             "new" => Ok(OrbitSubcommand::New(New::interpret(cli)?)),
             "search" => Ok(OrbitSubcommand::Search(Search::interpret(cli)?)),
+            "add" => Ok(OrbitSubcommand::Add(Add::interpret(cli)?)),
+            "rm" => Ok(OrbitSubcommand::Rm(Rm::interpret(cli)?)),
             "lock" => Ok(OrbitSubcommand::Lock(Lock::interpret(cli)?)),
+            "update" => Ok(OrbitSubcommand::Update(Update::interpret(cli)?)),
+            "outdated" => Ok(OrbitSubcommand::Outdated(Outdated::interpret(cli)?)),
             "b" | "build" => Ok(OrbitSubcommand::Build(Build::interpret(cli)?)),
             "t" | "test" => Ok(OrbitSubcommand::Test(Test::interpret(cli)?)),
             "init" => Ok(OrbitSubcommand::Init(Init::interpret(cli)?)),
+            "version" => Ok(OrbitSubcommand::Bump(Bump::interpret(cli)?)),
             "publish" => Ok(OrbitSubcommand::Publish(Publish::interpret(cli)?)),
             "install" => Ok(OrbitSubcommand::Install(Install::interpret(cli)?)),
             "tree" => Ok(OrbitSubcommand::Tree(Tree::interpret(cli)?)),
@@ -223,12 +242,17 @@ impl Subcommand<Context> for OrbitSubcommand {
         match self {
             OrbitSubcommand::Get(sub) => sub.execute(context),
             OrbitSubcommand::Search(sub) => sub.execute(context),
+            OrbitSubcommand::Add(sub) => sub.execute(context),
+            OrbitSubcommand::Rm(sub) => sub.execute(context),
             OrbitSubcommand::Lock(sub) => sub.execute(context),
+            OrbitSubcommand::Update(sub) => sub.execute(context),
+            OrbitSubcommand::Outdated(sub) => sub.execute(context),
             OrbitSubcommand::Build(sub) => sub.execute(context),
             OrbitSubcommand::Install(sub) => sub.execute(context),
             OrbitSubcommand::Help(sub) => sub.execute(&()),
             OrbitSubcommand::New(sub) => sub.execute(context),
             OrbitSubcommand::Test(sub) => sub.execute(context),
+            OrbitSubcommand::Bump(sub) => sub.execute(context),
             OrbitSubcommand::Publish(sub) => sub.execute(context),
             OrbitSubcommand::Tree(sub) => sub.execute(context),
             OrbitSubcommand::Init(sub) => sub.execute(context),
@@ -252,11 +276,12 @@ warranty; not even for MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE."#;
 
 // TODO: check for additional data such as the commit being used
 
+use crate::core::config::DownloadSettings;
 use crate::core::version::Version;
 use crate::util::anyerror::Fault;
 use crate::util::filesystem::get_exe_path;
+use crate::util::http;
 use crate::util::sha256;
-use curl::easy::{Easy, List};
 #[cfg(not(target_os = "windows"))]
 use flate2::read::GzDecoder;
 use std::env::consts;
@@ -307,7 +332,8 @@ impl Orbit {
         repo.replace("github.com", "api.github.com/repos")
     }
 
-    /// Runs a process to check for an updated version of Orbit on GitHub to install.
+    /// Runs a process to check for an updated version of Orbit on GitHub to install,
+    /// transferring files according to the download `settings`.
     ///
     /// Steps it follows:  
     /// 1. Removes any old version existing in executables' current folder
@@ -316,7 +342,7 @@ impl Orbit {
     /// 4. Download compatible platform zip file and verify checksum matches
     /// 5. Unzip the file and replace the Orbit executable in-place.
     /// 6. Rename the old executable as `orbit-<version>`.
-    fn upgrade(&self) -> Result<String, Fault> {
+    fn upgrade(&self, settings: &DownloadSettings) -> Result<String, Fault> {
         // check for stale versions at the current executable's path
         let exe_path = get_exe_path()?;
         let mut current_exe_dir = exe_path.clone();
@@ -342,34 +368,7 @@ impl Orbit {
         // check the connection to grab latest html data
         let api_url: String = Self::get_api_url(REPOSITORY) + "/releases/latest";
 
-        let mut dst = Vec::new();
-        {
-            let mut easy = Easy::new();
-            easy.url(&api_url).unwrap();
-            easy.follow_location(false).unwrap();
-            // create headers
-            let mut list = List::new();
-            list.append("User-Agent: Orbit").unwrap();
-            easy.http_headers(list).unwrap();
-            {
-                let mut transfer = easy.transfer();
-                transfer
-                    .write_function(|data| {
-                        dst.extend_from_slice(data);
-                        Ok(data.len())
-                    })
-                    .unwrap();
-
-                transfer.perform()?;
-            }
-            let rc = easy.response_code()?;
-            if rc != RESPONSE_OKAY {
-                return Err(Box::new(UpgradeError::FailedConnection(
-                    api_url.to_owned(),
-                    rc,
-                )));
-            }
-        }
+        let dst = http::download_to_bytes(&api_url, settings, &["User-Agent: Orbit"], false)?;
         let body: String = String::from_utf8(dst)?;
 
         // create body into string to find the latest version
@@ -406,27 +405,7 @@ impl Orbit {
         println!("info: downloading checksums...");
         let sum_url = format!("{0}/download/{1}/SHA256SUMS", &base_url, &latest);
 
-        let mut dst = Vec::new();
-        {
-            let mut easy = Easy::new();
-            easy.url(&sum_url).unwrap();
-            easy.follow_location(true).unwrap();
-            {
-                let mut transfer = easy.transfer();
-                transfer
-                    .write_function(|data| {
-                        dst.extend_from_slice(data);
-                        Ok(data.len())
-                    })
-                    .unwrap();
-
-                transfer.perform()?;
-            }
-            let rc = easy.response_code()?;
-            if rc != RESPONSE_OKAY {
-                return Err(Box::new(UpgradeError::FailedConnection(sum_url, rc)));
-            }
-        }
+        let dst = http::download_to_bytes(&sum_url, settings, &[], true)?;
         let checksums: String = String::from_utf8(dst)?;
 
         // store user's target
@@ -463,27 +442,10 @@ impl Orbit {
         // let body_bytes = res.bytes().await?;
 
         println!("info: downloading update...");
-        let mut body_bytes = Vec::new();
-        {
-            let mut easy = Easy::new();
-            easy.url(&pkg_url).unwrap();
-            easy.follow_location(true).unwrap();
-            {
-                let mut transfer = easy.transfer();
-                transfer
-                    .write_function(|data| {
-                        body_bytes.extend_from_slice(data);
-                        Ok(data.len())
-                    })
-                    .unwrap();
-
-                transfer.perform()?;
-            }
-            let rc = easy.response_code()?;
-            if rc != RESPONSE_OKAY {
-                return Err(Box::new(UpgradeError::FailedConnection(pkg_url, rc)));
-            }
-        }
+        let temp_pkg_dir = tempfile::tempdir()?;
+        let pkg_path = temp_pkg_dir.path().join(&pkg);
+        http::download_to_file(&pkg_url, &pkg_path, settings, &[])?;
+        let body_bytes = fs::read(&pkg_path)?;
 
         // compute the checksum on the downloaded zip file
         let sum = sha256::compute_sha256(&body_bytes);
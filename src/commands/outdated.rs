@@ -0,0 +1,260 @@
+//
+//  Copyright (C) 2022-2025  Chase Ruskin
+//
+//  This program is free software: you can redistribute it and/or modify
+//  it under the terms of the GNU General Public License as published by
+//  the Free Software Foundation, either version 3 of the License, or
+//  (at your option) any later version.
+//
+//  This program is distributed in the hope that it will be useful,
+//  but WITHOUT ANY WARRANTY; without even the implied warranty of
+//  MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+//  GNU General Public License for more details.
+//
+//  You should have received a copy of the GNU General Public License
+//  along with this program.  If not, see <http://www.gnu.org/licenses/>.
+//
+
+use crate::commands::helps::outdated;
+use crate::core::catalog::Catalog;
+use crate::core::context::Context;
+use crate::core::ip::Ip;
+use crate::core::lockfile::{LockEntry, LockFile};
+use crate::error::{Error, Hint};
+use cliproc::{cli, proc, stage::*};
+use cliproc::{Arg, Cli, Help, Subcommand};
+use serde_derive::Serialize;
+
+#[derive(Debug, PartialEq)]
+pub struct Outdated {
+    json: bool,
+}
+
+impl Subcommand<Context> for Outdated {
+    fn interpret<'c>(cli: &'c mut Cli<Memory>) -> cli::Result<Self> {
+        cli.help(Help::with(outdated::HELP))?;
+        let command = Ok(Outdated {
+            json: cli.check(Arg::flag("json"))?,
+        });
+        command
+    }
+
+    fn execute(self, c: &Context) -> proc::Result {
+        // check that user is in an IP directory
+        c.jump_to_working_ip()?;
+
+        // store the working ip struct
+        let working_ip = Ip::load(c.get_ip_path().unwrap().clone(), true)?;
+
+        // assemble the catalog from all levels
+        let catalog = Catalog::new()
+            .installations(c.get_cache_path())?
+            .shared_installations(c.get_shared_cache_paths())?
+            .downloads(c.get_downloads_path())?
+            .available(&c.get_config().get_channels())?;
+
+        if working_ip.lock_exists() == false {
+            return Err(Error::LockfileOutOfDate(Hint::MakeLock))?;
+        }
+
+        let le: LockEntry = LockEntry::from((&working_ip, true));
+        let report = Self::collect(working_ip.get_lock(), &le, &catalog);
+
+        match self.json {
+            true => println!("{}", serde_json::to_string(&report)?),
+            false => println!("{}", Self::fmt_table(&report)),
+        }
+        Ok(())
+    }
+}
+
+/// The version drift of a single dependency in the lockfile.
+#[derive(Debug, PartialEq, Serialize)]
+struct Drift {
+    name: String,
+    uuid: String,
+    direct: bool,
+    locked: String,
+    compatible: Option<String>,
+    latest: Option<String>,
+}
+
+impl Outdated {
+    /// Compares each dependency in the lockfile `lf` against the versions known
+    /// to the `catalog`.
+    fn collect(lf: &LockFile, le: &LockEntry, catalog: &Catalog) -> Vec<Drift> {
+        // the lockfile's entry for the working ip knows the uuid of each direct dependency
+        let root = lf.get_self_entry(le.get_name()).unwrap_or(le);
+        lf.inner()
+            .iter()
+            .filter(|e| e.matches_target(le, &catalog) == false && e.is_relative() == false)
+            .map(|entry| {
+                let reqs = lf.get_requirements(entry, le);
+                // versions are already sorted from highest to lowest
+                let versions: Vec<_> = catalog
+                    .get_unyanked_versions(entry.get_uuid())
+                    .unwrap_or_default()
+                    .into_iter()
+                    .map(|v| v.get_version().clone())
+                    .collect();
+                Drift {
+                    name: entry.get_name().to_string(),
+                    uuid: entry.get_uuid().to_string(),
+                    direct: root.get_deps().iter().any(|d| {
+                        d.get_name() == entry.get_name()
+                            && d.as_uuid()
+                                .as_ref()
+                                .map_or(true, |id| id == entry.get_uuid())
+                    }),
+                    locked: entry.get_version().to_string(),
+                    compatible: versions
+                        .iter()
+                        .find(|v| reqs.iter().all(|r| r.matches(v) == true))
+                        .map(|v| v.to_string()),
+                    latest: versions.first().map(|v| v.to_string()),
+                }
+            })
+            .collect()
+    }
+
+    fn fmt_table(report: &Vec<Drift>) -> String {
+        let header = format!(
+            "{:<24}{:<12}{:<12}{:<12}{}\n{:->24}{:->12}{:->12}{:->12}{:->10}\n",
+            "Ip", "Locked", "Compatible", "Latest", "Kind", " ", " ", " ", " ", " "
+        );
+        let mut body = String::new();
+        for drift in report {
+            body.push_str(&format!(
+                "{:<24}{:<12}{:<12}{:<12}{}\n",
+                drift.name,
+                drift.locked,
+                drift.compatible.as_ref().map_or("-", |v| v.as_str()),
+                drift.latest.as_ref().map_or("-", |v| v.as_str()),
+                match drift.direct {
+                    true => "direct",
+                    false => "transitive",
+                }
+            ));
+        }
+        // remove final \n from body
+        body.pop();
+        header + &body
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::core::pkgid::PkgPart;
+    use crate::core::testing::{create_catalog, create_lockfile, yank_version};
+    use std::str::FromStr;
+
+    const APP_ID: &str = "6f4mh2xsw6yt4m0bd6m8dzqz3";
+    const LIB_ID: &str = "esefn4auh48jjfz1g6xvwj48r";
+    const OLD_LIB_ID: &str = "40qzw0m5h35z0bbh1h7zqf8b3";
+    const MID_ID: &str = "2a4g8t6rs2ydmtqtc6fqx1ttn";
+    const UTIL_ID: &str = "1h875zp8cjtab1lrkdgue5gxk";
+
+    /// The working ip "app" directly requires "lib" and "mid", and "mid" requires
+    /// "util" and a different ip also named "lib".
+    fn lockfile() -> LockFile {
+        create_lockfile(
+            (
+                "app",
+                APP_ID,
+                "0.1.0",
+                &[
+                    "lib+esefn4auh48jjfz1g6xvwj48r:1",
+                    "mid+2a4g8t6rs2ydmtqtc6fqx1ttn:1.0.0",
+                ],
+            ),
+            &[
+                ("lib", LIB_ID, "1.0.0", &[]),
+                ("lib", OLD_LIB_ID, "0.4.0", &[]),
+                (
+                    "mid",
+                    MID_ID,
+                    "1.0.0",
+                    &[
+                        "util+1h875zp8cjtab1lrkdgue5gxk:0.1",
+                        "lib+40qzw0m5h35z0bbh1h7zqf8b3:0.4.0",
+                    ],
+                ),
+                ("util", UTIL_ID, "0.1.0", &[]),
+            ],
+        )
+    }
+
+    #[test]
+    fn collect_direct_and_transitive() {
+        let catalog = create_catalog(&[
+            ("lib", LIB_ID, "1.0.0"),
+            ("lib", LIB_ID, "1.2.0"),
+            ("lib", LIB_ID, "2.0.0"),
+            ("mid", MID_ID, "1.0.0"),
+            ("util", UTIL_ID, "0.1.0"),
+            ("util", UTIL_ID, "0.1.4"),
+            ("util", UTIL_ID, "0.2.0"),
+        ]);
+        let lf = lockfile();
+        let le = lf
+            .get_self_entry(&PkgPart::from_str("app").unwrap())
+            .unwrap();
+        let report = Outdated::collect(&lf, le, &catalog);
+        let find = |name: &str, version: &str| {
+            report
+                .iter()
+                .find(|d| d.name == name && d.locked == version)
+                .unwrap()
+        };
+        assert_eq!(report.len(), 4);
+
+        // direct dependency with a compatible and an incompatible newer version
+        let lib = find("lib", "1.0.0");
+        assert_eq!(lib.direct, true);
+        assert_eq!(lib.compatible, Some(String::from("1.2.0")));
+        assert_eq!(lib.latest, Some(String::from("2.0.0")));
+
+        // direct dependency that is already up to date
+        let mid = find("mid", "1.0.0");
+        assert_eq!(mid.direct, true);
+        assert_eq!(mid.compatible, Some(String::from("1.0.0")));
+        assert_eq!(mid.latest, Some(String::from("1.0.0")));
+
+        // transitive dependency constrained by its dependent
+        let util = find("util", "0.1.0");
+        assert_eq!(util.direct, false);
+        assert_eq!(util.compatible, Some(String::from("0.1.4")));
+        assert_eq!(util.latest, Some(String::from("0.2.0")));
+
+        // transitive dependency sharing a name with a direct dependency
+        let other = find("lib", "0.4.0");
+        assert_eq!(other.direct, false);
+        assert_eq!(other.compatible, None);
+        assert_eq!(other.latest, None);
+    }
+
+    #[test]
+    fn collect_skips_yanked_versions() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut catalog = create_catalog(&[
+            ("lib", LIB_ID, "1.0.0"),
+            ("lib", LIB_ID, "1.2.0"),
+            ("mid", MID_ID, "1.0.0"),
+            ("util", UTIL_ID, "0.1.0"),
+        ]);
+        yank_version(&mut catalog, dir.path(), "lib", LIB_ID, "1.3.0");
+        yank_version(&mut catalog, dir.path(), "lib", LIB_ID, "2.0.0");
+        let lf = lockfile();
+        let le = lf
+            .get_self_entry(&PkgPart::from_str("app").unwrap())
+            .unwrap();
+        let report = Outdated::collect(&lf, le, &catalog);
+        let lib = report
+            .iter()
+            .find(|d| d.name == "lib" && d.locked == "1.0.0")
+            .unwrap();
+        assert_eq!(lib.compatible, Some(String::from("1.2.0")));
+        assert_eq!(lib.latest, Some(String::from("1.2.0")));
+    }
+}
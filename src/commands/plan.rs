@@ -18,16 +18,18 @@
 use colored::Colorize;
 
 use crate::commands::download::Download;
-use crate::core::blueprint::{Blueprint, Instruction, Scheme};
+use crate::core::blueprint::{self, Blueprint, Instruction, Scheme};
+use crate::core::config::DownloadSettings;
 use crate::core::context::{self, Context};
 use crate::core::fileset::Fileset;
 use crate::core::iparchive::IpArchive;
+use crate::core::lang::cache::UnitCache;
 use crate::core::lang::parser::ParseError;
 use crate::core::lang::reference::CompoundIdentifier;
-use crate::core::lang::sv::symbols::{SystemVerilogParser, SystemVerilogSymbol};
-use crate::core::lang::verilog::symbols::{VerilogParser, VerilogSymbol};
-use crate::core::lang::vhdl::subunit::SubUnit;
-use crate::core::lang::vhdl::symbols::{VHDLParser, VhdlSymbol};
+use crate::core::lang::sv::symbols::SystemVerilogParser;
+use crate::core::lang::verilog::preprocess::{self, Define};
+use crate::core::lang::verilog::symbols::VerilogParser;
+use crate::core::lang::vhdl::symbols::VHDLParser;
 use crate::core::lang::vhdl::token::Identifier;
 use crate::core::lang::{self, Lang, LangIdentifier};
 use crate::core::swap;
@@ -40,6 +42,7 @@ use crate::util::environment;
 use crate::util::environment::EnvVar;
 use crate::util::environment::Environment;
 use crate::util::filesystem;
+use crate::util::filesystem::Standardize;
 use crate::util::graph::EdgeStatus;
 use crate::util::graphmap::GraphMap;
 use std::collections::{HashMap, HashSet};
@@ -47,6 +50,7 @@ use std::fs;
 use std::hash::Hash;
 use std::path::{Path, PathBuf};
 
+use crate::commands::add::Add;
 use crate::commands::install::Install;
 use crate::core::algo;
 use crate::core::algo::IpFileNode;
@@ -56,6 +60,9 @@ use crate::core::ip::Ip;
 use crate::core::ip::IpSpec;
 use crate::core::lockfile::LockEntry;
 use crate::core::lockfile::LockFile;
+use crate::core::manifest::{FromFile, ManifestDocument, IP_MANIFEST_FILE};
+use crate::core::pkgid::PkgPart;
+use crate::core::uuid::Uuid;
 use crate::util::graphmap::Node;
 
 #[derive(Debug, PartialEq)]
@@ -72,38 +79,68 @@ pub struct Plan {
     force: bool,
 }
 
+/// The choices that shape how a target is planned.
+#[derive(Debug, PartialEq)]
+pub struct PlanOptions<'a> {
+    /// The directory relative to the working ip where the target's outputs are written.
+    pub target_dir: &'a str,
+    /// Remove the target's previous outputs before planning.
+    pub clean: bool,
+    pub force: bool,
+    /// Only write the lockfile without creating a blueprint.
+    pub only_lock: bool,
+    /// Include all hdl files of the working ip.
+    pub all: bool,
+    pub bench: &'a Option<Identifier>,
+    pub top: &'a Option<Identifier>,
+    pub filesets: &'a Option<Vec<Fileset>>,
+    /// Verilog macros from the command-line, which replace the target's macros.
+    pub defines: &'a Option<Vec<Define>>,
+    pub scheme: &'a Scheme,
+    pub require_bench: bool,
+    pub allow_bench: bool,
+}
+
 impl Plan {
     /// Performs the backend logic for creating a blueprint file (planning a design).
     ///
     /// If a blueprint was created, it will return the file name for that blueprint.
     pub fn run(
         working_ip: &Ip,
-        target_dir: &str,
         target: &Target,
         catalog: Catalog,
-        clean: bool,
-        force: bool,
-        only_lock: bool,
-        all: bool,
-        bench_name: &Option<Identifier>,
-        top_name: &Option<Identifier>,
-        filesets: &Option<Vec<Fileset>>,
-        scheme: &Scheme,
-        require_bench: bool,
-        allow_bench: bool,
+        options: &PlanOptions,
+        unit_cache: &mut UnitCache,
     ) -> Result<Option<String>, Fault> {
+        let PlanOptions {
+            target_dir,
+            clean,
+            force,
+            only_lock,
+            all,
+            bench: bench_name,
+            top: top_name,
+            filesets,
+            defines,
+            scheme,
+            require_bench,
+            allow_bench,
+        } = *options;
         // create the output path to know where to begin storing files
         let working_ip_path = working_ip.get_root().clone();
         let target_path = working_ip_path.join(target_dir);
         let output_path = target_path.join(target.get_name());
 
         // build entire ip graph and resolve with dynamic symbol transformation
-        let ip_graph = match algo::compute_final_ip_graph(&working_ip, &catalog) {
-            Ok(g) => g,
+        let ip_graph = match algo::compute_final_ip_graph(working_ip, &catalog, unit_cache) {
+            Ok(g) => {
+                unit_cache.save()?;
+                g
+            }
             Err(e) => {
                 // generate a single blueprint
                 if e.is_source_err() == true && force == true {
-                    let mut blueprint = Blueprint::new(scheme.clone());
+                    let mut blueprint = Blueprint::new(scheme.clone(), &working_ip);
                     let ip_file_node = IpFileNode::new(
                         e.as_source_file().unwrap().to_string(),
                         &working_ip,
@@ -139,6 +176,16 @@ impl Plan {
             }
         };
 
+        // warn about any dependencies that are marked as deprecated
+        let mut warned = HashSet::new();
+        ip_graph
+            .get_map()
+            .iter()
+            .map(|p| p.1.as_ref().as_original_ip())
+            .filter(|ip| ip.get_uuid() != working_ip.get_uuid())
+            .filter(|ip| warned.insert(ip.get_man().get_ip().into_ip_spec()))
+            .for_each(|ip| ip.warn_if_deprecated());
+
         // only write lockfile and exit if flag is raised
         if only_lock == true {
             Self::write_lockfile(&working_ip, &ip_graph, force, true, &catalog)?;
@@ -152,7 +199,12 @@ impl Plan {
 
         let files = algo::build_ip_file_list(&ip_graph, &working_ip);
 
-        let global_graph = Self::build_full_graph(&files)?;
+        // macros set on the command-line take precedence over the target's macros
+        let defines =
+            preprocess::merge_defines(target.get_defines(), defines.as_deref().unwrap_or(&[]));
+
+        let global_graph = Self::build_full_graph(&files, &defines, unit_cache)?;
+        unit_cache.save()?;
 
         let working_lib = working_ip.get_hdl_library();
 
@@ -293,6 +345,21 @@ impl Plan {
             }
         };
 
+        // suggest ips from the catalog that provide any unresolved units
+        let missing = Self::collect_black_boxes(&global_graph, &min_order);
+        if missing.is_empty() == false {
+            for (unit, ip) in find_unit_providers(&catalog, working_ip, &missing)? {
+                let spec = ip.get_man().get_ip().into_ip_spec();
+                println!(
+                    "{} unit `{}` is provided by ip `{}`; add it with `orbit add {}`",
+                    "warning:".yellow(),
+                    unit,
+                    spec,
+                    spec
+                );
+            }
+        }
+
         // println!("{:?}", min_order);
 
         // generate the file order while merging dependencies for common file path names together
@@ -338,7 +405,7 @@ impl Plan {
         }
 
         // store data in blueprint
-        let mut blueprint = Blueprint::new(scheme.clone());
+        let mut blueprint = Blueprint::new(scheme.clone(), &working_ip);
 
         // [!] collect user-defined filesets
         {
@@ -411,9 +478,38 @@ impl Plan {
             }
         }
 
+        // store the directories to search for each header file
+        let include_dirs: HashMap<&String, &Vec<String>> = global_graph
+            .get_map()
+            .values()
+            .filter_map(|n| {
+                let dirs = n.as_ref().get_symbol().get_include_dirs()?;
+                Some((n.as_ref().get_associated_files().first()?.get_file(), dirs))
+            })
+            .collect();
+        let mut listed_dirs: HashSet<(&str, String)> = HashSet::new();
+
         // collect in-order HDL file list
         for ip_file_node in file_order {
-            if fileset::is_rtl(&ip_file_node.get_file()) == true {
+            if fileset::is_header(&ip_file_node.get_file()) == true {
+                // list the header's include directories rather than the header itself
+                let fallback = vec![filesystem::into_std_str(
+                    PathBuf::from(ip_file_node.get_file())
+                        .parent()
+                        .unwrap()
+                        .to_path_buf(),
+                )];
+                for dir in include_dirs
+                    .get(ip_file_node.get_file())
+                    .map(|d| d.to_vec())
+                    .unwrap_or(fallback)
+                {
+                    let set = blueprint::get_hdl_fileset(ip_file_node.get_file());
+                    if listed_dirs.insert((set, dir.clone())) == true {
+                        blueprint.add(Instruction::Include(ip_file_node, dir));
+                    }
+                }
+            } else if fileset::is_rtl(&ip_file_node.get_file()) == true {
                 blueprint.add(Instruction::Hdl(ip_file_node));
             } else {
                 blueprint.add(Instruction::Hdl(ip_file_node));
@@ -455,7 +551,14 @@ pub fn resolve_missing_deps<'a>(
             .from_config(c.get_config())?;
         let vtable = StrSwapTable::new().load_environment(&env)?;
 
-        download_missing_deps(vtable, &lf, &le, &catalog, &c.get_config().get_protocols())?;
+        download_missing_deps(
+            vtable,
+            lf,
+            &le,
+            &catalog,
+            &c.get_config().get_protocols(),
+            &c.get_config().get_download_settings(),
+        )?;
         // recollect the downloaded items to update the catalog for installations
         catalog = catalog.downloads(c.get_downloads_path())?;
 
@@ -467,12 +570,68 @@ pub fn resolve_missing_deps<'a>(
     }
 }
 
+/// Gathers the catalog of installed, downloaded, and available ips, and then
+/// downloads and installs any dependencies of the `working_ip` that its lockfile
+/// requires but are missing.
+pub fn gather_catalog<'a>(
+    c: &'a Context,
+    working_ip: &'a Ip,
+    force: bool,
+) -> Result<Catalog<'a>, Fault> {
+    let catalog = Catalog::new()
+        .installations(c.get_cache_path())?
+        .shared_installations(c.get_shared_cache_paths())?
+        .downloads(c.get_downloads_path())?
+        .available(&c.get_config().get_channels())?;
+    resolve_missing_deps(c, working_ip, catalog, force)
+}
+
+/// Searches the installed ips in the `catalog` for the public units that match
+/// the `missing` identifiers.
+///
+/// Only the latest installed version of each ip is searched, and its units are
+/// read from its stored metadata. Ips that are only downloaded or available from
+/// a channel are skipped because their units are not known until installed.
+pub fn find_unit_providers<'b>(
+    catalog: &'b Catalog,
+    working_ip: &Ip,
+    missing: &[LangIdentifier],
+) -> Result<Vec<(LangIdentifier, &'b Ip)>, Fault> {
+    let mut providers: Vec<(LangIdentifier, &'b Ip)> = Vec::new();
+    for (uuid, lvl) in catalog.inner() {
+        if uuid == working_ip.get_uuid() {
+            continue;
+        }
+        let ip = match lvl.get(false, false, &AnyVersion::Latest) {
+            Some(ip) => ip,
+            None => continue,
+        };
+        let units = ip.collect_units(false, true)?;
+        for name in missing {
+            if units.keys().any(|k| k == name) {
+                providers.push((name.clone(), ip));
+            }
+        }
+    }
+    providers.sort_by(|a, b| {
+        a.0.cmp(&b.0).then(
+            a.1.get_man()
+                .get_ip()
+                .into_ip_spec()
+                .to_string()
+                .cmp(&b.1.get_man().get_ip().into_ip_spec().to_string()),
+        )
+    });
+    Ok(providers)
+}
+
 pub fn download_missing_deps(
     vtable: StrSwapTable,
     lf: &LockFile,
     le: &LockEntry,
     catalog: &Catalog,
     protocols: &ProtocolMap,
+    settings: &DownloadSettings,
 ) -> Result<(), Fault> {
     let mut vtable = vtable;
     // fetch all non-downloaded packages
@@ -521,16 +680,17 @@ pub fn download_missing_deps(
         }
         // check if the slot is not already filled before trying to download
         if require_download == true {
-            match entry.get_source() {
+            match entry.get_pinned_source() {
                 Some(src) => {
                     // fetch from the internet
                     Download::download(
                         &mut vtable,
                         Some(&entry.to_ip_spec().to_partial_ip_spec()),
-                        src,
+                        &src,
                         None,
                         catalog.get_downloads_path(),
                         &protocols,
+                        settings,
                         false,
                         true,
                     )?;
@@ -647,205 +807,129 @@ use crate::util::anyerror::AnyError;
 
 use super::download::ProtocolMap;
 
-use crate::core::lang::node::SubUnitNode;
+use crate::core::lang::node::{FileSymbols, SubUnitNode};
 use crate::core::lang::node::{HdlNode, HdlSymbol};
 
 impl Plan {
-    pub fn create_verilog_node<'a, 'b>(
-        graph_map: &'b mut GraphMap<CompoundIdentifier, HdlNode<'a>, ()>,
-        node: &'a IpFileNode,
-        component_pairs: &'b mut HashMap<LangIdentifier, LangIdentifier>,
-    ) -> Result<(), Fault> {
-        let contents = lang::read_to_string(&node.get_file())?;
-        let symbols = match VerilogParser::read(&contents) {
-            Ok(s) => s.into_symbols(),
-            Err(e) => Err(ParseError::SourceCodeError(
-                node.get_file().clone(),
-                e.to_string(),
-            ))?,
+    /// Reads the symbols of the source file `node`, reusing the symbols stored in
+    /// the `cache` if the file has not changed since it was last read with the same
+    /// macros `defines`.
+    ///
+    /// Header files included by a Verilog or SystemVerilog file are found among
+    /// `files` and expanded while the file is preprocessed. Stored symbols are read
+    /// again once a header that could not be found is among `files`.
+    fn read_symbols(
+        node: &IpFileNode,
+        files: &Vec<IpFileNode>,
+        defines: &[Define],
+        cache: &mut UnitCache,
+    ) -> Result<FileSymbols, Fault> {
+        let lang = node.get_language();
+        // the file (or header) that holds an include directive
+        let source = |from: Option<&str>| {
+            from.and_then(|h| files.iter().find(|f| f.get_file() == h))
+                .unwrap_or(node)
         };
-
-        let lib = node.get_library();
-        let vhdl_lib = lib.as_vhdl_name().unwrap().clone();
-        // println!("{} {}", source_file.get_file(), source_file.get_library());
-
-        // add all entities to a graph and store architectures for later analysis
-        symbols.into_iter().for_each(|f| {
-            let name = f.as_name();
-            match f {
-                VerilogSymbol::Module(_) => {
-                    component_pairs.insert(
-                        LangIdentifier::Verilog(name.unwrap().clone()),
-                        LangIdentifier::Vhdl(vhdl_lib.clone()),
-                    );
-                    // add primary design units into the graph
-                    graph_map.add_node(
-                        CompoundIdentifier::new(
-                            lib.clone(),
-                            LangIdentifier::Verilog(name.unwrap().clone()),
-                        ),
-                        HdlNode::new(HdlSymbol::Verilog(f), node),
-                    );
-                }
-                VerilogSymbol::Config(_) => {
-                    component_pairs.insert(
-                        LangIdentifier::Verilog(name.unwrap().clone()),
-                        LangIdentifier::Vhdl(vhdl_lib.clone()),
-                    );
-                    // add primary design units into the graph
-                    graph_map.add_node(
-                        CompoundIdentifier::new(
-                            lib.clone(),
-                            LangIdentifier::Verilog(name.unwrap().clone()),
-                        ),
-                        HdlNode::new(HdlSymbol::Verilog(f), node),
-                    );
-                }
-                VerilogSymbol::Primitive(_) => {
-                    component_pairs.insert(
-                        LangIdentifier::Verilog(name.unwrap().clone()),
-                        LangIdentifier::Vhdl(vhdl_lib.clone()),
-                    );
-                    // add primary design units into the graph
-                    graph_map.add_node(
-                        CompoundIdentifier::new(
-                            lib.clone(),
-                            LangIdentifier::Verilog(name.unwrap().clone()),
-                        ),
-                        HdlNode::new(HdlSymbol::Verilog(f), node),
-                    );
-                }
-            }
-        });
-        Ok(())
-    }
-
-    pub fn create_systemverilog_node<'a, 'b>(
-        graph_map: &'b mut GraphMap<CompoundIdentifier, HdlNode<'a>, ()>,
-        node: &'a IpFileNode,
-        component_pairs: &'b mut HashMap<LangIdentifier, LangIdentifier>,
-    ) -> Result<(), Fault> {
-        let contents = lang::read_to_string(&node.get_file())?;
-        let symbols = match SystemVerilogParser::read(&contents) {
-            Ok(s) => s.into_symbols(),
-            Err(e) => Err(ParseError::SourceCodeError(
-                node.get_file().clone(),
-                e.to_string(),
-            ))?,
+        let key = cache.checksum(node.get_file())?;
+        let mut found = |from: Option<&str>, path: &str| {
+            // an ambiguous header must be reported by reading the file again
+            Self::resolve_include(files, source(from), path).map_or(true, |header| header.is_some())
         };
-
-        let lib = node.get_library();
-        let vhdl_lib = lib.as_vhdl_name().unwrap().clone();
-        // println!("{} {}", source_file.get_file(), source_file.get_library());
-
-        // add all entities to a graph and store architectures for later analysis
-        symbols.into_iter().for_each(|f| {
-            let name = f.as_name();
-            match f {
-                SystemVerilogSymbol::Module(_) => {
-                    component_pairs.insert(
-                        LangIdentifier::SystemVerilog(name.unwrap().clone()),
-                        LangIdentifier::Vhdl(vhdl_lib.clone()),
-                    );
-                    // add primary design units into the graph
-                    graph_map.add_node(
-                        CompoundIdentifier::new(
-                            lib.clone(),
-                            LangIdentifier::SystemVerilog(name.unwrap().clone()),
-                        ),
-                        HdlNode::new(HdlSymbol::SystemVerilog(f), node),
-                    );
-                }
-                SystemVerilogSymbol::Config(_)
-                | SystemVerilogSymbol::Class(_)
-                | SystemVerilogSymbol::Package(_)
-                | SystemVerilogSymbol::Primitive(_)
-                | SystemVerilogSymbol::Checker(_)
-                | SystemVerilogSymbol::Program(_)
-                | SystemVerilogSymbol::Interface(_) => {
-                    component_pairs.insert(
-                        LangIdentifier::Verilog(name.unwrap().clone()),
-                        LangIdentifier::Vhdl(vhdl_lib.clone()),
-                    );
-                    // add primary design units into the graph
-                    graph_map.add_node(
-                        CompoundIdentifier::new(
-                            lib.clone(),
-                            LangIdentifier::Verilog(name.unwrap().clone()),
-                        ),
-                        HdlNode::new(HdlSymbol::SystemVerilog(f), node),
-                    );
+        if let Some(symbols) = key
+            .as_ref()
+            .and_then(|k| cache.get_symbols(k, lang, defines, &mut found))
+        {
+            return Ok(symbols);
+        }
+        let contents = lang::read_to_string(&node.get_file())?;
+        // find included headers relative to the file (or header) that includes them
+        let mut missing = Vec::new();
+        let mut load = |from: Option<&str>, path: &str| {
+            let header = Self::resolve_include(files, source(from), path);
+            match header {
+                Ok(Some((header, _))) => lang::read_to_string(header.get_file())
+                    .map(|c| Some((header.get_file().clone(), c)))
+                    .map_err(|e| e.to_string()),
+                Ok(None) => {
+                    missing.push((from.map(String::from), path.to_string()));
+                    Ok(None)
                 }
+                Err(e) => Err(e.to_string()),
             }
-        });
-        Ok(())
+        };
+        let result = match lang {
+            Lang::Vhdl => VHDLParser::read(&contents)
+                .map(|s| FileSymbols::from_vhdl(s.into_symbols()))
+                .map_err(|e| e.to_string()),
+            Lang::Verilog => VerilogParser::read(&contents, defines, &mut load)
+                .map(|s| {
+                    let includes = s.get_includes().clone();
+                    let headers = s.get_headers().clone();
+                    FileSymbols::from_verilog(s.into_symbols(), includes, headers)
+                })
+                .map_err(|e| e.to_string()),
+            Lang::SystemVerilog => SystemVerilogParser::read(&contents, defines, &mut load)
+                .map(|s| {
+                    let includes = s.get_includes().clone();
+                    let headers = s.get_headers().clone();
+                    FileSymbols::from_systemverilog(s.into_symbols(), includes, headers)
+                })
+                .map_err(|e| e.to_string()),
+        };
+        let symbols = match result {
+            Ok(s) => s,
+            Err(e) => Err(ParseError::SourceCodeError(node.get_file().clone(), e))?,
+        };
+        if let Some(k) = key {
+            cache.insert_symbols(k, lang.clone(), defines, &symbols, &missing);
+        }
+        Ok(symbols)
     }
 
-    pub fn create_vhdl_node<'a, 'b>(
+    /// Adds a node to the graph for every primary design unit found in the source
+    /// file `node`, and stores its secondary design units for later analysis.
+    ///
+    /// Returns the paths of the files included by the source file.
+    pub fn create_node<'a, 'b>(
         graph_map: &'b mut GraphMap<CompoundIdentifier, HdlNode<'a>, ()>,
         node: &'a IpFileNode,
+        files: &'a Vec<IpFileNode>,
         component_pairs: &'b mut HashMap<LangIdentifier, LangIdentifier>,
         sub_nodes: &'b mut Vec<(LangIdentifier, SubUnitNode<'a>)>,
-    ) -> Result<(), Fault> {
-        let contents = lang::read_to_string(&node.get_file())?;
-        let symbols = match VHDLParser::read(&contents) {
-            Ok(s) => s.into_symbols(),
-            Err(e) => Err(ParseError::SourceCodeError(
-                node.get_file().clone(),
-                e.to_string(),
-            ))?,
-        };
+        defines: &[Define],
+        cache: &mut UnitCache,
+    ) -> Result<Vec<String>, Fault> {
+        let (units, subunits, includes) =
+            Self::read_symbols(node, files, defines, cache)?.into_parts();
 
         let lib = node.get_library();
         let vhdl_lib = lib.as_vhdl_name().unwrap().clone();
 
         // add all entities to a graph and store architectures for later analysis
-        let mut iter = symbols.into_iter().filter_map(|f| {
-            match f {
-                VhdlSymbol::Entity(_) => {
-                    component_pairs.insert(
-                        LangIdentifier::Vhdl(f.as_entity().unwrap().get_name().clone()),
-                        LangIdentifier::Vhdl(vhdl_lib.clone()),
-                    );
-                    Some(f)
-                }
-                VhdlSymbol::Package(_) => Some(f),
-                VhdlSymbol::Context(_) => Some(f),
-                VhdlSymbol::Architecture(arch) => {
-                    sub_nodes.push((
-                        LangIdentifier::Vhdl(vhdl_lib.clone()),
-                        SubUnitNode::new(SubUnit::from_arch(arch), node),
-                    ));
-                    None
-                }
-                VhdlSymbol::Configuration(cfg) => {
-                    sub_nodes.push((
-                        LangIdentifier::Vhdl(vhdl_lib.clone()),
-                        SubUnitNode::new(SubUnit::from_config(cfg), node),
-                    ));
-                    None
-                }
-                // package bodies are usually in same design file as package
-                VhdlSymbol::PackageBody(pb) => {
-                    sub_nodes.push((
-                        LangIdentifier::Vhdl(vhdl_lib.clone()),
-                        SubUnitNode::new(SubUnit::from_body(pb), node),
-                    ));
-                    None
-                }
+        for unit in units {
+            // vhdl packages and contexts cannot be instantiated as components
+            if node.get_language() != &Lang::Vhdl || unit.is_component() == true {
+                component_pairs.insert(
+                    unit.get_name().clone(),
+                    LangIdentifier::Vhdl(vhdl_lib.clone()),
+                );
             }
-        });
-        while let Some(e) = iter.next() {
+            let key = CompoundIdentifier::new(lib.clone(), unit.get_name().clone());
+            let sym = match node.get_language() {
+                Lang::Vhdl => HdlSymbol::Vhdl(unit),
+                Lang::Verilog => HdlSymbol::Verilog(unit),
+                Lang::SystemVerilog => HdlSymbol::SystemVerilog(unit),
+            };
             // add primary design units into the graph
-            graph_map.add_node(
-                CompoundIdentifier::new_vhdl(
-                    lib.as_vhdl_name().unwrap().clone(),
-                    e.get_name().unwrap().clone(),
-                ),
-                HdlNode::new(HdlSymbol::Vhdl(e), node),
-            );
+            graph_map.add_node(key, HdlNode::new(sym, node));
         }
-        Ok(())
+        for sub in subunits {
+            sub_nodes.push((
+                LangIdentifier::Vhdl(vhdl_lib.clone()),
+                SubUnitNode::new(sub, node),
+            ));
+        }
+        Ok(includes)
     }
 
     pub fn connect_edges_from_verilog<'b, 'a>(
@@ -868,7 +952,7 @@ impl Plan {
                                 Some((
                                     f.as_ref().get_library(),
                                     sym.get_name(),
-                                    sym.as_module().unwrap().get_edge_list_entities(),
+                                    sym.get_edge_list_entities().cloned().unwrap_or_default(),
                                 ))
                             } else {
                                 None
@@ -942,9 +1026,116 @@ impl Plan {
         }
     }
 
+    /// Finds the file among `files` that is referenced by the `path` of an include
+    /// directive within the `source` file, along with the directory to search to
+    /// find it by that `path`.
+    ///
+    /// The directory of the `source` file is searched first, then the rest of the
+    /// `source` file's ip, and then all other ips. Errors if more than one file
+    /// matches the `path` within the first place it is found.
+    fn resolve_include<'a>(
+        files: &'a Vec<IpFileNode<'a>>,
+        source: &IpFileNode,
+        path: &str,
+    ) -> Result<Option<(&'a IpFileNode<'a>, String)>, PlanError> {
+        if let Some(parent) = PathBuf::from(source.get_file()).parent() {
+            let local = PathBuf::standardize(parent.join(path));
+            if let Some(f) = files.iter().find(|f| PathBuf::from(f.get_file()) == local) {
+                return Ok(Some((f, filesystem::into_std_str(parent.to_path_buf()))));
+            }
+        }
+        let (same_ip, other_ips): (Vec<&IpFileNode>, Vec<&IpFileNode>) = files
+            .iter()
+            .filter(|f| PathBuf::from(f.get_file()).ends_with(path))
+            .partition(|f| f.get_ip() == source.get_ip());
+        let matches = match same_ip.is_empty() {
+            true => other_ips,
+            false => same_ip,
+        };
+        let header = match matches.len() {
+            0 => return Ok(None),
+            1 => matches[0],
+            _ => {
+                return Err(PlanError::AmbiguousInclude(
+                    path.to_string(),
+                    source.get_file().clone(),
+                    matches.iter().map(|f| f.get_file().clone()).collect(),
+                ))
+            }
+        };
+        // remove the components of the include path from the end of the file path
+        let mut dir = PathBuf::from(header.get_file());
+        for _ in PathBuf::from(path).components() {
+            dir.pop();
+        }
+        Ok(Some((header, filesystem::into_std_str(dir))))
+    }
+
+    /// Adds a node for every header file included by a Verilog or SystemVerilog file,
+    /// with an edge to each design unit (or header) defined in the including file.
+    fn connect_includes<'a>(
+        graph_map: &mut GraphMap<CompoundIdentifier, HdlNode<'a>, ()>,
+        files: &'a Vec<IpFileNode<'a>>,
+        includes: Vec<(&'a IpFileNode<'a>, Vec<String>)>,
+    ) -> Result<(), Fault> {
+        let mut edges: Vec<(CompoundIdentifier, &'a IpFileNode<'a>)> = Vec::new();
+        for (source, paths) in includes {
+            for path in paths {
+                let (header, dir) = match Self::resolve_include(files, source, &path)? {
+                    Some(h) => h,
+                    None => continue,
+                };
+                let name = filesystem::into_std_str(filesystem::remove_base(
+                    header.get_ip().get_root(),
+                    &PathBuf::from(header.get_file()),
+                ));
+                let key = CompoundIdentifier::new(
+                    header.get_library(),
+                    HdlSymbol::Header(name.clone(), Vec::new()).get_name(),
+                );
+                if graph_map.has_node_by_key(&key) == false {
+                    graph_map.add_node(
+                        key.clone(),
+                        HdlNode::new(HdlSymbol::Header(name, Vec::new()), header),
+                    );
+                }
+                if let HdlSymbol::Header(_, dirs) = graph_map
+                    .get_node_by_key_mut(&key)
+                    .unwrap()
+                    .as_ref_mut()
+                    .get_symbol_mut()
+                {
+                    if dirs.contains(&dir) == false {
+                        dirs.push(dir);
+                    }
+                }
+                edges.push((key, source));
+            }
+        }
+        // connect after all headers exist so that nested includes are linked
+        for (key, source) in edges {
+            let users: Vec<CompoundIdentifier> = graph_map
+                .get_map()
+                .iter()
+                .filter(|(k, n)| *k != &key && n.as_ref().get_associated_files().contains(&source))
+                .map(|(k, _)| k.clone())
+                .collect();
+            for user in users {
+                graph_map.add_edge_by_key(&key, &user, ());
+            }
+        }
+        Ok(())
+    }
+
     /// Builds a graph of design units. Used for planning
+    ///
+    /// Verilog and SystemVerilog files are preprocessed with the macros from `defines`.
+    /// Files that have not changed since they were last read reuse their symbols
+    /// from the `cache`.
     pub fn build_full_graph<'a>(
         files: &'a Vec<IpFileNode>,
+        defines: &[Define],
+        cache: &mut UnitCache,
     ) -> Result<GraphMap<CompoundIdentifier, HdlNode<'a>, ()>, Fault> {
         let mut graph_map: GraphMap<CompoundIdentifier, HdlNode, ()> = GraphMap::new();
 
@@ -952,30 +1143,31 @@ impl Plan {
 
         // store the (suffix, prefix) for all entities
         let mut component_pairs: HashMap<LangIdentifier, LangIdentifier> = HashMap::new();
+        // store the include paths for all verilog and systemverilog files
+        let mut includes: Vec<(&IpFileNode, Vec<String>)> = Vec::new();
         // read all files
         for source_file in files {
             // println!("{}", source_file.get_file());
-            match source_file.get_language() {
-                Lang::Vhdl => Self::create_vhdl_node(
-                    &mut graph_map,
-                    source_file,
-                    &mut component_pairs,
-                    &mut sub_nodes,
-                )?,
-                Lang::Verilog => {
-                    Self::create_verilog_node(&mut graph_map, source_file, &mut component_pairs)?
-                }
-                Lang::SystemVerilog => Self::create_systemverilog_node(
-                    &mut graph_map,
-                    source_file,
-                    &mut component_pairs,
-                )?,
+            let paths = Self::create_node(
+                &mut graph_map,
+                source_file,
+                files,
+                &mut component_pairs,
+                &mut sub_nodes,
+                defines,
+                cache,
+            )?;
+            if paths.is_empty() == false {
+                includes.push((source_file, paths));
             }
         }
 
         // add connections for verilog and systemverilog
         Self::connect_edges_from_verilog(&mut graph_map, &mut component_pairs, false);
 
+        // order header files before the files that include them
+        Self::connect_includes(&mut graph_map, files, includes)?;
+
         // go through all architectures and make the connections
         let mut sub_nodes_iter = sub_nodes.into_iter();
         while let Some((lib, node)) = sub_nodes_iter.next() {
@@ -1069,6 +1261,75 @@ impl Plan {
         Ok(graph_map)
     }
 
+    /// Finds the dependencies listed in the manifest of `working_ip` whose units
+    /// are never reached from any unit within the working ip.
+    ///
+    /// Every unit of the working ip is considered as a top, so a dependency is
+    /// used if any of its units are referenced directly or indirectly from the
+    /// working ip. Dependencies are matched by their uuid as resolved in the
+    /// `ip_graph`, so an ip sharing a dependency's name does not mark it as used.
+    pub fn find_unused_deps<'b>(
+        working_ip: &'b Ip,
+        ip_graph: &GraphMap<IpSpec, IpNode, ()>,
+        global_graph: &GraphMap<CompoundIdentifier, HdlNode, ()>,
+    ) -> Vec<&'b PkgPart> {
+        // begin the traversal from every unit within the working ip
+        let mut stack: Vec<usize> = global_graph
+            .get_map()
+            .values()
+            .filter(|n| {
+                n.as_ref()
+                    .get_associated_files()
+                    .iter()
+                    .any(|f| f.get_ip() == working_ip)
+            })
+            .map(|n| n.index())
+            .collect();
+
+        let mut visited: HashSet<usize> = HashSet::new();
+        let mut used: HashSet<&Uuid> = HashSet::new();
+        while let Some(i) = stack.pop() {
+            if visited.insert(i) == false {
+                continue;
+            }
+            if let Some(node) = global_graph.get_node_by_index(i) {
+                for file in node.as_ref().get_associated_files() {
+                    used.insert(file.get_ip().get_uuid());
+                }
+            }
+            // visit the units this unit depends on
+            stack.extend(global_graph.get_graph().predecessors(i));
+        }
+
+        // the direct dependencies are the predecessors of the working ip in the ip graph
+        let direct: Vec<&IpSpec> =
+            match ip_graph.get_node_by_key(&working_ip.get_man().get_ip().into_ip_spec()) {
+                Some(node) => ip_graph
+                    .get_graph()
+                    .predecessors(node.index())
+                    .filter_map(|i| ip_graph.get_key_by_index(i))
+                    .collect(),
+                None => Vec::new(),
+            };
+
+        working_ip
+            .get_man()
+            .get_deps_list(true, true)
+            .into_iter()
+            .filter(|(name, dep)| {
+                direct
+                    .iter()
+                    .filter(|spec| {
+                        spec.get_name() == *name
+                            && dep.as_uuid().is_none_or(|id| id == spec.get_uuid())
+                    })
+                    .any(|spec| used.contains(spec.get_uuid()))
+                    == false
+            })
+            .map(|(name, _)| name)
+            .collect()
+    }
+
     /// Writes the lockfile according to the constructed `ip_graph`. Only writes if the lockfile is
     /// out of date or `force` is `true`.
     pub fn write_lockfile<'c>(
@@ -1119,6 +1380,25 @@ impl Plan {
             .unwrap()
     }
 
+    /// Suggests the components in the `local` graph with names similar to `name`.
+    ///
+    /// Only testbenches are suggested when `is_bench` is `true`, otherwise only
+    /// components that are not testbenches are suggested.
+    fn suggest_components(
+        local: &GraphMap<&CompoundIdentifier, &HdlNode, &()>,
+        name: &Identifier,
+        is_bench: bool,
+    ) -> Hint {
+        let names: Vec<String> = local
+            .get_map()
+            .values()
+            .map(|node| node.as_ref().get_symbol())
+            .filter(|sym| sym.is_component() == true && sym.is_testbench() == is_bench)
+            .map(|sym| sym.get_name().to_string())
+            .collect();
+        Hint::did_you_mean(&name.to_string(), &names, Hint::ShowAvailableUnitsLocal)
+    }
+
     fn detect_bench(
         _graph: &GraphMap<CompoundIdentifier, HdlNode, ()>,
         local: &GraphMap<&CompoundIdentifier, &HdlNode, &()>,
@@ -1143,7 +1423,12 @@ impl Plan {
                         return Err(PlanError::BadEntity(t.clone()))?;
                     }
                 }
-                None => return Err(PlanError::UnknownEntity(t.clone()))?,
+                None => {
+                    return Err(PlanError::UnknownEntity(
+                        t.clone(),
+                        Self::suggest_components(local, t, true),
+                    ))?
+                }
             }
         // try to find the naturally occurring top-level if user did not provide --bench and did not provide --top
         } else if top.is_none() {
@@ -1288,7 +1573,12 @@ impl Plan {
                     // return the index from the local graph
                     Some(n)
                 }
-                None => return Err(PlanError::UnknownEntity(t.clone()))?,
+                None => {
+                    return Err(PlanError::UnknownEntity(
+                        t.clone(),
+                        Self::suggest_components(local, t, false),
+                    ))?
+                }
             }
         } else {
             match natural_top {
@@ -1535,6 +1825,102 @@ impl Plan {
         local_graph
     }
 
+    /// Returns the names of the black box units that appear in `order`.
+    fn collect_black_boxes(
+        global_graph: &GraphMap<CompoundIdentifier, HdlNode, ()>,
+        order: &[usize],
+    ) -> Vec<LangIdentifier> {
+        let mut names: Vec<LangIdentifier> = order
+            .iter()
+            .filter_map(|i| global_graph.get_node_by_index(*i))
+            .filter(|n| n.as_ref().is_black_box() == true)
+            .map(|n| n.as_ref().get_symbol().get_name())
+            .collect();
+        names.sort();
+        names.dedup();
+        names
+    }
+
+    /// Adds the installed ips that provide any units instantiated by `working_ip`
+    /// but not defined within its dependency graph as dependencies in its manifest.
+    ///
+    /// Verilog files are read with the `target`'s macros and the `defines` from the
+    /// command-line. Returns the working ip loaded again from its updated manifest
+    /// if any dependencies were added.
+    pub fn add_missing_deps(
+        c: &Context,
+        working_ip: Ip,
+        target: &Target,
+        defines: &Option<Vec<Define>>,
+        force: bool,
+        cache: &mut UnitCache,
+    ) -> Result<Ip, Fault> {
+        let defines =
+            preprocess::merge_defines(target.get_defines(), defines.as_deref().unwrap_or(&[]));
+        let added = {
+            let catalog = gather_catalog(c, &working_ip, force)?;
+            let ip_graph = algo::compute_final_ip_graph(&working_ip, &catalog, cache)?;
+            let files = algo::build_ip_file_list(&ip_graph, &working_ip);
+            let global_graph = Self::build_full_graph(&files, &defines, cache)?;
+            cache.save()?;
+
+            // find the units instantiated by the working ip's own units
+            let used: Vec<usize> = global_graph
+                .get_map()
+                .values()
+                .filter(|n| {
+                    n.as_ref()
+                        .get_associated_files()
+                        .iter()
+                        .any(|f| f.get_ip() == &working_ip)
+                })
+                .flat_map(|n| global_graph.get_graph().predecessors(n.index()))
+                .collect();
+            let missing = Self::collect_black_boxes(&global_graph, &used);
+            let providers = find_unit_providers(&catalog, &working_ip, &missing)?;
+            if providers.is_empty() == false {
+                Self::add_providers(&working_ip, &catalog, &providers)?;
+            }
+            providers.is_empty() == false
+        };
+        match added {
+            true => Ip::load(working_ip.get_root().clone(), true),
+            false => Ok(working_ip),
+        }
+    }
+
+    /// Writes each ip in `providers` as a dependency in the manifest of `working_ip`.
+    fn add_providers(
+        working_ip: &Ip,
+        catalog: &Catalog,
+        providers: &[(LangIdentifier, &Ip)],
+    ) -> Result<(), Fault> {
+        let man_path = working_ip.get_root().join(IP_MANIFEST_FILE);
+        let mut doc = ManifestDocument::from_file(&man_path)?;
+
+        let mut added: Vec<&Uuid> = Vec::new();
+        for (_, ip) in providers {
+            if added.contains(&ip.get_uuid()) == true {
+                continue;
+            }
+            added.push(ip.get_uuid());
+            let name = ip.get_man().get_ip().get_name();
+            // only write the uuid when the name alone is ambiguous
+            let uuid = match catalog.mappings().get(name) {
+                Some(ids) if ids.len() > 1 => Some(ip.get_uuid()),
+                _ => None,
+            };
+            let requirement = Add::into_requirement(ip.get_man().get_ip().get_version());
+            doc.add_dependency(name, &requirement, None, uuid, false);
+            println!(
+                "info: added {} = \"{}\" to [dependencies]",
+                name, requirement
+            );
+        }
+        doc.write(&man_path)?;
+        Ok(())
+    }
+
     /// Writes the blueprint and env file to the build directory.
     fn create_outputs(
         blueprint: &Blueprint,
@@ -1627,6 +2013,7 @@ impl Plan {
         // assemble the catalog
         let mut catalog = Catalog::new()
             .installations(c.get_cache_path())?
+            .shared_installations(c.get_shared_cache_paths())?
             .downloads(c.get_downloads_path())?;
 
         // @todo: recreate the ip graph from the lockfile, then read each installation
@@ -1642,7 +2029,14 @@ impl Plan {
                 .from_config(c.get_config())?;
             let vtable = StrSwapTable::new().load_environment(&env)?;
 
-            download_missing_deps(vtable, &lf, &le, &catalog, &c.get_config().get_protocols())?;
+            download_missing_deps(
+                vtable,
+                lf,
+                &le,
+                &catalog,
+                &c.get_config().get_protocols(),
+                &c.get_config().get_download_settings(),
+            )?;
             // recollect the downloaded items to update the catalog for installations
             catalog = catalog.downloads(c.get_downloads_path())?;
 
@@ -1658,21 +2052,26 @@ impl Plan {
             None => &default_target_dir,
         };
 
+        let mut cache = UnitCache::open(&working_ip.get_root().join(target_dir));
         let _ = Self::run(
             &working_ip,
-            target_dir,
             target,
             catalog,
-            self.clean,
-            self.force,
-            self.only_lock,
-            self.all,
-            &self.bench,
-            &self.top,
-            &self.filesets,
-            &Scheme::default(),
-            false,
-            true,
+            &PlanOptions {
+                target_dir: target_dir,
+                clean: self.clean,
+                force: self.force,
+                only_lock: self.only_lock,
+                all: self.all,
+                bench: &self.bench,
+                top: &self.top,
+                filesets: &self.filesets,
+                defines: &None,
+                scheme: &Scheme::default(),
+                require_bench: false,
+                allow_bench: true,
+            },
+            &mut cache,
         );
         Ok(())
     }
@@ -1686,8 +2085,10 @@ pub enum PlanError {
     BadEntity(Identifier),
     TestbenchNoTest(LangIdentifier), // this error gets skipped
     UnknownUnit(Identifier),
-    UnknownEntity(Identifier),
+    UnknownEntity(Identifier, Hint),
     Ambiguous(String, Vec<LangIdentifier>, Hint),
+    /// The include path, the including file, and the files that match the path.
+    AmbiguousInclude(String, String, Vec<String>),
     Empty,
 }
 
@@ -1699,10 +2100,10 @@ impl std::fmt::Display for PlanError {
             Self::TestbenchNoTest(id) => {
                 write!(f, "zero entities are tested in testbench \"{}\"", id)
             }
-            Self::UnknownEntity(id) => write!(
+            Self::UnknownEntity(id, hint) => write!(
                 f,
-                "local ip does not contain any component named \"{}\"",
-                id
+                "local ip does not contain any component named \"{}\"{}",
+                id, hint
             ),
             Self::Empty => write!(f, "zero components found in the local ip"),
             Self::BadEntity(id) => write!(f, "design element \"{}\" is not a component", id),
@@ -1735,6 +2136,15 @@ impl std::fmt::Display for PlanError {
                 }),
                 hint,
             ),
+            Self::AmbiguousInclude(path, source, files) => write!(
+                f,
+                "include path \"{}\" in file \"{}\" matches multiple files:\n{}",
+                path,
+                source,
+                files.iter().enumerate().fold(String::new(), |sum, (i, x)| {
+                    sum + &format!("    {}{}", x, if i + 1 < files.len() { "\n" } else { "" })
+                }),
+            ),
         }
     }
 }
@@ -1742,6 +2152,8 @@ impl std::fmt::Display for PlanError {
 #[cfg(test)]
 mod test {
     use super::*;
+    use crate::core::testing::{manifest_text, write_ip};
+    use std::str::FromStr;
 
     #[test]
     fn remove_multi_occur() {
@@ -1762,4 +2174,210 @@ mod test {
             vec![&9, &8, &7, &6, &5, &4]
         );
     }
+
+    #[test]
+    fn collect_black_boxes() {
+        let mut graph: GraphMap<CompoundIdentifier, HdlNode, ()> = GraphMap::new();
+        for name in ["sync_fifo", "and_gate", "sync_fifo"] {
+            let key = CompoundIdentifier::new(
+                LangIdentifier::new_working(),
+                LangIdentifier::from_str(name).unwrap(),
+            );
+            if graph.has_node_by_key(&key) == false {
+                graph.add_node(
+                    key,
+                    HdlNode::black_box(HdlSymbol::BlackBox(name.to_string())),
+                );
+            }
+        }
+        // only the black boxes within the order are collected
+        assert_eq!(
+            Plan::collect_black_boxes(&graph, &[0]),
+            vec![LangIdentifier::from_str("sync_fifo").unwrap()]
+        );
+        assert_eq!(
+            Plan::collect_black_boxes(&graph, &[1, 0]),
+            vec![
+                LangIdentifier::from_str("and_gate").unwrap(),
+                LangIdentifier::from_str("sync_fifo").unwrap(),
+            ]
+        );
+        assert_eq!(Plan::collect_black_boxes(&graph, &[]), vec![]);
+    }
+
+    #[test]
+    fn finds_unit_providers() {
+        let dir = tempfile::tempdir().unwrap();
+        let cache = dir.path().join("cache");
+        write_ip(
+            &cache.join("gates"),
+            &manifest_text("gates", "a5xt109p1pz6moqez7h8x969v", "1.2.0", &[]),
+            &["and_gate", "or_gate"],
+        );
+        write_ip(
+            &cache.join("alu"),
+            &manifest_text("alu", "b5xt109p1pz6moqez7h8x969v", "1.2.0", &[]),
+            &["adder"],
+        );
+        let work = dir.path().join("work");
+        write_ip(
+            &work,
+            &manifest_text("work", "c5xt109p1pz6moqez7h8x969v", "1.2.0", &[]),
+            &["top"],
+        );
+        let working_ip = Ip::load(work.clone(), true).unwrap();
+
+        let catalog = Catalog::new().installations(&cache).unwrap();
+        let missing = vec![
+            LangIdentifier::from_str("or_gate").unwrap(),
+            LangIdentifier::from_str("adder").unwrap(),
+            LangIdentifier::from_str("multiplier").unwrap(),
+        ];
+        let providers: Vec<(String, String)> = find_unit_providers(&catalog, &working_ip, &missing)
+            .unwrap()
+            .iter()
+            .map(|(unit, ip)| {
+                (
+                    unit.to_string(),
+                    ip.get_man().get_ip().get_name().to_string(),
+                )
+            })
+            .collect();
+        assert_eq!(
+            providers,
+            vec![
+                (String::from("adder"), String::from("alu")),
+                (String::from("or_gate"), String::from("gates")),
+            ]
+        );
+
+        // the working ip never provides its own units
+        let missing = vec![LangIdentifier::from_str("top").unwrap()];
+        assert_eq!(
+            find_unit_providers(&catalog, &working_ip, &missing)
+                .unwrap()
+                .is_empty(),
+            true
+        );
+    }
+
+    #[test]
+    fn adds_providers_to_manifest() {
+        let dir = tempfile::tempdir().unwrap();
+        let cache = dir.path().join("cache");
+        write_ip(
+            &cache.join("gates"),
+            &manifest_text("gates", "a5xt109p1pz6moqez7h8x969v", "1.2.0", &[]),
+            &["and_gate", "or_gate"],
+        );
+        // a second ip with the same name must be written with its uuid
+        write_ip(
+            &cache.join("alu"),
+            &manifest_text("alu", "b5xt109p1pz6moqez7h8x969v", "1.2.0", &[]),
+            &["adder"],
+        );
+        write_ip(
+            &cache.join("alu2"),
+            &manifest_text("alu", "d5xt109p1pz6moqez7h8x969v", "1.2.0", &[]),
+            &["mult"],
+        );
+        let work = dir.path().join("work");
+        write_ip(
+            &work,
+            &manifest_text("work", "c5xt109p1pz6moqez7h8x969v", "1.2.0", &[]),
+            &["top"],
+        );
+        let working_ip = Ip::load(work.clone(), true).unwrap();
+
+        let catalog = Catalog::new().installations(&cache).unwrap();
+        let missing = vec![
+            LangIdentifier::from_str("and_gate").unwrap(),
+            LangIdentifier::from_str("or_gate").unwrap(),
+            LangIdentifier::from_str("adder").unwrap(),
+        ];
+        let providers = find_unit_providers(&catalog, &working_ip, &missing).unwrap();
+        assert_eq!(providers.len(), 3);
+        Plan::add_providers(&working_ip, &catalog, &providers).unwrap();
+
+        let updated_ip = Ip::load(work, true).unwrap();
+        let deps: Vec<String> = updated_ip
+            .get_man()
+            .get_deps_list(false, true)
+            .iter()
+            .map(|(name, dep)| {
+                format!(
+                    "{} {} {}",
+                    name,
+                    dep.get_version(),
+                    dep.as_uuid().map_or(String::new(), |u| u.to_string())
+                )
+            })
+            .collect();
+        assert_eq!(
+            deps,
+            vec!["alu 1.2 b5xt109p1pz6moqez7h8x969v", "gates 1.2 "]
+        );
+    }
+
+    #[test]
+    fn finds_unused_deps() {
+        let dir = tempfile::tempdir().unwrap();
+        let cache = dir.path().join("cache");
+        // "alpha" instantiates a unit from "gamma"
+        write_ip(
+            &cache.join("gamma"),
+            &manifest_text("gamma", "e5xt109p1pz6moqez7h8x969v", "1.2.0", &[]),
+            &["inv"],
+        );
+        write_ip(
+            &cache.join("alpha"),
+            &manifest_text(
+                "alpha",
+                "a5xt109p1pz6moqez7h8x969v",
+                "1.2.0",
+                &[("gamma", "1.2.0")],
+            ),
+            &[],
+        );
+        fs::write(
+            cache.join("alpha").join("buf.vhd"),
+            "entity buf is end entity;\narchitecture rtl of buf is begin\n  u0: entity work.inv;\nend architecture;\n",
+        )
+        .unwrap();
+        write_ip(
+            &cache.join("beta"),
+            &manifest_text("beta", "b5xt109p1pz6moqez7h8x969v", "1.2.0", &[]),
+            &["mux"],
+        );
+
+        // the working ip only instantiates a unit from "alpha"
+        let work = dir.path().join("work");
+        write_ip(
+            &work,
+            &manifest_text(
+                "work",
+                "c5xt109p1pz6moqez7h8x969v",
+                "1.2.0",
+                &[("alpha", "1.2.0"), ("beta", "1.2.0"), ("gamma", "1.2.0")],
+            ),
+            &[],
+        );
+        fs::write(
+            work.join("top.vhd"),
+            "entity top is end entity;\narchitecture rtl of top is begin\n  u0: entity work.buf;\nend architecture;\n",
+        )
+        .unwrap();
+        let working_ip = Ip::load(work, true).unwrap();
+
+        let catalog = Catalog::new().installations(&cache).unwrap();
+        let mut unit_cache = UnitCache::new();
+        let ip_graph =
+            algo::compute_final_ip_graph(&working_ip, &catalog, &mut unit_cache).unwrap();
+        let files = algo::build_ip_file_list(&ip_graph, &working_ip);
+        let global_graph = Plan::build_full_graph(&files, &[], &mut unit_cache).unwrap();
+
+        // "gamma" is only reached through "alpha" but is still used
+        let unused = Plan::find_unused_deps(&working_ip, &ip_graph, &global_graph);
+        assert_eq!(unused, vec![&PkgPart::from_str("beta").unwrap()]);
+    }
 }
@@ -24,17 +24,25 @@ use crate::core::algo;
 use crate::core::catalog::{Catalog, PointerSlot};
 use crate::core::channel::Channel;
 use crate::core::context::Context;
-use crate::core::ip::Ip;
+use crate::core::ip::{Ip, IpSpec};
 use crate::core::iparchive::IpArchive;
+use crate::core::ippointer::{ChannelIndex, ORBIT_YANK_FILE};
+use crate::core::lang::cache::UnitCache;
+use crate::core::lang::compat;
+use crate::core::lang::{LangIdentifier, LangUnit};
 use crate::core::manifest::IP_MANIFEST_FILE;
+use crate::core::swap::StrSwapTable;
+use crate::core::version::{AnyVersion, Version};
 use crate::error::{Error, Hint, LastError};
 use crate::util::anyerror::Fault;
 use crate::util::environment::{EnvVar, Environment, ORBIT_CHAN_INDEX};
 use crate::util::filesystem;
+use colored::Colorize;
 
 use cliproc::{cli, proc, stage::*};
 use cliproc::{Arg, Cli, Help, Subcommand};
 
+use super::download::{Download, ProtocolMap};
 use super::helps::publish::HELP;
 use super::install::Install;
 use super::remove::Remove;
@@ -44,6 +52,8 @@ pub struct Publish {
     ready: bool,
     no_install: bool,
     list: bool,
+    allow_breaking: bool,
+    yank: Option<Version>,
 }
 
 impl Subcommand<Context> for Publish {
@@ -52,7 +62,9 @@ impl Subcommand<Context> for Publish {
         Ok(Publish {
             list: cli.check(Arg::flag("list"))?,
             no_install: cli.check(Arg::flag("no-install"))?,
+            allow_breaking: cli.check(Arg::flag("allow-breaking"))?,
             ready: cli.check(Arg::flag("ready").switch('y'))?,
+            yank: cli.get(Arg::option("yank").value("version"))?,
         })
     }
 
@@ -127,9 +139,15 @@ impl Subcommand<Context> for Publish {
             chan.run_sync(&env)?;
         }
 
+        // mark a previously published version as yanked and exit
+        if let Some(version) = &self.yank {
+            return Self::yank_all(&local_ip, version, channels, env);
+        }
+
         // verify the version of the ip does not already exist at the available level
         let catalog = Catalog::new()
             .installations(c.get_cache_path())?
+            .shared_installations(c.get_shared_cache_paths())?
             .downloads(c.get_downloads_path())?
             .available(&channels)?;
 
@@ -148,7 +166,12 @@ impl Subcommand<Context> for Publish {
             }
         }
 
-        if let Err(e) = Self::run_ip_checkpoints(&local_ip, &catalog) {
+        // reuse the design units of any unchanged files from previous runs
+        let mut cache = UnitCache::open(&local_ip.get_root().join(c.get_target_dir()));
+        let checkpoints =
+            Self::run_ip_checkpoints(&local_ip, &catalog, self.allow_breaking, c, &mut cache);
+        cache.save()?;
+        if let Err(e) = checkpoints {
             return Err(Box::new(Error::PublishFailedCheckpoint(LastError(
                 e.to_string(),
             ))));
@@ -178,7 +201,13 @@ impl Subcommand<Context> for Publish {
 }
 
 impl Publish {
-    pub fn run_ip_checkpoints(local_ip: &Ip, catalog: &Catalog) -> Result<(), Fault> {
+    pub fn run_ip_checkpoints(
+        local_ip: &Ip,
+        catalog: &Catalog,
+        allow_breaking: bool,
+        c: &Context,
+        cache: &mut UnitCache,
+    ) -> Result<(), Fault> {
         // verify the lock file is generated and up to date
         println!("info: {}", "verifying lockfile is up to date ...");
         if local_ip.can_use_lock(&catalog) == false {
@@ -204,15 +233,162 @@ impl Publish {
 
         // verify the graph build with no errors
         println!("info: {}", "verifying hardware graph construction ...");
-        if let Err(e) = Self::check_graph_builds_okay(&local_ip, &catalog) {
+        if let Err(e) = Self::check_graph_builds_okay(&local_ip, &catalog, cache) {
             return Err(Box::new(Error::PublishHdlGraphFailed(LastError(
                 e.to_string(),
             ))))?;
         }
 
+        // verify the public units are compatible with the previous version
+        println!(
+            "info: {}",
+            "verifying interface compatibility with previous version ..."
+        );
+        Self::check_interface_compatibility(local_ip, catalog, allow_breaking, c)?;
+
         Ok(())
     }
 
+    /// Compares the public units of the `local_ip` against the highest version
+    /// of the ip published before it.
+    ///
+    /// Breaking changes are only allowed when the major version is incremented,
+    /// or the minor version for versions below 1.0.0. Otherwise, they are
+    /// reported as an error unless `allow_breaking` is `true`.
+    pub fn check_interface_compatibility(
+        local_ip: &Ip,
+        catalog: &Catalog,
+        allow_breaking: bool,
+        c: &Context,
+    ) -> Result<(), Fault> {
+        let version = local_ip.get_man().get_ip().get_version();
+        // versions are already sorted from highest to lowest
+        let previous = match catalog.get_possible_versions(local_ip.get_uuid()) {
+            Some(versions) => versions
+                .into_iter()
+                .map(|v| v.get_version().clone())
+                .find(|v| v < version),
+            None => None,
+        };
+        let previous = match previous {
+            Some(v) => v,
+            None => return Ok(()),
+        };
+        if Self::allows_breaking_changes(&previous, version) == true {
+            return Ok(());
+        }
+        let old_units = match Self::read_previous_units(local_ip, catalog, &previous, c)? {
+            Some(units) => units,
+            None => match allow_breaking {
+                true => {
+                    println!(
+                        "{} unable to compare interfaces with version {} because its source code is not available",
+                        "warning:".yellow(),
+                        previous
+                    );
+                    return Ok(());
+                }
+                false => Err(Error::PublishPreviousUnavailable(
+                    previous.clone(),
+                    Hint::PublishAllowBreaking,
+                ))?,
+            },
+        };
+
+        let old = compat::collect_interfaces(&old_units);
+        let new = compat::collect_interfaces(&local_ip.collect_units(true, true)?);
+        let changes = compat::find_breaking_changes(&old, &new);
+        if changes.is_empty() == true {
+            return Ok(());
+        }
+        match allow_breaking {
+            true => {
+                for change in &changes {
+                    println!("{} {}", "warning:".yellow(), change);
+                }
+                Ok(())
+            }
+            false => Err(Error::PublishBreakingChanges(
+                previous,
+                version.clone(),
+                changes
+                    .iter()
+                    .map(|c| format!("  - {}", c))
+                    .collect::<Vec<String>>()
+                    .join("\n"),
+                Hint::PublishAllowBreaking,
+            ))?,
+        }
+    }
+
+    /// Reads the design units of the `previous` version of the `local_ip`.
+    ///
+    /// An installed version is read in place. Otherwise, the version is extracted
+    /// from its archive in the downloads, which is first downloaded from the
+    /// source listed in its channel if needed. Returns `None` if the version's
+    /// source code cannot be found.
+    fn read_previous_units(
+        local_ip: &Ip,
+        catalog: &Catalog,
+        previous: &Version,
+        c: &Context,
+    ) -> Result<Option<HashMap<LangIdentifier, LangUnit>>, Fault> {
+        let lvl = match catalog.inner().get(local_ip.get_uuid()) {
+            Some(lvl) => lvl,
+            None => return Ok(None),
+        };
+        let version = AnyVersion::Specific(previous.to_partial_version());
+        if let Some(ip) = lvl.get_install(&version) {
+            return Ok(Some(ip.collect_units(true, true)?));
+        }
+        let bytes = match lvl.get_download(&version) {
+            Some(ip) => match ip.get_mapping().as_bytes() {
+                Some(b) => b.clone(),
+                None => return Ok(None),
+            },
+            None => {
+                let (source, spec) = match lvl.get_available(&version).and_then(|ip| {
+                    ip.get_man()
+                        .get_ip()
+                        .get_source()
+                        .map(|s| (s, ip.get_man().get_ip().into_ip_spec()))
+                }) {
+                    Some(s) => s,
+                    None => return Ok(None),
+                };
+                let env = Environment::new().from_config(c.get_config())?;
+                let mut vtable = StrSwapTable::new().load_environment(&env)?;
+                env.initialize();
+                let protocols: ProtocolMap = c.get_config().get_protocols();
+                Download::download(
+                    &mut vtable,
+                    Some(&spec.to_partial_ip_spec()),
+                    source,
+                    None,
+                    c.get_downloads_path(),
+                    &protocols,
+                    &c.get_config().get_download_settings(),
+                    false,
+                    false,
+                )?
+                .1
+            }
+        };
+        let dir = tempfile::tempdir()?;
+        IpArchive::extract(&bytes, dir.path())?;
+        let previous_ip = Ip::load(dir.path().to_path_buf(), false)?;
+        Ok(Some(previous_ip.collect_units(true, true)?))
+    }
+
+    /// Checks if moving from the `previous` version to the `next` version is
+    /// allowed to break compatibility.
+    fn allows_breaking_changes(previous: &Version, next: &Version) -> bool {
+        match previous.get_major() {
+            0 => next.get_major() > 0 || next.get_minor() > previous.get_minor(),
+            _ => next.get_major() > previous.get_major(),
+        }
+    }
+
     pub fn test_download_and_install(
         local_ip: &Ip,
         c: &Context,
@@ -296,11 +472,16 @@ impl Publish {
         }
     }
 
-    pub fn check_graph_builds_okay(local_ip: &Ip, catalog: &Catalog) -> Result<(), Fault> {
+    /// Any design units already stored in the `cache` are not parsed again.
+    pub fn check_graph_builds_okay(
+        local_ip: &Ip,
+        catalog: &Catalog,
+        cache: &mut UnitCache,
+    ) -> Result<(), Fault> {
         // use all language settings
-        let ip_graph = algo::compute_final_ip_graph(&local_ip, &catalog)?;
+        let ip_graph = algo::compute_final_ip_graph(&local_ip, &catalog, cache)?;
         let files = algo::build_ip_file_list(&ip_graph, &local_ip);
-        let _global_graph = Plan::build_full_graph(&files)?;
+        let _global_graph = Plan::build_full_graph(&files, &[], cache)?;
         Ok(())
     }
 
@@ -340,16 +521,64 @@ impl Publish {
         Ok(())
     }
 
-    /// Creates the path where an ip will place its pointer contents.
+    /// Marks the `version` of the ip as yanked in each channel that holds it.
     ///
-    /// The directory is something like this: `uuid[0]/uuid-version`.
+    /// A yanked version is kept in the channel so existing lockfiles can still
+    /// resolve it, but it is skipped when selecting versions for new requirements.
+    /// Errors if none of the `channels` hold the version.
+    fn yank_all(
+        local_ip: &Ip,
+        version: &Version,
+        channels: HashMap<&String, &Channel>,
+        mut env: Environment,
+    ) -> Result<(), Fault> {
+        let name = local_ip.get_man().get_ip().get_name();
+        let spec = IpSpec::new(name.clone(), local_ip.get_uuid().clone(), version.clone());
+        let index_dir = PointerSlot::new(name, local_ip.get_uuid(), version).to_channel_dir();
+        let holders: Vec<(&&String, &&Channel)> = channels
+            .iter()
+            .filter(|(_, chan)| {
+                chan.get_root()
+                    .join(&index_dir)
+                    .join(IP_MANIFEST_FILE)
+                    .exists()
+            })
+            .collect();
+        if holders.is_empty() == true {
+            let mut names: Vec<String> = channels.keys().map(|n| format!("{:?}", n)).collect();
+            names.sort();
+            return Err(Error::YankNotPublished(spec, names.join(", ")))?;
+        }
+        for (name, chan) in holders {
+            let index_path = chan.get_root().join(&index_dir);
+            if index_path.join(ORBIT_YANK_FILE).exists() == true {
+                println!(
+                    "info: ip {} is already yanked from {:?} channel",
+                    spec, name
+                );
+                continue;
+            }
+            println!("info: yanking ip {} from {:?} channel ...", spec, name);
+            env = env.overwrite(EnvVar::with(
+                ORBIT_CHAN_INDEX,
+                filesystem::into_std_str(index_path.clone()).as_str(),
+            ));
+            chan.run_pre(&env)?;
+            std::fs::write(index_path.join(ORBIT_YANK_FILE), "")?;
+            ChannelIndex::sync(chan.get_root())?;
+            chan.run_post(&env)?;
+        }
+        Ok(())
+    }
+
+    /// Creates the path where an ip will place its pointer contents.
     fn create_pointer_directory(ip: &Ip) -> PathBuf {
-        let name = ip.get_man().get_ip().get_name();
-        let version = ip.get_man().get_ip().get_version();
-        let uuid = ip.get_uuid();
-        PathBuf::new()
-            .join(String::from(uuid.encode().chars().next().unwrap()))
-            .join(PointerSlot::new(name, uuid, version).as_ref())
+        PointerSlot::new(
+            ip.get_man().get_ip().get_name(),
+            ip.get_uuid(),
+            ip.get_man().get_ip().get_version(),
+        )
+        .to_channel_dir()
     }
 
     /// Writes the ip's manifest to the channel.
@@ -365,6 +594,8 @@ impl Publish {
         )?;
         // copy the (raw) lockfile there
         local_ip.get_lock().save_to_disk(&output_path)?;
+        // add the new pointer to the channel's index
+        ChannelIndex::sync(channel.get_root())?;
         Ok(())
     }
 
@@ -387,11 +618,17 @@ impl Publish {
         }
 
         let index_dir = Self::create_pointer_directory(&local_ip);
-        let index_path = channel.get_root().join(index_dir);
+        let index_path = channel.get_root().join(&index_dir);
 
         if index_path.exists() && index_path.is_dir() {
             std::fs::remove_dir_all(index_path)?;
         }
+        // remove the pointer from the channel's index
+        if let Some(mut index) = ChannelIndex::load(channel.get_root()) {
+            if index.remove(&index_dir) == true {
+                index.save(channel.get_root())?;
+            }
+        }
         // check if we should remove the first-layer directory
         let first_dir = PathBuf::from(String::from(
             local_ip
@@ -21,7 +21,7 @@ use std::io::Write;
 use std::path::PathBuf;
 use std::str::FromStr;
 
-use super::get::GetError;
+use super::get::{Get, GetError};
 use crate::commands::helps::read;
 use crate::core::catalog::Catalog;
 use crate::core::context::Context;
@@ -111,7 +111,9 @@ impl Subcommand<Context> for Read {
         // checking external IP
         if let Some(spec) = &self.ip {
             // gather the catalog (all manifests)
-            let catalog = Catalog::new().installations(c.get_cache_path())?;
+            let catalog = Catalog::new()
+                .installations(c.get_cache_path())?
+                .shared_installations(c.get_shared_cache_paths())?;
 
             // access the requested ip
             match catalog.translate_name(&spec.to_pkg_name())? {
@@ -287,7 +289,11 @@ impl Read {
                 let spec = ip.get_man().get_ip().into_ip_spec();
                 return Err(GetError::SuggestShow(
                     GetError::UnitNotFound(unit.clone(), spec.clone()).to_string(),
-                    Hint::ShowAvailableUnitsExternal(spec),
+                    Hint::did_you_mean(
+                        &unit.to_string(),
+                        &Get::list_unit_names(&units, is_local),
+                        Hint::ShowAvailableUnitsExternal(spec),
+                    ),
                 ))?;
             }
         };
@@ -55,6 +55,7 @@ impl Subcommand<Context> for Remove {
         // collect the catalog from dev and installations
         let catalog = Catalog::new()
             .installations(c.get_cache_path())?
+            .shared_installations(c.get_shared_cache_paths())?
             .downloads(c.get_downloads_path())?;
 
         // check for ip in development or installation
@@ -112,6 +113,13 @@ impl Subcommand<Context> for Remove {
             None => archived_ip.unwrap().get_man().get_ip().into_ip_spec(),
         };
 
+        // shared caches are read-only, so their installations are never removed
+        if let Some(t) = cached_ip {
+            if catalog.is_shared_install(t) == true {
+                return Err(Error::RemoveSharedInstall(ip_spec, t.get_root().clone()))?;
+            }
+        }
+
         // TODO: issue a warning if the ip to be deleted is not found in a channel (this action may be
         // unrecoverable because there is no channel configured for this ip)
 
@@ -0,0 +1,63 @@
+//
+//  Copyright (C) 2022-2025  Chase Ruskin
+//
+//  This program is free software: you can redistribute it and/or modify
+//  it under the terms of the GNU General Public License as published by
+//  the Free Software Foundation, either version 3 of the License, or
+//  (at your option) any later version.
+//
+//  This program is distributed in the hope that it will be useful,
+//  but WITHOUT ANY WARRANTY; without even the implied warranty of
+//  MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+//  GNU General Public License for more details.
+//
+//  You should have received a copy of the GNU General Public License
+//  along with this program.  If not, see <http://www.gnu.org/licenses/>.
+//
+
+use super::add::Add;
+use super::helps::rm;
+use crate::core::context::Context;
+use crate::core::manifest::{FromFile, IpName, ManifestDocument, IP_MANIFEST_FILE};
+use crate::error::Error;
+
+use cliproc::{cli, proc, stage::*};
+use cliproc::{Arg, Cli, Help, Subcommand};
+
+#[derive(Debug, PartialEq)]
+pub struct Rm {
+    ip: Vec<IpName>,
+}
+
+impl Subcommand<Context> for Rm {
+    fn interpret<'c>(cli: &'c mut Cli<Memory>) -> cli::Result<Self> {
+        cli.help(Help::with(rm::HELP))?;
+        // collect all positional arguments
+        let mut ip: Vec<IpName> = vec![cli.require(Arg::positional("ip"))?];
+        while let Some(name) = cli.get(Arg::positional("ip"))? {
+            ip.push(name);
+        }
+        Ok(Rm { ip: ip })
+    }
+
+    fn execute(self, c: &Context) -> proc::Result {
+        // check that user is in an IP directory
+        c.jump_to_working_ip()?;
+
+        let ip_path = c.get_ip_path().unwrap().clone();
+
+        let man_path = ip_path.join(IP_MANIFEST_FILE);
+        let mut doc = ManifestDocument::from_file(&man_path)?;
+        // verify each requested ip is an entry in the manifest before editing
+        for name in &self.ip {
+            if doc.remove_dependency(name) == false {
+                return Err(Error::DependencyNotFound(name.clone()))?;
+            }
+        }
+        Add::write_and_lock(c, &ip_path, &mut doc)?;
+        for name in &self.ip {
+            println!("info: removed {} from the manifest", name);
+        }
+        Ok(())
+    }
+}
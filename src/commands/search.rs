@@ -27,6 +27,7 @@ use cliproc::{Arg, Cli, Help, Subcommand};
 use crate::commands::helps::search;
 use crate::core::catalog::{Catalog, IpLevel, PkgName};
 use crate::core::version::AnyVersion;
+use crate::error::Hint;
 
 #[derive(Debug, PartialEq)]
 pub struct Search {
@@ -59,6 +60,7 @@ impl Subcommand<Context> for Search {
         let mut catalog = Catalog::new();
         // collect installed IP
         catalog = catalog.installations(c.get_cache_path())?;
+        catalog = catalog.shared_installations(c.get_shared_cache_paths())?;
         // collect downloaded IP
         catalog = catalog.downloads(c.get_downloads_path())?;
         // collect available IP
@@ -153,6 +155,18 @@ impl Search {
                 tree.insert(PkgName::new(name, Some(key)), status);
             });
 
+        // suggest similar names when nothing matched the requested ip
+        if tree.is_empty() == true {
+            if let Some(pkgid) = &self.ip {
+                println!(
+                    "info: no ips found matching \"{}\"{}",
+                    pkgid,
+                    Hint::did_you_mean(pkgid.as_ref(), &catalog.get_names(), Hint::CatalogList)
+                );
+                return Ok(());
+            }
+        }
+
         println!(
             "{}",
             Self::fmt_table(
@@ -181,6 +195,7 @@ impl Search {
         //         );
         let header = String::new();
         let mut body = String::new();
+        let mut warnings = Vec::new();
         let mut index = 0;
 
         let default = !(cached || downloaded || available);
@@ -283,6 +298,14 @@ impl Search {
                 },
                 name.get_uuid().unwrap().encode()
             ));
+            if let Some(w) = ip.deprecation_warning() {
+                warnings.push(w);
+            }
+        }
+        // list any deprecation warnings after the displayed ips
+        for w in warnings {
+            body.push_str(&w);
+            body.push('\n');
         }
         // remove final \n from body
         body.pop();
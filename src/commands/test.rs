@@ -23,6 +23,8 @@ use crate::core::catalog::Catalog;
 use crate::core::context::Context;
 use crate::core::fileset::Fileset;
 use crate::core::ip::Ip;
+use crate::core::lang::cache::UnitCache;
+use crate::core::lang::verilog::preprocess::Define;
 use crate::core::lang::vhdl::token::Identifier;
 use crate::core::swap::StrSwapTable;
 use crate::core::target::Process;
@@ -34,7 +36,7 @@ use crate::util::environment::ORBIT_OUT_DIR;
 use crate::util::environment::ORBIT_TARGET;
 use crate::util::environment::{EnvVar, Environment, ORBIT_BLUEPRINT, ORBIT_TARGET_DIR};
 
-use super::plan::{self, Plan};
+use super::plan::{self, Plan, PlanOptions};
 
 #[derive(Debug, PartialEq)]
 pub struct Test {
@@ -50,6 +52,8 @@ pub struct Test {
     dut: Option<Identifier>,
     command: Option<String>,
     filesets: Option<Vec<Fileset>>,
+    defines: Option<Vec<Define>>,
+    add_missing: bool,
     bench: Option<Identifier>,
 }
 
@@ -63,6 +67,7 @@ impl Subcommand<Context> for Test {
             force: cli.check(Arg::flag("force"))?,
             all: cli.check(Arg::flag("all"))?,
             dirty: cli.check(Arg::flag("no-clean"))?,
+            add_missing: cli.check(Arg::flag("add-missing"))?,
             // Options
             dut: cli.get(Arg::option("dut").value("unit"))?,
             bench: cli.get(Arg::option("tb").value("unit"))?,
@@ -71,6 +76,7 @@ impl Subcommand<Context> for Test {
             target_dir: cli.get(Arg::option("target-dir"))?,
             command: cli.get(Arg::option("command").value("path"))?,
             filesets: cli.get_all(Arg::option("fileset").value("key=glob"))?,
+            defines: cli.get_all(Arg::option("define").value("macro"))?,
             // Remaining args
             args: cli.remainder()?,
         })
@@ -122,11 +128,17 @@ impl Subcommand<Context> for Test {
             None => &default_build_dir,
         };
 
+        // depend on the installed ips that provide any missing units before planning
+        let ip = match self.add_missing {
+            true => {
+                let mut cache = UnitCache::open(&ip.get_root().join(target_dir));
+                Plan::add_missing_deps(c, ip, target, &self.defines, self.force, &mut cache)?
+            }
+            false => ip,
+        };
+
         // gather the catalog and resolve any missing dependencies
-        let catalog = Catalog::new()
-            .installations(c.get_cache_path())?
-            .downloads(c.get_downloads_path())?;
-        let catalog = plan::resolve_missing_deps(c, &ip, catalog, self.force)?;
+        let catalog = plan::gather_catalog(c, &ip, self.force)?;
 
         self.run(
             &ip,
@@ -151,22 +163,29 @@ impl Test {
         c: &Context,
         scheme: &Scheme,
     ) -> Result<(), Fault> {
+        // reuse the design units of any unchanged files from previous plans
+        let mut cache = UnitCache::open(&working_ip.get_root().join(target_dir));
+
         // plan the target
         let blueprint_name = Plan::run(
             &working_ip,
-            target_dir,
             target,
             catalog,
-            self.dirty == false,
-            self.force,
-            false,
-            self.all,
-            &self.bench,
-            &self.dut,
-            &self.filesets,
-            &scheme,
-            true,
-            true,
+            &PlanOptions {
+                target_dir: target_dir,
+                clean: self.dirty == false,
+                force: self.force,
+                only_lock: false,
+                all: self.all,
+                bench: &self.bench,
+                top: &self.dut,
+                filesets: &self.filesets,
+                defines: &self.defines,
+                scheme: scheme,
+                require_bench: true,
+                allow_bench: true,
+            },
+            &mut cache,
         )?
         .unwrap_or_default();
 
@@ -22,14 +22,16 @@ use crate::core::algo;
 use crate::core::algo::IpFileNode;
 use crate::core::catalog::Catalog;
 use crate::core::context::Context;
+use crate::core::export::{ExportEdge, ExportFormat, ExportNode, GraphExport};
 use crate::core::ip::Ip;
+use crate::core::lang::cache::UnitCache;
 use crate::core::lang::node::HdlNode;
 use crate::core::lang::node::HdlSymbol;
 use crate::core::lang::node::IdentifierFormat;
 use crate::core::lang::node::SubUnitNode;
 use crate::core::lang::reference::CompoundIdentifier;
+use crate::core::lang::verilog::preprocess::{self, Define};
 use crate::core::lang::vhdl::token::Identifier as VhdlIdentifier;
-use crate::core::lang::Lang;
 use crate::core::lang::LangIdentifier;
 use crate::error::Error;
 use crate::error::Hint;
@@ -37,6 +39,10 @@ use crate::util::anyerror::Fault;
 use crate::util::graph::EdgeStatus;
 use crate::util::graphmap::GraphMap;
 use std::collections::HashMap;
+use std::collections::HashSet;
+use std::collections::VecDeque;
+use std::hash::Hash;
+use std::str::FromStr;
 
 use cliproc::{cli, proc, stage::*};
 use cliproc::{Arg, Cli, Help, Subcommand};
@@ -44,8 +50,13 @@ use cliproc::{Arg, Cli, Help, Subcommand};
 #[derive(Debug, PartialEq)]
 pub struct Tree {
     roots: Option<Vec<VhdlIdentifier>>,
-    // compress: bool,
+    compress: bool,
+    depth: Option<usize>,
     format: Option<IdentifierFormat>,
+    output: Option<ExportFormat>,
+    invert: Option<String>,
+    target: Option<String>,
+    defines: Option<Vec<Define>>,
     ascii: bool,
     ip: bool,
 }
@@ -54,16 +65,27 @@ impl Subcommand<Context> for Tree {
     fn interpret<'c>(cli: &'c mut Cli<Memory>) -> cli::Result<Self> {
         cli.help(Help::with(tree::HELP))?;
         Ok(Tree {
-            // TODO: implement compression logic
-            // compress: cli.check(Arg::flag("compress"))?,
+            compress: cli.check(Arg::flag("compress"))?,
             ascii: cli.check(Arg::flag("ascii"))?,
             ip: cli.check(Arg::flag("ip"))?,
+            depth: cli.get(Arg::option("depth").value("n"))?,
             format: cli.get(Arg::option("format").value("fmt"))?,
+            output: cli.get(Arg::option("output").value("fmt"))?,
+            invert: cli.get(Arg::option("invert").value("unit"))?,
+            target: cli.get(Arg::option("target").value("name").switch('t'))?,
+            defines: cli.get_all(Arg::option("define").value("macro"))?,
             roots: cli.get_all(Arg::positional("unit"))?,
         })
     }
 
     fn execute(self, c: &Context) -> proc::Result {
+        // read verilog files with the target's macros and any macros from the command-line
+        let target = c.select_target(&self.target, false, true)?;
+        let defines = preprocess::merge_defines(
+            target.map_or(&[], |t| t.get_defines()),
+            self.defines.as_deref().unwrap_or(&[]),
+        );
+
         // go to the ip directory
         c.jump_to_working_ip()?;
 
@@ -71,30 +93,89 @@ impl Subcommand<Context> for Tree {
         let ip = Ip::load(c.get_ip_path().unwrap().clone(), true)?;
 
         // gather the catalog
-        let catalog = Catalog::new().installations(c.get_cache_path())?;
+        let catalog = Catalog::new()
+            .installations(c.get_cache_path())?
+            .shared_installations(c.get_shared_cache_paths())?;
+
+        // reuse the design units of any unchanged files from previous runs
+        let mut cache = UnitCache::open(&ip.get_root().join(c.get_target_dir()));
 
-        self.run(ip, catalog)
+        self.run(ip, catalog, &defines, &mut cache)?;
+        cache.save()
     }
 }
 
 impl Tree {
-    fn run(&self, target: Ip, catalog: Catalog) -> Result<(), Fault> {
+    fn run(
+        &self,
+        target: Ip,
+        catalog: Catalog,
+        defines: &[Define],
+        cache: &mut UnitCache,
+    ) -> Result<(), Fault> {
         match &self.ip {
-            true => self.run_ip_graph(target, catalog),
-            false => self.run_hdl_graph(target, catalog),
+            true => self.run_ip_graph(target, catalog, cache),
+            false => self.run_hdl_graph(target, catalog, defines, cache),
         }
     }
 
     /// Construct and print the graph at an HDL-entity level.
-    fn run_hdl_graph(&self, target: Ip, catalog: Catalog) -> Result<(), Fault> {
+    fn run_hdl_graph(
+        &self,
+        target: Ip,
+        catalog: Catalog,
+        defines: &[Define],
+        cache: &mut UnitCache,
+    ) -> Result<(), Fault> {
         let working_lib = target.get_hdl_library();
 
         // build graph again but with entire set of all files available from all depdendencies
-        let ip_graph = algo::compute_final_ip_graph(&target, &catalog)?;
+        let ip_graph = algo::compute_final_ip_graph(&target, &catalog, cache)?;
         let files = algo::build_ip_file_list(&ip_graph, &target);
 
         // build the complete graph (using entities as the nodes)
-        let global_graph = Self::build_graph(&files)?;
+        let mut architectures = HashMap::new();
+        let global_graph = Self::build_graph(&files, &mut architectures, defines, cache)?;
+        let instances = |user: usize, used: usize| {
+            Self::count_instances(&global_graph, &architectures, user, used)
+        };
+
+        // find every unit matching the name to display its dependents
+        if let Some(name) = &self.invert {
+            let names: Vec<String> = global_graph
+                .get_map()
+                .keys()
+                .map(|key| key.get_suffix().to_string())
+                .collect();
+            let not_found = || {
+                Error::TreeInvertNotFound(
+                    name.clone(),
+                    Hint::did_you_mean(name, &names, Hint::ShowAvailableUnitsLocal),
+                )
+            };
+            let unit = match LangIdentifier::from_str(name) {
+                Ok(u) => u,
+                Err(_) => return Err(not_found())?,
+            };
+            let roots: Vec<usize> = global_graph
+                .get_map()
+                .iter()
+                .filter(|(key, _)| key.get_suffix() == &unit)
+                .map(|(_, node)| node.index())
+                .collect();
+            if roots.is_empty() == true {
+                return Err(not_found())?;
+            }
+            self.show_graph(
+                &global_graph,
+                &roots,
+                true,
+                Some(&instances),
+                &Self::to_hdl_export_node,
+                &|node| node.display(self.format.as_ref().unwrap_or(&IdentifierFormat::Short)),
+            );
+            return Ok(());
+        }
 
         let roots = match &self.roots {
             Some(user_roots) => {
@@ -115,10 +196,20 @@ impl Tree {
                             id.index()
                         }
                         None => {
+                            let names: Vec<String> = local_graph
+                                .get_map()
+                                .iter()
+                                .filter(|(_, node)| node.as_ref().get_symbol().is_component())
+                                .map(|(key, _)| key.get_suffix().to_string())
+                                .collect();
                             return Err(Error::GetUnitNotFound(
                                 root_name.to_string(),
-                                Hint::ShowAvailableUnitsLocal,
-                            ))?
+                                Hint::did_you_mean(
+                                    &root_name.to_string(),
+                                    &names,
+                                    Hint::ShowAvailableUnitsLocal,
+                                ),
+                            ))?;
                         }
                     };
                     roots.push(Plan::local_to_global(i, &global_graph, &local_graph).index())
@@ -146,64 +237,200 @@ impl Tree {
             }
         };
 
-        // display each root's tree to the console
-        roots
-            .iter()
+        let roots: Vec<usize> = roots
+            .into_iter()
             .filter(|k| {
                 global_graph
-                    .get_node_by_index(**k)
+                    .get_node_by_index(*k)
                     .unwrap()
                     .as_ref()
                     .get_symbol()
                     .is_component()
             })
-            .for_each(|n| {
-                let tree = global_graph.get_graph().treeview(*n);
-                for twig in &tree {
-                    let branch_str = match self.ascii {
-                        true => Self::to_ascii(&twig.0.to_string()),
-                        false => twig.0.to_string(),
-                    };
-                    println!(
-                        "{}{}",
-                        branch_str,
-                        global_graph
-                            .get_node_by_index(twig.1)
-                            .unwrap()
-                            .as_ref()
-                            .display(self.format.as_ref().unwrap_or(&IdentifierFormat::Short))
-                    );
-                }
-            });
+            .collect();
 
+        self.show_graph(
+            &global_graph,
+            &roots,
+            false,
+            Some(&instances),
+            &Self::to_hdl_export_node,
+            &|node| node.display(self.format.as_ref().unwrap_or(&IdentifierFormat::Short)),
+        );
         Ok(())
     }
 
     /// Construct and print the graph at an IP dependency level.
-    fn run_ip_graph(&self, target: Ip, catalog: Catalog) -> Result<(), Fault> {
-        let ip_graph = algo::compute_final_ip_graph(&target, &catalog)?;
+    fn run_ip_graph(
+        &self,
+        target: Ip,
+        catalog: Catalog,
+        cache: &mut UnitCache,
+    ) -> Result<(), Fault> {
+        let ip_graph = algo::compute_final_ip_graph(&target, &catalog, cache)?;
+
+        let (roots, inverted) = match &self.invert {
+            // find every ip matching the name to display its dependents
+            Some(name) => {
+                let roots: Vec<usize> = ip_graph
+                    .get_map()
+                    .iter()
+                    .filter(|(key, _)| &key.get_name().to_string() == name)
+                    .map(|(_, node)| node.index())
+                    .collect();
+                if roots.is_empty() == true {
+                    let names: Vec<String> = ip_graph
+                        .get_map()
+                        .keys()
+                        .map(|key| key.get_name().to_string())
+                        .collect();
+                    return Err(Error::TreeInvertNotFound(
+                        name.clone(),
+                        Hint::did_you_mean(name, &names, Hint::ShowIpTree),
+                    ))?;
+                }
+                (roots, true)
+            }
+            None => (vec![0], false),
+        };
+
+        self.show_graph(
+            &ip_graph,
+            &roots,
+            inverted,
+            None,
+            &|key, node| {
+                let ip = node.as_ip();
+                ExportNode::new(
+                    key.to_string(),
+                    ip.get_man().get_ip().get_name().to_string(),
+                )
+                .library(ip.get_hdl_library().to_string())
+                .ip(key.to_string())
+            },
+            &|node| node.as_ip().get_man().get_ip().into_ip_spec().to_string(),
+        );
+        Ok(())
+    }
+
+    /// Collects the information of a hdl `node` for exporting.
+    fn to_hdl_export_node(key: &CompoundIdentifier, node: &HdlNode) -> ExportNode {
+        let result = ExportNode::new(key.to_string(), node.get_symbol().get_name().to_string());
+        match node.get_associated_files().first() {
+            Some(file) => result
+                .library(file.get_library().to_string())
+                .file(file.get_file().clone())
+                .language(file.get_language().to_string())
+                .ip(file.get_ip().get_man().get_ip().into_ip_spec().to_string()),
+            None => result,
+        }
+    }
 
-        let tree = ip_graph.get_graph().treeview(0);
+    /// Displays the trees of the `graph` starting from each of the `roots`, or
+    /// writes the graph in the requested output format.
+    ///
+    /// When `inverted` is set, the trees show the nodes that depend on each root.
+    /// The compressed trees are labeled with instance counts when `instances` is
+    /// given, which returns the number of times a user instantiates the unit it uses.
+    fn show_graph<K, V>(
+        &self,
+        graph: &GraphMap<K, V, ()>,
+        roots: &Vec<usize>,
+        inverted: bool,
+        instances: Option<&dyn Fn(usize, usize) -> usize>,
+        to_node: &dyn Fn(&K, &V) -> ExportNode,
+        display: &dyn Fn(&V) -> String,
+    ) where
+        K: Eq + Hash + Clone,
+    {
+        // write the graph in the requested format instead of the tree
+        if let Some(fmt) = &self.output {
+            let export = Self::export_graph(graph, roots, inverted, self.depth, to_node);
+            println!("{}", export.write(fmt));
+            return;
+        }
+        // display each root's tree to the console
+        for root in roots {
+            let tree = match self.compress {
+                true => graph.get_graph().compress_treeview(
+                    *root,
+                    inverted,
+                    self.depth,
+                    &|parent, branch| match (instances, inverted) {
+                        (Some(f), false) => f(parent, branch),
+                        (Some(f), true) => f(branch, parent),
+                        (None, _) => 1,
+                    },
+                ),
+                false => match inverted {
+                    true => graph.get_graph().inverted_treeview(*root, self.depth),
+                    false => graph.get_graph().treeview(*root, self.depth),
+                }
+                .into_iter()
+                .map(|(twig, i)| (twig, i, 1))
+                .collect(),
+            };
+            for (twig, i, count) in &tree {
+                let branch_str = match self.ascii {
+                    true => Self::to_ascii(&twig.to_string()),
+                    false => twig.to_string(),
+                };
+                let text = display(graph.get_node_by_index(*i).unwrap().as_ref());
+                match instances.is_some() && *count != 1 {
+                    true => println!("{}{} (x{})", branch_str, text, count),
+                    false => println!("{}{}", branch_str, text),
+                }
+            }
+        }
+    }
 
-        for twig in &tree {
-            let branch_str = match self.ascii {
-                true => Self::to_ascii(&twig.0.to_string()),
-                false => twig.0.to_string(),
+    /// Collects the nodes and edges reachable from the `roots` of the `graph`.
+    ///
+    /// When `inverted` is set, the nodes that depend on the `roots` are collected
+    /// instead. Each edge is directed from the user to the node it uses. Nodes more
+    /// than `limit` levels away from the `roots` are omitted.
+    fn export_graph<K, V>(
+        graph: &GraphMap<K, V, ()>,
+        roots: &Vec<usize>,
+        inverted: bool,
+        limit: Option<usize>,
+        to_node: &dyn Fn(&K, &V) -> ExportNode,
+    ) -> GraphExport
+    where
+        K: Eq + Hash + Clone,
+    {
+        let mut export = GraphExport::new();
+        let mut visited = HashSet::new();
+        // visit the nodes in breadth-first order to find their least depth
+        let mut processing: VecDeque<(usize, usize)> = roots.iter().map(|r| (*r, 0)).collect();
+        while let Some((i, depth)) = processing.pop_front() {
+            if visited.insert(i) == false {
+                continue;
+            }
+            let key = graph.get_key_by_index(i).unwrap();
+            let node = to_node(key, graph.get_node_by_index(i).unwrap().as_ref());
+            let branches: Vec<usize> = match limit.is_some_and(|l| depth >= l) {
+                true => Vec::new(),
+                false => match inverted {
+                    false => graph.get_graph().predecessors(i).collect(),
+                    true => graph.get_graph().successors(i).collect(),
+                },
             };
-            println!(
-                "{}{}",
-                branch_str,
-                ip_graph
-                    .get_node_by_index(twig.1)
-                    .unwrap()
-                    .as_ref()
-                    .as_ip()
-                    .get_man()
-                    .get_ip()
-                    .into_ip_spec()
-            );
+            for j in branches {
+                let other = to_node(
+                    graph.get_key_by_index(j).unwrap(),
+                    graph.get_node_by_index(j).unwrap().as_ref(),
+                );
+                let edge = match inverted {
+                    false => ExportEdge::new(node.get_id().clone(), other.get_id().clone()),
+                    true => ExportEdge::new(other.get_id().clone(), node.get_id().clone()),
+                };
+                export.add_edge(edge);
+                processing.push_back((j, depth + 1));
+            }
+            export.add_node(node);
         }
-        Ok(())
+        export
     }
 
     /// Converts the original treeview text from using extended ascii characters
@@ -223,9 +450,45 @@ impl Tree {
         transform
     }
 
+    /// Counts the number of times the `user` unit instantiates the `used` unit.
+    ///
+    /// An entity's count is taken from its architecture with the most instances.
+    /// Every edge of the `graph` counts as at least one instance.
+    fn count_instances(
+        graph: &GraphMap<CompoundIdentifier, HdlNode, ()>,
+        architectures: &HashMap<usize, Vec<Vec<CompoundIdentifier>>>,
+        user: usize,
+        used: usize,
+    ) -> usize {
+        let name = graph.get_key_by_index(used).unwrap().get_suffix();
+        // every instantiation lists the unit's name without a library
+        let count = |list: &Vec<CompoundIdentifier>| {
+            list.iter()
+                .filter(|c| c.get_prefix().is_none() && c.get_suffix() == name)
+                .count()
+        };
+        let module = graph
+            .get_node_by_index(user)
+            .unwrap()
+            .as_ref()
+            .get_symbol()
+            .get_edge_list_entities()
+            .map_or(0, count);
+        let entity = architectures
+            .get(&user)
+            .map_or(0, |archs| archs.iter().map(count).max().unwrap_or(0));
+        module.max(entity).max(1)
+    }
+
     /// Constructs a graph of the design heirarchy with entity nodes.
+    ///
+    /// The units instantiated within each architecture are stored in
+    /// `architectures` by the index of the architecture's entity.
     fn build_graph<'a>(
         files: &'a Vec<IpFileNode>,
+        architectures: &mut HashMap<usize, Vec<Vec<CompoundIdentifier>>>,
+        defines: &[Define],
+        cache: &mut UnitCache,
     ) -> Result<GraphMap<CompoundIdentifier, HdlNode<'a>, ()>, Fault> {
         // entity identifier, HashNode (hash-node holds entity structs)
         let mut graph_map = GraphMap::<CompoundIdentifier, HdlNode, ()>::new();
@@ -236,22 +499,15 @@ impl Tree {
 
         // read all files (same as planning)
         for source_file in files {
-            match source_file.get_language() {
-                Lang::Vhdl => Plan::create_vhdl_node(
-                    &mut graph_map,
-                    source_file,
-                    &mut component_pairs,
-                    &mut sub_nodes,
-                )?,
-                Lang::Verilog => {
-                    Plan::create_verilog_node(&mut graph_map, source_file, &mut component_pairs)?
-                }
-                Lang::SystemVerilog => Plan::create_systemverilog_node(
-                    &mut graph_map,
-                    source_file,
-                    &mut component_pairs,
-                )?,
-            }
+            Plan::create_node(
+                &mut graph_map,
+                source_file,
+                files,
+                &mut component_pairs,
+                &mut sub_nodes,
+                defines,
+                cache,
+            )?;
         }
 
         // differs from planning below
@@ -274,6 +530,10 @@ impl Tree {
                 None => continue,
             };
             entity_node.as_ref_mut().add_file(node.get_file());
+            architectures
+                .entry(entity_node.index())
+                .or_default()
+                .push(node.get_sub().get_edge_list_entities().clone());
             // create edges by ordered edge list (for entities)
             for dep in node.get_sub().get_edge_list_entities() {
                 // need to locate the key with a suffix matching `dep` if it was a component instantiation
@@ -0,0 +1,372 @@
+//
+//  Copyright (C) 2022-2025  Chase Ruskin
+//
+//  This program is free software: you can redistribute it and/or modify
+//  it under the terms of the GNU General Public License as published by
+//  the Free Software Foundation, either version 3 of the License, or
+//  (at your option) any later version.
+//
+//  This program is distributed in the hope that it will be useful,
+//  but WITHOUT ANY WARRANTY; without even the implied warranty of
+//  MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+//  GNU General Public License for more details.
+//
+//  You should have received a copy of the GNU General Public License
+//  along with this program.  If not, see <http://www.gnu.org/licenses/>.
+//
+
+use super::plan;
+use crate::commands::helps::update;
+use crate::core::catalog::Catalog;
+use crate::core::context::Context;
+use crate::core::ip::{Ip, IpSpec};
+use crate::core::lockfile::{LockEntry, LockFile};
+use crate::core::pkgid::PkgPart;
+use crate::core::swap::StrSwapTable;
+use crate::core::version::{AnyVersion, Version};
+use crate::error::{Error, Hint};
+use crate::util::anyerror::Fault;
+use crate::util::environment::Environment;
+use cliproc::{cli, proc, stage::*};
+use cliproc::{Arg, Cli, Help, Subcommand};
+
+#[derive(Debug, PartialEq)]
+pub struct Update {
+    ip: Vec<PkgPart>,
+    dry_run: bool,
+}
+
+impl Subcommand<Context> for Update {
+    fn interpret<'c>(cli: &'c mut Cli<Memory>) -> cli::Result<Self> {
+        cli.help(Help::with(update::HELP))?;
+        let dry_run = cli.check(Arg::flag("dry-run"))?;
+        // collect all positional arguments
+        let mut ip: Vec<PkgPart> = Vec::new();
+        while let Some(name) = cli.get(Arg::positional("ip"))? {
+            ip.push(name);
+        }
+        let command = Ok(Update {
+            dry_run: dry_run,
+            ip: ip,
+        });
+        command
+    }
+
+    fn execute(self, c: &Context) -> proc::Result {
+        // check that user is in an IP directory
+        c.jump_to_working_ip()?;
+
+        // store the working ip struct
+        let working_ip = Ip::load(c.get_ip_path().unwrap().clone(), true)?;
+
+        // assemble the catalog
+        let mut catalog = Catalog::new()
+            .installations(c.get_cache_path())?
+            .shared_installations(c.get_shared_cache_paths())?
+            .downloads(c.get_downloads_path())?
+            .available(&c.get_config().get_channels())?;
+
+        // the lockfile must already capture the manifest's requirements
+        if working_ip.lock_exists() == false || working_ip.can_use_lock(&catalog) == false {
+            return Err(Error::LockfileOutOfDate(Hint::MakeLock))?;
+        }
+
+        let le: LockEntry = LockEntry::from((&working_ip, true));
+        let lf = working_ip.get_lock();
+
+        // verify each requested ip is an entry in the lockfile
+        for name in &self.ip {
+            if lf
+                .inner()
+                .iter()
+                .find(|e| e.get_name() == name && e.matches_target(&le, &catalog) == false)
+                .is_none()
+            {
+                return Err(Error::UpdateUnknownEntry(name.clone()))?;
+            }
+        }
+
+        let updates = Self::resolve(lf, &le, &self.ip, &catalog);
+
+        // display the differences
+        if updates.is_empty() == true {
+            println!("info: lockfile is already up to date");
+            return Ok(());
+        }
+        for (entry, version) in &updates {
+            println!(
+                "info: {} {} -> {}",
+                entry.get_name(),
+                entry.get_version(),
+                version
+            );
+        }
+
+        if self.dry_run == true {
+            return Ok(());
+        }
+
+        // create a provisional lockfile to fetch the newer versions
+        let mut next = Vec::new();
+        for (entry, version) in &updates {
+            let ver = AnyVersion::Specific(version.to_partial_version());
+            let ip = match catalog
+                .inner()
+                .get(entry.get_uuid())
+                .and_then(|lvl| lvl.get(true, true, &ver))
+            {
+                Some(ip) => ip,
+                None => {
+                    return Err(Error::EntryUnknownIp(IpSpec::from((
+                        entry.get_name().clone(),
+                        entry.get_uuid().clone(),
+                        version.clone(),
+                    ))))?
+                }
+            };
+            next.push((*entry, LockEntry::from((ip, false))));
+        }
+        let staged = lf.replace_entries(next);
+        // stop before downloading anything if the lockfile cannot capture the newer versions
+        Self::check_new_dependencies(&staged, &updates)?;
+
+        let env = Environment::new()
+            // read config.toml for setting any env variables
+            .from_config(c.get_config())?;
+        let vtable = StrSwapTable::new().load_environment(&env)?;
+
+        plan::download_missing_deps(
+            vtable,
+            &staged,
+            &le,
+            &catalog,
+            &c.get_config().get_protocols(),
+            &c.get_config().get_download_settings(),
+        )?;
+        // recollect the downloaded items to update the catalog for installations
+        catalog = catalog.downloads(c.get_downloads_path())?;
+
+        plan::install_missing_deps(&staged, &le, &catalog)?;
+        // recollect the installations to compute the final checksums
+        catalog = catalog.installations(c.get_cache_path())?;
+
+        Self::run(&working_ip, &updates, &catalog)
+    }
+}
+
+impl Update {
+    /// Finds the newest version in the `catalog` for every entry in the lockfile `lf`
+    /// that is named in `names` (or all entries when `names` is empty).
+    ///
+    /// The newest version must satisfy the requirements of each entry that
+    /// depends on it. Entries that are already at their newest version are omitted.
+    fn resolve<'a>(
+        lf: &'a LockFile,
+        le: &LockEntry,
+        names: &Vec<PkgPart>,
+        catalog: &Catalog,
+    ) -> Vec<(&'a LockEntry, Version)> {
+        let mut updates = Vec::new();
+        for entry in lf.inner() {
+            // skip the current project's ip entry and any relative ip
+            if entry.matches_target(le, &catalog) == true || entry.is_relative() == true {
+                continue;
+            }
+            if names.is_empty() == false && names.contains(entry.get_name()) == false {
+                continue;
+            }
+            // collect the requirements placed on this entry by its dependents
+            let reqs = lf.get_requirements(entry, le);
+            // versions are already sorted from highest to lowest
+            let newest = match catalog.get_unyanked_versions(entry.get_uuid()) {
+                Some(versions) => versions
+                    .into_iter()
+                    .map(|v| v.get_version().clone())
+                    .find(|v| reqs.iter().all(|r| r.matches(v) == true)),
+                None => None,
+            };
+            if let Some(version) = newest {
+                if &version > entry.get_version() {
+                    updates.push((entry, version));
+                }
+            }
+        }
+        updates
+    }
+
+    /// Verifies every dependency of the updated entries in the lockfile `lf` is
+    /// also captured by `lf`.
+    ///
+    /// A newer version may require an ip that was never resolved into the lockfile,
+    /// in which case the lockfile must be regenerated instead.
+    fn check_new_dependencies(
+        lf: &LockFile,
+        updates: &Vec<(&LockEntry, Version)>,
+    ) -> Result<(), Fault> {
+        for (entry, version) in updates {
+            let updated = match lf.get(entry.get_name(), &version.to_partial_version()) {
+                Some(e) => e,
+                None => continue,
+            };
+            if let Some(dep) = updated
+                .get_deps()
+                .iter()
+                .find(|d| lf.get_highest(d.get_name(), d.get_version()).is_none())
+            {
+                return Err(Error::UpdateRequiresLock(
+                    updated.to_ip_spec(),
+                    dep.to_string(),
+                    Hint::ForceLock,
+                ))?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Writes the lockfile with the `updates` applied to it.
+    ///
+    /// Assumes each newer version is already installed in the `catalog`.
+    pub fn run(
+        working_ip: &Ip,
+        updates: &Vec<(&LockEntry, Version)>,
+        catalog: &Catalog,
+    ) -> Result<(), Fault> {
+        let mut next = Vec::new();
+        for (entry, version) in updates {
+            let ver = AnyVersion::Specific(version.to_partial_version());
+            let ip = match catalog
+                .inner()
+                .get(entry.get_uuid())
+                .map(|lvl| lvl.get_install(&ver))
+                .flatten()
+            {
+                Some(ip) => ip,
+                None => {
+                    return Err(Error::EntryNotQueued(entry.to_ip_spec()))?;
+                }
+            };
+            next.push((*entry, LockEntry::from((ip, false))));
+        }
+        let lock = working_ip.get_lock().replace_entries(next);
+        Self::check_new_dependencies(&lock, updates)?;
+        lock.save_to_disk(working_ip.get_root())?;
+        println!("info: lockfile updated");
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::core::testing::{self, create_catalog};
+    use std::str::FromStr;
+
+    const APP_ID: &str = "1h875zp8cjtab1lrkdgue5gxk";
+    const LIB_ID: &str = "esefn4auh48jjfz1g6xvwj48r";
+    const MID_ID: &str = "2a4g8t6rs2ydmtqtc6fqx1ttn";
+
+    /// Creates a lockfile for the working ip "app" that requires `lib_req` of
+    /// "lib", which is locked at `lib_ver` and requires `lib_deps`.
+    ///
+    /// When `mid_req` is provided, "mid" is also a dependency that requires
+    /// `mid_req` of "lib".
+    fn create_lockfile(
+        lib_req: &str,
+        lib_ver: &str,
+        lib_deps: &[&str],
+        mid_req: Option<&str>,
+    ) -> LockFile {
+        let mut app_deps = vec![format!("lib+{}:{}", LIB_ID, lib_req)];
+        let mid_deps = [format!("lib+{}:{}", LIB_ID, mid_req.unwrap_or_default())];
+        let mid_deps: Vec<&str> = mid_deps.iter().map(|d| d.as_str()).collect();
+        let mut deps = vec![("lib", LIB_ID, lib_ver, lib_deps)];
+        if mid_req.is_some() == true {
+            app_deps.push(format!("mid+{}:1.0.0", MID_ID));
+            deps.push(("mid", MID_ID, "1.0.0", &mid_deps));
+        }
+        let app_deps: Vec<&str> = app_deps.iter().map(|d| d.as_str()).collect();
+        testing::create_lockfile(("app", APP_ID, "0.1.0", &app_deps), &deps)
+    }
+
+    fn root(lf: &LockFile) -> &LockEntry {
+        lf.get_self_entry(&PkgPart::from_str("app").unwrap())
+            .unwrap()
+    }
+
+    fn libs<'a>(versions: &[&'a str]) -> Vec<(&'a str, &'a str, &'a str)> {
+        versions.iter().map(|v| ("lib", LIB_ID, *v)).collect()
+    }
+
+    #[test]
+    fn resolve_newest_compatible_version() {
+        let catalog = create_catalog(&libs(&["1.0.0", "1.2.0", "2.0.0"]));
+        let lf = create_lockfile("1", "1.0.0", &[], None);
+        let updates = Update::resolve(&lf, root(&lf), &Vec::new(), &catalog);
+        assert_eq!(updates.len(), 1);
+        assert_eq!(updates[0].0.get_name().to_string(), "lib");
+        assert_eq!(updates[0].1, Version::from_str("1.2.0").unwrap());
+    }
+
+    #[test]
+    fn resolve_skips_yanked_versions() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut catalog = create_catalog(&libs(&["1.0.0", "1.2.0"]));
+        testing::yank_version(&mut catalog, dir.path(), "lib", LIB_ID, "1.3.0");
+        let lf = create_lockfile("1", "1.0.0", &[], None);
+        let updates = Update::resolve(&lf, root(&lf), &Vec::new(), &catalog);
+        assert_eq!(updates.len(), 1);
+        assert_eq!(updates[0].1, Version::from_str("1.2.0").unwrap());
+    }
+
+    #[test]
+    fn resolve_already_up_to_date() {
+        let catalog = create_catalog(&libs(&["1.0.0", "1.2.0", "2.0.0"]));
+        let lf = create_lockfile("1", "1.2.0", &[], None);
+        let updates = Update::resolve(&lf, root(&lf), &Vec::new(), &catalog);
+        assert_eq!(updates.len(), 0);
+    }
+
+    #[test]
+    fn resolve_only_named_entries() {
+        let catalog = create_catalog(&libs(&["1.0.0", "1.2.0"]));
+        let lf = create_lockfile("1", "1.0.0", &[], None);
+        let names = vec![PkgPart::from_str("mid").unwrap()];
+        let updates = Update::resolve(&lf, root(&lf), &names, &catalog);
+        assert_eq!(updates.len(), 0);
+
+        let names = vec![PkgPart::from_str("lib").unwrap()];
+        let updates = Update::resolve(&lf, root(&lf), &names, &catalog);
+        assert_eq!(updates.len(), 1);
+    }
+
+    #[test]
+    fn resolve_with_transitive_requirement() {
+        let mut ips = libs(&["1.0.0", "1.1.3", "1.2.0"]);
+        ips.push(("mid", MID_ID, "1.0.0"));
+        let catalog = create_catalog(&ips);
+        // the dependency "mid" restricts "lib" more than the working ip
+        let lf = create_lockfile("1", "1.0.0", &[], Some("1.1"));
+        let updates = Update::resolve(&lf, root(&lf), &Vec::new(), &catalog);
+        assert_eq!(updates.len(), 1);
+        assert_eq!(updates[0].1, Version::from_str("1.1.3").unwrap());
+    }
+
+    #[test]
+    fn check_new_dependencies_not_in_lockfile() {
+        let lf = create_lockfile("1", "1.0.0", &[], None);
+        let entry = lf
+            .inner()
+            .iter()
+            .find(|e| e.get_name().to_string() == "lib")
+            .unwrap();
+        let updates = vec![(entry, Version::from_str("1.2.0").unwrap())];
+
+        // the newer version has no dependencies
+        let next = create_lockfile("1", "1.2.0", &[], None);
+        assert!(Update::check_new_dependencies(&next, &updates).is_ok());
+
+        // the newer version requires an ip that is not in the lockfile
+        let next = create_lockfile("1", "1.2.0", &["extra:1"], None);
+        assert!(Update::check_new_dependencies(&next, &updates).is_err());
+    }
+}
@@ -0,0 +1,258 @@
+//
+//  Copyright (C) 2022-2025  Chase Ruskin
+//
+//  This program is free software: you can redistribute it and/or modify
+//  it under the terms of the GNU General Public License as published by
+//  the Free Software Foundation, either version 3 of the License, or
+//  (at your option) any later version.
+//
+//  This program is distributed in the hope that it will be useful,
+//  but WITHOUT ANY WARRANTY; without even the implied warranty of
+//  MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+//  GNU General Public License for more details.
+//
+//  You should have received a copy of the GNU General Public License
+//  along with this program.  If not, see <http://www.gnu.org/licenses/>.
+//
+
+use super::helps::version;
+use crate::core::context::Context;
+use crate::core::ip::Ip;
+use crate::core::lockfile::LockEntry;
+use crate::core::lockfile::IP_LOCK_FILE;
+use crate::core::manifest::{FromFile, IpVersion, ManifestDocument, IP_MANIFEST_FILE};
+use crate::core::version::{VerStr, VersionError};
+use crate::error::{Error, LastError};
+use crate::util::anyerror::Fault;
+use std::path::Path;
+use std::process::Command;
+use std::str::FromStr;
+
+use cliproc::{cli, proc, stage::*};
+use cliproc::{Arg, Cli, Help, Subcommand};
+
+#[derive(Debug, PartialEq)]
+enum Level {
+    Major,
+    Minor,
+    Patch,
+    Exact(IpVersion),
+}
+
+impl FromStr for Level {
+    type Err = VersionError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "major" => Ok(Self::Major),
+            "minor" => Ok(Self::Minor),
+            "patch" => Ok(Self::Patch),
+            _ => Ok(Self::Exact(IpVersion::from_str(s)?)),
+        }
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub struct Bump {
+    level: Level,
+    label: Option<VerStr>,
+    tag: bool,
+}
+
+impl Subcommand<Context> for Bump {
+    fn interpret<'c>(cli: &'c mut Cli<Memory>) -> cli::Result<Self> {
+        cli.help(Help::with(version::HELP))?;
+        Ok(Bump {
+            // flags
+            tag: cli.check(Arg::flag("tag"))?,
+            // options
+            label: cli.get(Arg::option("label"))?,
+            level: cli.require(Arg::positional("version"))?,
+        })
+    }
+
+    fn execute(self, c: &Context) -> proc::Result {
+        // check that user is in an IP directory
+        c.jump_to_working_ip()?;
+
+        let ip_path = c.get_ip_path().unwrap().clone();
+
+        // store the working ip struct
+        let working_ip = Ip::load(ip_path.clone(), true)?;
+
+        let current = working_ip.get_man().get_ip().get_version();
+        let next = Self::bump(current, &self.level, &self.label);
+        if &next <= current {
+            return Err(Error::VersionNotIncreasing(next, current.clone()))?;
+        }
+
+        // verify the changes can be committed and tagged before making any changes
+        if self.tag == true {
+            Self::check_repository(&ip_path, &next)?;
+        }
+
+        // edit the manifest in place
+        let man_path = ip_path.join(IP_MANIFEST_FILE);
+        let mut doc = ManifestDocument::from_file(&man_path)?;
+        doc.set_version(&next);
+        doc.write(&man_path)?;
+        println!("info: version {} -> {}", current, next);
+
+        // keep the lockfile's entry for the local ip in sync with the manifest
+        if working_ip.lock_exists() == true {
+            let name = working_ip.get_man().get_ip().get_name();
+            if let Some(entry) = working_ip.get_lock().get_self_entry(name) {
+                let updated_ip = Ip::load(ip_path.clone(), true)?;
+                let lock = working_ip
+                    .get_lock()
+                    .replace_entries(vec![(entry, LockEntry::from((&updated_ip, true)))]);
+                lock.save_to_disk(&ip_path)?;
+                println!("info: lockfile updated");
+            }
+        }
+
+        if self.tag == true {
+            Self::create_tag(&ip_path, &next)?;
+            println!("info: committed and created tag {}", next);
+        }
+        Ok(())
+    }
+}
+
+impl Bump {
+    /// Computes the next version from the `current` version by applying the `level`
+    /// and then the `label`.
+    ///
+    /// Incrementing a pre-release only drops its label when the parts after the
+    /// `level` are already 0, so "1.3.0-beta" becomes "1.3.0" for a patch.
+    fn bump(current: &IpVersion, level: &Level, label: &Option<VerStr>) -> IpVersion {
+        let mut next = current.clone();
+        let labeled = current.has_label();
+        match level {
+            // a pre-release is released by dropping the label at the level it prepares
+            Level::Major if labeled && current.get_minor() == 0 && current.get_micro() == 0 => {
+                next = next.label(None)
+            }
+            Level::Minor if labeled && current.get_micro() == 0 => next = next.label(None),
+            Level::Patch if labeled => next = next.label(None),
+            Level::Major => next.inc_major(),
+            Level::Minor => next.inc_minor(),
+            Level::Patch => next.inc_micro(),
+            Level::Exact(v) => next = v.clone(),
+        }
+        match label {
+            Some(l) => next.label(Some(l.clone())),
+            None => next,
+        }
+    }
+
+    /// Runs git with the `args` in the repository at `root`, returning what it
+    /// writes to standard output.
+    ///
+    /// Errors with git's message if the process does not exit successfully.
+    fn git(root: &Path, args: &[&str]) -> Result<String, Fault> {
+        let output = match Command::new("git").current_dir(root).args(args).output() {
+            Ok(o) => o,
+            Err(e) => return Err(Error::VersionTagFailed(LastError(e.to_string())))?,
+        };
+        match output.status.success() {
+            true => Ok(String::from_utf8_lossy(&output.stdout).trim().to_string()),
+            false => Err(Error::VersionTagFailed(LastError(
+                String::from_utf8_lossy(&output.stderr).trim().to_string(),
+            )))?,
+        }
+    }
+
+    /// Returns the files to commit for the new version of the ip at `root`.
+    fn tracked_files(root: &Path) -> Vec<&'static str> {
+        let mut files = vec![IP_MANIFEST_FILE];
+        if root.join(IP_LOCK_FILE).exists() == true {
+            files.push(IP_LOCK_FILE);
+        }
+        files
+    }
+
+    /// Verifies that the repository at `root` can commit the manifest and lockfile
+    /// and tag the `version`.
+    ///
+    /// Errors if the manifest or lockfile have uncommitted changes, if git does not
+    /// know who to commit as, or if the tag already exists.
+    fn check_repository(root: &Path, version: &IpVersion) -> Result<(), Fault> {
+        Self::git(root, &["rev-parse", "--is-inside-work-tree"])?;
+        let files = Self::tracked_files(root);
+        let status = Self::git(
+            root,
+            &[&["status", "--porcelain", "--"], files.as_slice()].concat(),
+        )?;
+        // each line of the status ends with the path of a changed file
+        let changed: Vec<&str> = files
+            .into_iter()
+            .filter(|f| status.lines().any(|l| l.ends_with(f)))
+            .collect();
+        if changed.is_empty() == false {
+            return Err(Error::VersionUncommittedChanges(changed.join(", ")))?;
+        }
+        Self::git(root, &["var", "GIT_COMMITTER_IDENT"])?;
+        let tag = format!("refs/tags/{}", version);
+        if Self::git(root, &["rev-parse", "--verify", "--quiet", &tag]).is_ok() {
+            return Err(Error::VersionTagFailed(LastError(format!(
+                "tag \"{}\" already exists",
+                version
+            ))))?;
+        }
+        Ok(())
+    }
+
+    /// Commits the manifest and lockfile changes and creates an annotated git
+    /// tag named after the `version` in the repository at `root`.
+    fn create_tag(root: &Path, version: &IpVersion) -> Result<(), Fault> {
+        let message = format!("version {}", version);
+        let files = Self::tracked_files(root);
+        Self::git(root, &[&["add", "--"], files.as_slice()].concat())?;
+        Self::git(
+            root,
+            &[&["commit", "-m", &message, "--"], files.as_slice()].concat(),
+        )?;
+        Self::git(root, &["tag", "-a", &version.to_string(), "-m", &message])?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn ut_bump() {
+        let current = IpVersion::from_str("1.2.4").unwrap();
+        let bump = |s: &str, l: Option<&str>| {
+            Bump::bump(
+                &current,
+                &Level::from_str(s).unwrap(),
+                &l.map(|l| VerStr::from_str(l).unwrap()),
+            )
+            .to_string()
+        };
+        assert_eq!(bump("major", None), "2.0.0");
+        assert_eq!(bump("minor", None), "1.3.0");
+        assert_eq!(bump("patch", None), "1.2.5");
+        assert_eq!(bump("minor", Some("beta")), "1.3.0-beta");
+        assert_eq!(bump("1.2.4-rc1", None), "1.2.4-rc1");
+        assert_eq!(Level::from_str("huge").is_err(), true);
+
+        // a pre-release drops its label at the level it prepares
+        let release = |v: &str, s: &str| {
+            Bump::bump(
+                &IpVersion::from_str(v).unwrap(),
+                &Level::from_str(s).unwrap(),
+                &None,
+            )
+            .to_string()
+        };
+        assert_eq!(release("1.3.0-beta", "patch"), "1.3.0");
+        assert_eq!(release("1.3.0-beta", "minor"), "1.3.0");
+        assert_eq!(release("1.3.0-beta", "major"), "2.0.0");
+        assert_eq!(release("1.3.2-beta", "minor"), "1.4.0");
+        assert_eq!(release("2.0.0-rc1", "major"), "2.0.0");
+    }
+}
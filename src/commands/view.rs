@@ -20,7 +20,6 @@ use crate::core::catalog::Catalog;
 use crate::core::context::Context;
 use crate::core::ip::{Ip, PartialIpSpec};
 use crate::core::lang::LangUnit;
-use crate::core::version;
 use crate::core::visibility::Visibility;
 use crate::error::{Error, Hint};
 use crate::util::anyerror::AnyError;
@@ -57,6 +56,7 @@ impl Subcommand<Context> for View {
         // collect all manifests available (load catalog)
         let catalog = Catalog::new()
             .installations(c.get_cache_path())?
+            .shared_installations(c.get_shared_cache_paths())?
             .downloads(c.get_downloads_path())?
             .available(&c.get_config().get_channels())?;
 
@@ -136,7 +136,7 @@ impl Subcommand<Context> for View {
         // display all installed versions in the cache
         if self.versions == true {
             let specified_ver = if let Some(spec) = self.ip.as_ref() {
-                spec.get_version().as_specific()
+                Some(spec.get_version())
             } else {
                 None
             };
@@ -153,10 +153,7 @@ impl Subcommand<Context> for View {
                             vers.iter()
                                 .filter(move |p| {
                                     specified_ver.is_none()
-                                        || version::is_compatible(
-                                            specified_ver.unwrap(),
-                                            &p.get_version(),
-                                        ) == true
+                                        || specified_ver.unwrap().matches(&p.get_version()) == true
                                 })
                                 .for_each(|v| {
                                     data.push_str(&format!(
@@ -36,6 +36,7 @@ use crate::core::version::AnyVersion;
 use super::catalog::PkgName;
 use super::fileset;
 use super::ip::PartialIpSpec;
+use super::lang::cache::UnitCache;
 use super::lang::sv::token::tokenizer::SystemVerilogTokenizer;
 use super::lang::verilog::token::tokenizer::VerilogTokenizer;
 use super::lang::{sv, verilog, vhdl, Lang, LangIdentifier};
@@ -67,6 +68,7 @@ pub fn graph_ip_from_lock(lock: &LockFile) -> Result<GraphMap<IpSpec, &LockEntry
 fn graph_ip<'a>(
     root: &'a Ip,
     catalog: &'a Catalog<'a>,
+    cache: &mut UnitCache,
 ) -> Result<GraphMap<IpSpec, IpNode<'a>, ()>, CodeFault> {
     // create empty graph
     let mut g = GraphMap::new();
@@ -78,7 +80,7 @@ fn graph_ip<'a>(
     let mut processing = vec![(t, root)];
 
     // add root's identifiers and parse files according to the correct language settings
-    let mut unit_map = root.collect_units(true, false)?;
+    let mut unit_map = root.collect_units_cached(true, false, cache)?;
 
     let mut is_root: bool = true;
 
@@ -100,7 +102,7 @@ fn graph_ip<'a>(
                                 existing_node.index()
                             } else {
                                 // check if identifiers are already taken in graph
-                                let units = relative_ip.collect_units(false, true)?;
+                                let units = relative_ip.collect_units_cached(false, true, cache)?;
                                 if let Some(dupe) =
                                     units.iter().find(|(key, _)| unit_map.contains_key(key))
                                 {
@@ -160,9 +162,9 @@ fn graph_ip<'a>(
                     match catalog.translate_name(&PkgName::new(pkgid, dependency.as_uuid()))? {
                         Some(status) => {
                             // find this IP to read its dependencies
-                            match status.get_install(&AnyVersion::Specific(
-                                dependency.get_version().clone(),
-                            )) {
+                            match status
+                                .get_install(&AnyVersion::from(dependency.get_version().clone()))
+                            {
                                 Some(cached_ip) => {
                                     // check if node is already in graph ????
                                     let s = if let Some(existing_node) = g.get_node_by_key(
@@ -171,7 +173,8 @@ fn graph_ip<'a>(
                                         existing_node.index()
                                     } else {
                                         // check if identifiers are already taken in graph
-                                        let units = cached_ip.collect_units(false, true)?;
+                                        let units =
+                                            cached_ip.collect_units_cached(false, true, cache)?;
                                         let dst = if let Some(dupe) =
                                             units.iter().find(|(key, _)| unit_map.contains_key(key))
                                         {
@@ -255,12 +258,16 @@ fn graph_ip<'a>(
     Ok(g)
 }
 
+/// Constructs the final graph at the IP-level.
+///
+/// Any design units already stored in the `cache` are not parsed again.
 pub fn compute_final_ip_graph<'a>(
     target: &'a Ip,
     catalog: &'a Catalog<'a>,
+    cache: &mut UnitCache,
 ) -> Result<GraphMap<IpSpec, IpNode<'a>, ()>, CodeFault> {
     // collect rough outline of ip graph (after this function, the correct files according to language are kept)
-    let mut rough_ip_graph = graph_ip(&target, &catalog)?;
+    let mut rough_ip_graph = graph_ip(&target, &catalog, cache)?;
 
     // keep track of list of neighbors that must perform dst and their lookup-tables to use after processing all direct impacts
     let mut transforms = HashMap::<IpSpec, HashMap<LangIdentifier, String>>::new();
@@ -272,7 +279,7 @@ pub fn compute_final_ip_graph<'a>(
         while let Some((key, node)) = graph_iter.next() {
             if node.as_ref().is_direct_conflict() == true {
                 // remember units if true that a transform occurred
-                let lut = node.as_ref().as_ip().generate_dst_lut();
+                let lut = node.as_ref().as_ip().generate_dst_lut(cache);
                 match transforms.get_mut(key) {
                     // update the hashmap for the key
                     Some(entry) => lut.into_iter().for_each(|pair| {
@@ -292,7 +299,7 @@ pub fn compute_final_ip_graph<'a>(
 
                 while let Some(i) = dependents.next() {
                     // remember units if true that a transform occurred on the direct conflict node
-                    let lut = node.as_ref().as_ip().generate_dst_lut();
+                    let lut = node.as_ref().as_ip().generate_dst_lut(cache);
                     // determine the neighboring node's ip spec
                     let neighbor_key = rough_ip_graph.get_key_by_index(i).unwrap();
 
@@ -16,19 +16,21 @@
 //
 
 use crate::core::fileset;
-use crate::util::anyerror::AnyError;
-use cliproc::cli::Error;
+use crate::util::anyerror::{AnyError, Fault};
 use serde_derive::{Deserialize, Serialize};
 use std::fmt::Display;
 use std::io::Write;
 use std::{fs::File, path::PathBuf, str::FromStr};
 
 use super::algo::IpFileNode;
+use super::ip::Ip;
 
 #[derive(Debug, PartialEq, Serialize, Deserialize, Clone)]
 pub enum Scheme {
+    #[serde(rename = "tsv")]
     Tsv,
-    // Json,
+    #[serde(rename = "json")]
+    Json,
 }
 
 impl Default for Scheme {
@@ -44,6 +46,7 @@ impl Display for Scheme {
             "{}",
             match self {
                 Self::Tsv => "tsv",
+                Self::Json => "json",
             }
         )
     }
@@ -55,7 +58,7 @@ impl FromStr for Scheme {
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         match s.to_ascii_lowercase().as_ref() {
             "tsv" => Ok(Self::Tsv),
-            // "json" => Ok(Self::Json),
+            "json" => Ok(Self::Json),
             _ => Err(AnyError(format!("unknown file format: {}", s))),
         }
     }
@@ -64,24 +67,71 @@ impl FromStr for Scheme {
 #[derive(Debug, PartialEq)]
 pub enum Instruction<'a, 'b> {
     Hdl(&'b IpFileNode<'a>),
+    /// A header file and the directory to search to find it.
+    Include(&'b IpFileNode<'a>, String),
     Auxiliary(String, String, String),
 }
 
+/// Determines the built-in fileset name for the given hdl source file.
+pub fn get_hdl_fileset(file: &str) -> &'static str {
+    if fileset::is_header(file) == true {
+        match fileset::is_systemverilog(file) {
+            true => "SYSV-INC",
+            false => "VLOG-INC",
+        }
+    } else if fileset::is_verilog(file) == true {
+        "VLOG"
+    } else if fileset::is_vhdl(file) == true {
+        "VHDL"
+    } else if fileset::is_systemverilog(file) == true {
+        "SYSV"
+    } else {
+        panic!("unknown file in source file set")
+    }
+}
+
+/// The ip that owns the file referenced in a step of a json blueprint.
+#[derive(Debug, PartialEq, Serialize)]
+struct JsonIp {
+    name: String,
+    version: String,
+    uuid: String,
+}
+
+impl From<&Ip> for JsonIp {
+    fn from(value: &Ip) -> Self {
+        Self {
+            name: value.get_man().get_ip().get_name().to_string(),
+            version: value.get_man().get_ip().get_version().to_string(),
+            uuid: value.get_uuid().to_string(),
+        }
+    }
+}
+
+/// A single step of a json blueprint.
+#[derive(Debug, PartialEq, Serialize)]
+struct JsonStep {
+    fileset: String,
+    library: String,
+    path: String,
+    language: Option<String>,
+    ip: JsonIp,
+    dependency: bool,
+}
+
+/// The top-level structure of a json blueprint.
+#[derive(Debug, PartialEq, Serialize)]
+struct JsonBlueprint {
+    steps: Vec<JsonStep>,
+}
+
 impl<'a, 'b> Instruction<'a, 'b> {
-    pub fn write(&self, format: &Scheme) -> String {
-        match &format {
+    pub fn write(&self, format: &Scheme, working_ip: &Ip) -> Result<String, serde_json::Error> {
+        Ok(match &format {
             Scheme::Tsv => match &self {
                 Self::Hdl(node) => {
                     // match on what type of file we have
-                    let source_set = if fileset::is_verilog(node.get_file()) == true {
-                        "VLOG"
-                    } else if fileset::is_vhdl(node.get_file()) == true {
-                        "VHDL"
-                    } else if fileset::is_systemverilog(node.get_file()) == true {
-                        "SYSV"
-                    } else {
-                        panic!("unknown file in source file set")
-                    };
+                    let source_set = get_hdl_fileset(node.get_file());
                     format!(
                         "{}\t{}\t{}",
                         source_set,
@@ -89,11 +139,44 @@ impl<'a, 'b> Instruction<'a, 'b> {
                         node.get_file()
                     )
                 }
+                Self::Include(node, dir) => format!(
+                    "{}\t{}\t{}",
+                    get_hdl_fileset(node.get_file()),
+                    node.get_library(),
+                    dir
+                ),
                 Self::Auxiliary(key, lib, file) => format!("{}\t{}\t{}", key, lib, file),
             },
-            // Scheme::Json => {
-            //     todo!()
-            // }
+            Scheme::Json => serde_json::to_string(&self.to_json_step(working_ip))?,
+        })
+    }
+
+    /// Collects the information for this instruction as a step in a json blueprint.
+    ///
+    /// Auxiliary files are always collected from the `working_ip`.
+    fn to_json_step(&self, working_ip: &Ip) -> JsonStep {
+        let (node, path) = match &self {
+            Self::Hdl(node) => (node, node.get_file()),
+            // the path of an include step is the directory to search for the header
+            Self::Include(node, dir) => (node, dir),
+            Self::Auxiliary(key, lib, file) => {
+                return JsonStep {
+                    fileset: key.clone(),
+                    library: lib.clone(),
+                    path: file.clone(),
+                    language: None,
+                    ip: JsonIp::from(working_ip),
+                    dependency: false,
+                }
+            }
+        };
+        JsonStep {
+            fileset: get_hdl_fileset(node.get_file()).to_string(),
+            library: node.get_library().to_string(),
+            path: path.clone(),
+            language: Some(node.get_language().to_string()),
+            ip: JsonIp::from(node.get_ip()),
+            dependency: working_ip != node.get_ip(),
         }
     }
 }
@@ -101,22 +184,15 @@ impl<'a, 'b> Instruction<'a, 'b> {
 #[derive(Debug, PartialEq)]
 pub struct Blueprint<'a, 'b> {
     scheme: Scheme,
+    working_ip: &'a Ip,
     steps: Vec<Instruction<'a, 'b>>,
 }
 
-impl<'a, 'b> Default for Blueprint<'a, 'b> {
-    fn default() -> Self {
-        Self {
-            scheme: Scheme::default(),
-            steps: Vec::default(),
-        }
-    }
-}
-
 impl<'a, 'b> Blueprint<'a, 'b> {
-    pub fn new(scheme: Scheme) -> Self {
+    pub fn new(scheme: Scheme, working_ip: &'a Ip) -> Self {
         Self {
             scheme: scheme,
+            working_ip: working_ip,
             steps: Vec::new(),
         }
     }
@@ -124,7 +200,7 @@ impl<'a, 'b> Blueprint<'a, 'b> {
     pub fn get_filename(&self) -> String {
         String::from(match self.scheme {
             Scheme::Tsv => "blueprint.tsv",
-            // Scheme::Json => "blueprint.json",
+            Scheme::Json => "blueprint.json",
         })
     }
 
@@ -133,17 +209,82 @@ impl<'a, 'b> Blueprint<'a, 'b> {
         self.steps.push(instr);
     }
 
-    pub fn write(&self, output_path: &PathBuf) -> Result<(PathBuf, usize), Error> {
+    /// Serializes the blueprint's steps according to its scheme.
+    fn encode(&self) -> Result<String, serde_json::Error> {
+        match &self.scheme {
+            Scheme::Tsv => self.steps.iter().try_fold(String::new(), |mut acc, i| {
+                acc.push_str(i.write(&self.scheme, self.working_ip)?.as_ref());
+                acc.push('\n');
+                Ok(acc)
+            }),
+            Scheme::Json => {
+                let data = JsonBlueprint {
+                    steps: self
+                        .steps
+                        .iter()
+                        .map(|i| i.to_json_step(self.working_ip))
+                        .collect(),
+                };
+                let mut text = serde_json::to_string_pretty(&data)?;
+                text.push('\n');
+                Ok(text)
+            }
+        }
+    }
+
+    pub fn write(&self, output_path: &PathBuf) -> Result<(PathBuf, usize), Fault> {
         let blueprint_path = output_path.join(self.get_filename());
         let mut fd = File::create(&blueprint_path).expect("could not create blueprint file");
         // write the data
-        let data = self.steps.iter().fold(String::new(), |mut acc, i| {
-            acc.push_str(i.write(&self.scheme).as_ref());
-            acc.push('\n');
-            acc
-        });
+        let data = self.encode()?;
         fd.write_all(data.as_bytes())
             .expect("failed to write data to blueprint");
         Ok((blueprint_path, self.steps.len()))
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::core::lang::LangIdentifier;
+    use crate::core::testing::create_ip;
+
+    #[test]
+    fn json_hdl_step() {
+        let working_ip = create_ip("top", "c5xt109p1pz6moqez7h8x969v", "1.2.0");
+        let dep_ip = create_ip("gates", "a5xt109p1pz6moqez7h8x969v", "1.2.0");
+        let lib = LangIdentifier::from_str("gates").unwrap();
+
+        let node = IpFileNode::new(String::from("/ip/gates/and_gate.vhd"), &dep_ip, lib.clone());
+        assert_eq!(
+            Instruction::Hdl(&node)
+                .write(&Scheme::Json, &working_ip)
+                .unwrap(),
+            r#"{"fileset":"VHDL","library":"gates","path":"/ip/gates/and_gate.vhd","language":"vhdl","ip":{"name":"gates","version":"1.2.0","uuid":"a5xt109p1pz6moqez7h8x969v"},"dependency":true}"#
+        );
+
+        // include steps point to the header's directory
+        let node = IpFileNode::new(String::from("/ip/top/defs.vh"), &working_ip, lib);
+        assert_eq!(
+            Instruction::Include(&node, String::from("/ip/top"))
+                .write(&Scheme::Json, &working_ip)
+                .unwrap(),
+            r#"{"fileset":"VLOG-INC","library":"top","path":"/ip/top","language":"verilog","ip":{"name":"top","version":"1.2.0","uuid":"c5xt109p1pz6moqez7h8x969v"},"dependency":false}"#
+        );
+    }
+
+    #[test]
+    fn json_auxiliary_step() {
+        let working_ip = create_ip("top", "c5xt109p1pz6moqez7h8x969v", "1.2.0");
+        assert_eq!(
+            Instruction::Auxiliary(
+                String::from("XDC"),
+                String::from("top"),
+                String::from("/ip/top/pins.xdc")
+            )
+            .write(&Scheme::Json, &working_ip)
+            .unwrap(),
+            r#"{"fileset":"XDC","library":"top","path":"/ip/top/pins.xdc","language":null,"ip":{"name":"top","version":"1.2.0","uuid":"c5xt109p1pz6moqez7h8x969v"},"dependency":false}"#
+        );
+    }
+}
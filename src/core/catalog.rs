@@ -95,6 +95,7 @@ pub struct Catalog<'a> {
     inner: HashMap<Uuid, IpLevel>,
     mappings: HashMap<PkgPart, Vec<Uuid>>,
     cache: Option<&'a PathBuf>,
+    shared: Vec<&'a PathBuf>,
     downloads: Option<&'a PathBuf>,
     available: Option<HashMap<&'a String, &'a PathBuf>>,
 }
@@ -123,6 +124,8 @@ pub struct IpLevel {
     installs: Vec<Ip>,
     downloads: Vec<Ip>,
     available: Vec<Ip>,
+    /// Versions yanked from a channel that are skipped during new resolution.
+    yanked: Vec<Version>,
 }
 
 impl IpLevel {
@@ -131,6 +134,7 @@ impl IpLevel {
             installs: Vec::new(),
             available: Vec::new(),
             downloads: Vec::new(),
+            yanked: Vec::new(),
         }
     }
 
@@ -146,6 +150,9 @@ impl IpLevel {
     }
 
     pub fn add_available(&mut self, m: Ip) -> () {
+        if m.is_yanked() == true {
+            self.yanked.push(m.get_man().get_ip().get_version().clone());
+        }
         self.available.push(m);
     }
 
@@ -161,6 +168,11 @@ impl IpLevel {
         &self.available
     }
 
+    /// Checks if the `version` was yanked from a channel.
+    pub fn is_yanked(&self, version: &Version) -> bool {
+        self.yanked.contains(version)
+    }
+
     pub fn is_available(&self) -> bool {
         self.available.is_empty() == false
     }
@@ -175,17 +187,17 @@ impl IpLevel {
 
     /// Returns the manifest with the most compatible version fitting `version`.
     pub fn get_install(&self, version: &AnyVersion) -> Option<&Ip> {
-        Self::get_target_version(version, self.get_installations())
+        self.get_target_version(version, self.get_installations())
     }
 
     /// Returns the manifest with the most compatible version fitting `version`.
     pub fn get_download(&self, version: &AnyVersion) -> Option<&Ip> {
-        Self::get_target_version(version, self.get_downloads())
+        self.get_target_version(version, self.get_downloads())
     }
 
     /// Returns the manifest with the most compatible version fitting `version`.
     pub fn get_available(&self, version: &AnyVersion) -> Option<&Ip> {
-        Self::get_target_version(version, self.get_availability())
+        self.get_target_version(version, self.get_availability())
     }
 
     /// References the ip matching the most compatible version `version`.
@@ -257,19 +269,20 @@ impl IpLevel {
 
     /// Finds the most compatible version matching `target` among the possible `space`.
     ///
+    /// Yanked versions are skipped unless `target` exactly requests one of them.
+    ///
     /// Returns `None` if no compatible version was found.
     ///
     /// Panics if a development version is entered as `target`.
-    fn get_target_version<'a>(target: &AnyVersion, space: &'a Vec<Ip>) -> Option<&'a Ip> {
+    fn get_target_version<'a>(&self, target: &AnyVersion, space: &'a Vec<Ip>) -> Option<&'a Ip> {
         // find the specified version for the given ip
         let mut latest_version: Option<&Ip> = None;
         space
             .iter()
-            .filter(|ip| match &target {
-                AnyVersion::Specific(v) => {
-                    crate::core::version::is_compatible(v, ip.get_man().get_ip().get_version())
-                }
-                AnyVersion::Latest => true,
+            .filter(|ip| {
+                let version = ip.get_man().get_ip().get_version();
+                target.matches(version)
+                    && (target.is_exact() == true || self.is_yanked(version) == false)
             })
             .for_each(|ip| {
                 if latest_version.is_none()
@@ -294,6 +307,7 @@ impl<'a> Catalog<'a> {
             inner: HashMap::new(),
             mappings: HashMap::new(),
             cache: None,
+            shared: Vec::new(),
             downloads: None,
             available: None,
         }
@@ -341,6 +355,21 @@ impl<'a> Catalog<'a> {
         self.detect(path, &IpLevel::add_install, IpState::Installation)
     }
 
+    /// Searches the read-only `paths` for ip installed, after the cache path.
+    ///
+    /// Installations are only ever written to the cache path, so ip found in
+    /// these directories are used where they are without being copied.
+    pub fn shared_installations(mut self, paths: Vec<&'a PathBuf>) -> Result<Self, Fault> {
+        for path in &paths {
+            // skip any shared caches that are currently unreachable
+            if path.is_dir() == true {
+                self = self.detect(path, &IpLevel::add_install, IpState::Installation)?;
+            }
+        }
+        self.shared = paths;
+        Ok(self)
+    }
+
     /// Searches the `path` for ip downloaded.
     pub fn downloads(mut self, path: &'a PathBuf) -> Result<Self, Fault> {
         self.downloads = Some(&path);
@@ -404,13 +433,22 @@ impl<'a> Catalog<'a> {
                     None,
                     Box::new(Error::IpNotFoundAnywhere(
                         name.name.to_string(),
-                        Hint::CatalogList,
+                        Hint::did_you_mean(
+                            name.name.as_ref(),
+                            &self.get_names(),
+                            Hint::CatalogList,
+                        ),
                     )),
                 ))?
             }
         }
     }
 
+    /// Returns the names of every ip in the catalog.
+    pub fn get_names(&self) -> Vec<String> {
+        self.mappings.keys().map(|n| n.to_string()).collect()
+    }
+
     /// Returns all possible versions found for the `target` ip.
     ///
     /// Returns `None` if the id is not found in the catalog.
@@ -444,6 +482,20 @@ impl<'a> Catalog<'a> {
         Some(arr)
     }
 
+    /// Returns all possible versions found for the `target` ip that may be
+    /// newly resolved, skipping any version yanked from a channel.
+    ///
+    /// Returns `None` if the id is not found in the catalog.
+    pub fn get_unyanked_versions(&self, id: &Uuid) -> Option<Vec<VersionItem<'_>>> {
+        let kaban = self.inner.get(id)?;
+        let arr = self
+            .get_possible_versions(id)?
+            .into_iter()
+            .filter(|v| kaban.is_yanked(v.get_version()) == false)
+            .collect();
+        Some(arr)
+    }
+
     pub fn update_installations(&mut self) -> () {
         todo!()
     }
@@ -486,12 +538,21 @@ impl<'a> Catalog<'a> {
         Ok(self)
     }
 
-    pub fn get_cache_path(&self) -> &PathBuf {
-        self.cache.as_ref().unwrap()
+    pub fn get_cache_path(&self) -> &'a PathBuf {
+        self.cache.unwrap()
+    }
+
+    pub fn get_downloads_path(&self) -> &'a PathBuf {
+        self.downloads.unwrap()
+    }
+
+    pub fn get_shared_cache_paths(&self) -> &Vec<&'a PathBuf> {
+        &self.shared
     }
 
-    pub fn get_downloads_path(&self) -> &PathBuf {
-        self.downloads.as_ref().unwrap()
+    /// Checks if the installed `ip` belongs to one of the read-only shared caches.
+    pub fn is_shared_install(&self, ip: &Ip) -> bool {
+        self.shared.iter().any(|p| ip.get_root().starts_with(p))
     }
 }
 
@@ -535,6 +596,7 @@ impl CacheEntry {
 #[cfg(test)]
 mod test {
     use super::*;
+    use crate::core::testing::{manifest_text, write_ip};
 
     #[test]
     fn disp_set() {
@@ -553,6 +615,44 @@ mod test {
         let ce = CacheEntry::from(&Uuid::nil());
         assert_eq!("0000000000000000000000000000", ce.offset());
     }
+
+    /// Writes the manifest text of the ip "gates" at `version`.
+    fn gates(version: &str) -> String {
+        manifest_text("gates", "a5xt109p1pz6moqez7h8x969v", version, &[])
+    }
+
+    #[test]
+    fn shared_installs_follow_user_cache() {
+        let dir = tempfile::tempdir().unwrap();
+        let user = dir.path().join("cache");
+        let shared = dir.path().join("shared");
+        write_ip(&user.join("gates-1.0.0"), &gates("1.0.0"), &[]);
+        write_ip(&shared.join("gates-1.0.0"), &gates("1.0.0"), &[]);
+        write_ip(&shared.join("gates-1.1.0"), &gates("1.1.0"), &[]);
+
+        let catalog = Catalog::new()
+            .installations(&user)
+            .unwrap()
+            .shared_installations(vec![&shared])
+            .unwrap();
+        let lvl = catalog
+            .inner()
+            .get(&Uuid::from_str("a5xt109p1pz6moqez7h8x969v").unwrap())
+            .unwrap();
+        assert_eq!(lvl.get_installations().len(), 3);
+
+        // the user cache takes priority for the same version
+        let ip = lvl
+            .get_install(&AnyVersion::from_str("1.0.0").unwrap())
+            .unwrap();
+        assert_eq!(ip.get_root(), &user.join("gates-1.0.0"));
+        assert_eq!(catalog.is_shared_install(ip), false);
+
+        // newer versions are still found in the shared cache
+        let ip = lvl.get_install(&AnyVersion::Latest).unwrap();
+        assert_eq!(ip.get_root(), &shared.join("gates-1.1.0"));
+        assert_eq!(catalog.is_shared_install(ip), true);
+    }
 }
 
 type Checksum = String;
@@ -629,6 +729,13 @@ impl PointerSlot {
     pub fn new(_name: &PkgPart, uuid: &Uuid, version: &Version) -> Self {
         Self(format!("{}-{}", uuid, version))
     }
+
+    /// Creates the path where the pointer is placed within a channel.
+    ///
+    /// The directory is something like this: `uuid[0]/uuid-version`.
+    pub fn to_channel_dir(&self) -> PathBuf {
+        PathBuf::from(&self.0[..1]).join(&self.0)
+    }
 }
 
 impl AsRef<str> for PointerSlot {
@@ -18,6 +18,7 @@
 use super::target::Process;
 use crate::core::context::Context;
 use crate::core::ip::Ip;
+use crate::core::ippointer::ChannelIndex;
 use crate::{
     error::Error,
     util::{anyerror::Fault, environment::Environment},
@@ -171,6 +172,8 @@ impl Channel {
         }
         for c in channels {
             c.run_sync(&env)?;
+            // update the index to capture any changes to the channel
+            ChannelIndex::sync(c.get_root())?;
         }
         Ok(())
     }
@@ -30,6 +30,7 @@ use std::collections::HashSet;
 use std::fmt::Display;
 use std::path::PathBuf;
 use std::str::FromStr;
+use std::time::Duration;
 
 use serde_derive::{Deserialize, Serialize};
 
@@ -58,6 +59,41 @@ const PUBLISH_KEY: &str = "publish";
 
 const TOP_KEYS: [&str; 5] = [INCLUDE_KEY, GENERAL_KEY, BUILD_KEY, TEST_KEY, PUBLISH_KEY];
 
+/// The table whose keys are user-defined environment variables.
+pub const ENV_KEY: &str = "env";
+
+/// The full keys of every table field that can be edited through the command-line.
+pub const EDITABLE_KEYS: [&str; 28] = [
+    "general.target-dir",
+    "build.default-target",
+    "test.default-target",
+    "publish.default-channel",
+    "download.connect-timeout",
+    "download.timeout",
+    "download.retries",
+    "download.retry-delay",
+    "download.proxy",
+    "download.no-proxy",
+    "download.ca-bundle",
+    "vhdl-format.highlight-syntax",
+    "vhdl-format.tab-size",
+    "vhdl-format.type-auto-alignment",
+    "vhdl-format.type-offset",
+    "vhdl-format.mapping-auto-alignment",
+    "vhdl-format.mapping-offset",
+    "vhdl-format.indent-interface",
+    "vhdl-format.space-interface-parenthesis",
+    "vhdl-format.instance-name",
+    "verilog-format.highlight-syntax",
+    "verilog-format.tab-size",
+    "verilog-format.name-auto-alignment",
+    "verilog-format.name-offset",
+    "verilog-format.range-offset",
+    "verilog-format.mapping-auto-alignment",
+    "verilog-format.mapping-offset",
+    "verilog-format.instance-name",
+];
+
 use crate::util::anyerror::Fault;
 use toml_edit::Array;
 use toml_edit::Document;
@@ -405,11 +441,33 @@ impl From<Configs> for Config {
 pub struct General {
     #[serde(rename = "target-dir")]
     target_dir: Option<String>,
+    /// Read-only cache directories searched for installations after the user's cache
+    #[serde(rename = "shared-caches")]
+    shared_caches: Option<Vec<PathBuf>>,
 }
 
 impl General {
     pub fn new() -> Self {
-        Self { target_dir: None }
+        Self {
+            target_dir: None,
+            shared_caches: None,
+        }
+    }
+
+    pub fn get_shared_caches(&self) -> Vec<&PathBuf> {
+        match &self.shared_caches {
+            Some(c) => c.iter().collect(),
+            None => Vec::new(),
+        }
+    }
+
+    /// Resolves any relative shared cache directories from the `base` directory.
+    pub fn set_root(&mut self, base: &PathBuf) {
+        if let Some(caches) = &mut self.shared_caches {
+            caches
+                .iter_mut()
+                .for_each(|c| *c = filesystem::resolve_rel_path2(base, c));
+        }
     }
 
     pub fn get_build_dir(&self) -> String {
@@ -427,6 +485,16 @@ impl General {
             if self.target_dir.is_some() == false {
                 self.target_dir = rhs.target_dir
             }
+            // collect the shared caches from every configuration
+            if let Some(mut caches) = rhs.shared_caches {
+                match &mut self.shared_caches {
+                    Some(v) => {
+                        caches.retain(|c| v.contains(c) == false);
+                        v.append(&mut caches);
+                    }
+                    None => self.shared_caches = Some(caches),
+                }
+            }
         }
     }
 }
@@ -521,6 +589,111 @@ impl Test {
     }
 }
 
+#[derive(PartialEq, Debug, Serialize, Deserialize, Clone)]
+#[serde(deny_unknown_fields)]
+pub struct DownloadSettings {
+    #[serde(rename = "connect-timeout")]
+    connect_timeout: Option<u64>,
+    timeout: Option<u64>,
+    retries: Option<u32>,
+    #[serde(rename = "retry-delay")]
+    retry_delay: Option<u64>,
+    proxy: Option<String>,
+    #[serde(rename = "no-proxy")]
+    no_proxy: Option<String>,
+    #[serde(rename = "ca-bundle")]
+    ca_bundle: Option<PathBuf>,
+}
+
+impl DownloadSettings {
+    pub fn new() -> Self {
+        Self {
+            connect_timeout: None,
+            timeout: None,
+            retries: None,
+            retry_delay: None,
+            proxy: None,
+            no_proxy: None,
+            ca_bundle: None,
+        }
+    }
+
+    /// Returns the time allowed to establish a connection, if limited.
+    pub fn get_connect_timeout(&self) -> Option<Duration> {
+        match self.connect_timeout.unwrap_or(30) {
+            0 => None,
+            secs => Some(Duration::from_secs(secs)),
+        }
+    }
+
+    /// Returns the time allowed for an entire transfer, if limited.
+    pub fn get_timeout(&self) -> Option<Duration> {
+        match self.timeout.unwrap_or(0) {
+            0 => None,
+            secs => Some(Duration::from_secs(secs)),
+        }
+    }
+
+    /// Returns the number of times to retry a failed transfer.
+    pub fn get_retries(&self) -> u32 {
+        self.retries.unwrap_or(3)
+    }
+
+    /// Returns the time to wait before the retry following `attempt` failed
+    /// attempts, which doubles with each attempt.
+    pub fn get_retry_delay(&self, attempt: u32) -> Duration {
+        let base = self.retry_delay.unwrap_or(1);
+        Duration::from_secs(base.saturating_mul(2u64.saturating_pow(attempt.min(16))))
+    }
+
+    pub fn get_proxy(&self) -> Option<&String> {
+        self.proxy.as_ref()
+    }
+
+    pub fn get_no_proxy(&self) -> Option<&String> {
+        self.no_proxy.as_ref()
+    }
+
+    pub fn get_ca_bundle(&self) -> Option<&PathBuf> {
+        self.ca_bundle.as_ref()
+    }
+
+    /// Resolves a relative certificate bundle path from the `base` directory.
+    pub fn set_root(&mut self, base: &PathBuf) {
+        if let Some(path) = &self.ca_bundle {
+            self.ca_bundle = Some(filesystem::resolve_rel_path2(base, path));
+        }
+    }
+
+    /// Merges any populated data from `rhs` into attributes that do not already
+    /// have data defined in `self`.
+    pub fn merge(&mut self, rhs: Option<Self>) {
+        if let Some(rhs) = rhs {
+            if self.connect_timeout.is_some() == false {
+                self.connect_timeout = rhs.connect_timeout;
+            }
+            if self.timeout.is_some() == false {
+                self.timeout = rhs.timeout;
+            }
+            if self.retries.is_some() == false {
+                self.retries = rhs.retries;
+            }
+            if self.retry_delay.is_some() == false {
+                self.retry_delay = rhs.retry_delay;
+            }
+            if self.proxy.is_some() == false {
+                self.proxy = rhs.proxy;
+            }
+            if self.no_proxy.is_some() == false {
+                self.no_proxy = rhs.no_proxy;
+            }
+            if self.ca_bundle.is_some() == false {
+                self.ca_bundle = rhs.ca_bundle;
+            }
+        }
+    }
+}
+
 pub const CONFIG_FILE: &str = "config.toml";
 
 #[derive(PartialEq, Debug, Serialize, Deserialize)]
@@ -531,6 +704,7 @@ pub struct Config {
     build: Option<Build>,
     test: Option<Test>,
     publish: Option<Publish>,
+    download: Option<DownloadSettings>,
     env: Option<HashMap<String, String>>,
     target: Option<Targets>,
     protocol: Option<Protocols>,
@@ -561,6 +735,7 @@ impl Config {
             build: None,
             test: None,
             publish: None,
+            download: None,
         }
     }
 
@@ -620,6 +795,11 @@ impl Config {
             Some(v) => v.merge(rhs.publish),
             None => self.publish = rhs.publish,
         }
+        // combine '[download]' table
+        match &mut self.download {
+            Some(v) => v.merge(rhs.download),
+            None => self.download = rhs.download,
+        }
         // combine '[vhdl-format]' table
         match &mut self.vhdl_format {
             Some(v) => v.merge(rhs.vhdl_format),
@@ -674,6 +854,14 @@ impl Config {
         }
     }
 
+    /// Returns the settings for transferring files over the network.
+    pub fn get_download_settings(&self) -> DownloadSettings {
+        match &self.download {
+            Some(d) => d.clone(),
+            None => DownloadSettings::new(),
+        }
+    }
+
     pub fn get_env(&self) -> &Option<HashMap<String, String>> {
         &self.env
     }
@@ -734,6 +922,14 @@ impl Config {
     pub fn get_general(&self) -> Option<&General> {
         self.general.as_ref()
     }
+
+    /// Returns the read-only cache directories to search after the user's cache.
+    pub fn get_shared_caches(&self) -> Vec<&PathBuf> {
+        match &self.general {
+            Some(g) => g.get_shared_caches(),
+            None => Vec::new(),
+        }
+    }
 }
 
 impl FromStr for Config {
@@ -772,6 +968,12 @@ impl FromFile for Config {
                         c.set_root(base.clone())?;
                     }
                 }
+                if let Some(general) = &mut r.general {
+                    general.set_root(&base);
+                }
+                if let Some(download) = &mut r.download {
+                    download.set_root(&base);
+                }
                 Ok(r)
             }
             // enter a blank lock file if failed (do not exit)
@@ -840,6 +1042,64 @@ tab-size = 3
         }
     }
 
+    #[test]
+    fn merge_shared_caches() {
+        let mut base = Config::from_str(
+            "[general]\nshared-caches = [\"/mnt/a\", \"/mnt/b\"]\ntarget-dir = \"build\"\n",
+        )
+        .unwrap();
+        let rhs = Config::from_str(
+            "[general]\nshared-caches = [\"/mnt/b\", \"/mnt/c\"]\ntarget-dir = \"out\"\n",
+        )
+        .unwrap();
+        base.append(rhs);
+        // caches from every configuration are kept in order of precedence
+        assert_eq!(
+            base.get_shared_caches(),
+            vec![
+                &PathBuf::from("/mnt/a"),
+                &PathBuf::from("/mnt/b"),
+                &PathBuf::from("/mnt/c")
+            ]
+        );
+        assert_eq!(base.get_general().unwrap().get_build_dir(), "build");
+        assert_eq!(Config::new().get_shared_caches().is_empty(), true);
+    }
+
+    #[test]
+    fn download_settings() {
+        let defaults = Config::new().get_download_settings();
+        assert_eq!(
+            defaults.get_connect_timeout(),
+            Some(Duration::from_secs(30))
+        );
+        assert_eq!(defaults.get_timeout(), None);
+        assert_eq!(defaults.get_retries(), 3);
+        // the delay doubles with each attempt
+        assert_eq!(defaults.get_retry_delay(0), Duration::from_secs(1));
+        assert_eq!(defaults.get_retry_delay(2), Duration::from_secs(4));
+
+        let mut base = Config::from_str("[download]\nretries = 5\nconnect-timeout = 0\n").unwrap();
+        let rhs = Config::from_str("[download]\nretries = 1\ntimeout = 600\n").unwrap();
+        base.append(rhs);
+        let settings = base.get_download_settings();
+        assert_eq!(settings.get_retries(), 5);
+        assert_eq!(settings.get_connect_timeout(), None);
+        assert_eq!(settings.get_timeout(), Some(Duration::from_secs(600)));
+    }
+
+    #[test]
+    fn editable_keys_are_valid() {
+        for key in EDITABLE_KEYS {
+            let (table, field) = key.split_once('.').unwrap();
+            // the value's type depends on the field
+            let is_valid = ["1", "true", "\"a\""].iter().any(|value| {
+                Config::from_str(&format!("[{}]\n{} = {}\n", table, field, value)).is_ok()
+            });
+            assert_eq!(is_valid, true, "{}", key);
+        }
+    }
+
     #[test]
     fn linked_configs() {
         Configs::new()
@@ -211,6 +211,11 @@ impl Context {
         &self.cache_path
     }
 
+    /// References the read-only cache directories searched after the cache directory.
+    pub fn get_shared_cache_paths(&self) -> Vec<&PathBuf> {
+        self.get_config().get_shared_caches()
+    }
+
     /// References the downloads directory
     pub fn get_downloads_path(&self) -> &PathBuf {
         &self.archive_path
@@ -414,7 +419,18 @@ impl Context {
             // verify the target name matches
             Some(name) => match self.get_config().get_targets().get(name.as_str()) {
                 Some(&t) => Ok(Some(t)),
-                None => Err(Error::TargetNotFound(name.to_string(), Hint::TargetsList)),
+                None => {
+                    let names: Vec<String> = self
+                        .get_config()
+                        .get_targets()
+                        .keys()
+                        .map(|n| n.to_string())
+                        .collect();
+                    Err(Error::TargetNotFound(
+                        name.to_string(),
+                        Hint::did_you_mean(name, &names, Hint::TargetsList),
+                    ))
+                }
             },
             None => match required {
                 true => Err(Error::MissingRequiredTarget),
@@ -0,0 +1,336 @@
+//
+//  Copyright (C) 2022-2025  Chase Ruskin
+//
+//  This program is free software: you can redistribute it and/or modify
+//  it under the terms of the GNU General Public License as published by
+//  the Free Software Foundation, either version 3 of the License, or
+//  (at your option) any later version.
+//
+//  This program is distributed in the hope that it will be useful,
+//  but WITHOUT ANY WARRANTY; without even the implied warranty of
+//  MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+//  GNU General Public License for more details.
+//
+//  You should have received a copy of the GNU General Public License
+//  along with this program.  If not, see <http://www.gnu.org/licenses/>.
+//
+
+//! Exports a dependency graph into formats readable by other tools.
+
+use crate::util::anyerror::AnyError;
+use serde_derive::Serialize;
+use std::collections::HashSet;
+use std::fmt::Display;
+use std::str::FromStr;
+
+#[derive(Debug, PartialEq, Clone)]
+pub enum ExportFormat {
+    Dot,
+    GraphMl,
+    Json,
+}
+
+impl FromStr for ExportFormat {
+    type Err = AnyError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_ref() {
+            "dot" => Ok(Self::Dot),
+            "graphml" => Ok(Self::GraphMl),
+            "json" => Ok(Self::Json),
+            _ => Err(AnyError(String::from(
+                "output can be 'dot', 'graphml', or 'json'",
+            ))),
+        }
+    }
+}
+
+impl Display for ExportFormat {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}",
+            match self {
+                Self::Dot => "dot",
+                Self::GraphMl => "graphml",
+                Self::Json => "json",
+            }
+        )
+    }
+}
+
+/// A single node of an exported graph.
+#[derive(Debug, PartialEq, Serialize)]
+pub struct ExportNode {
+    id: String,
+    name: String,
+    library: Option<String>,
+    file: Option<String>,
+    language: Option<String>,
+    ip: Option<String>,
+}
+
+impl ExportNode {
+    pub fn new(id: String, name: String) -> Self {
+        Self {
+            id: id,
+            name: name,
+            library: None,
+            file: None,
+            language: None,
+            ip: None,
+        }
+    }
+
+    pub fn library(mut self, library: String) -> Self {
+        self.library = Some(library);
+        self
+    }
+
+    pub fn file(mut self, file: String) -> Self {
+        self.file = Some(file);
+        self
+    }
+
+    pub fn language(mut self, language: String) -> Self {
+        self.language = Some(language);
+        self
+    }
+
+    pub fn ip(mut self, ip: String) -> Self {
+        self.ip = Some(ip);
+        self
+    }
+
+    pub fn get_id(&self) -> &String {
+        &self.id
+    }
+
+    /// Lists the optional attributes in a fixed order.
+    fn attributes(&self) -> [(&str, &Option<String>); 4] {
+        [
+            ("library", &self.library),
+            ("file", &self.file),
+            ("language", &self.language),
+            ("ip", &self.ip),
+        ]
+    }
+}
+
+/// A directed reference from the `source` node (the user) to the `target` node
+/// (the one being used).
+#[derive(Debug, PartialEq, Serialize)]
+pub struct ExportEdge {
+    source: String,
+    target: String,
+}
+
+impl ExportEdge {
+    pub fn new(source: String, target: String) -> Self {
+        Self {
+            source: source,
+            target: target,
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Serialize)]
+pub struct GraphExport {
+    nodes: Vec<ExportNode>,
+    edges: Vec<ExportEdge>,
+    /// The ids of the nodes already added.
+    #[serde(skip)]
+    node_ids: HashSet<String>,
+    /// The `(source, target)` pairs of the edges already added.
+    #[serde(skip)]
+    edge_ids: HashSet<(String, String)>,
+}
+
+impl GraphExport {
+    pub fn new() -> Self {
+        Self {
+            nodes: Vec::new(),
+            edges: Vec::new(),
+            node_ids: HashSet::new(),
+            edge_ids: HashSet::new(),
+        }
+    }
+
+    /// Adds the `node` if a node with the same id does not already exist.
+    pub fn add_node(&mut self, node: ExportNode) {
+        if self.node_ids.insert(node.id.clone()) == true {
+            self.nodes.push(node);
+        }
+    }
+
+    /// Adds the `edge` if it does not already exist.
+    pub fn add_edge(&mut self, edge: ExportEdge) {
+        if self
+            .edge_ids
+            .insert((edge.source.clone(), edge.target.clone()))
+            == true
+        {
+            self.edges.push(edge);
+        }
+    }
+
+    /// Serializes the graph according to the `format`.
+    pub fn write(&self, format: &ExportFormat) -> String {
+        match format {
+            ExportFormat::Dot => self.to_dot(),
+            ExportFormat::GraphMl => self.to_graphml(),
+            ExportFormat::Json => serde_json::to_string_pretty(&self).unwrap(),
+        }
+    }
+
+    fn to_dot(&self) -> String {
+        let mut result = String::from("digraph {\n");
+        for node in &self.nodes {
+            result.push_str(&format!(
+                "    \"{}\" [label=\"{}\"",
+                escape_dot(&node.id),
+                escape_dot(&node.name)
+            ));
+            for (key, value) in node.attributes() {
+                if let Some(v) = value {
+                    result.push_str(&format!(", {}=\"{}\"", key, escape_dot(v)));
+                }
+            }
+            result.push_str("];\n");
+        }
+        for edge in &self.edges {
+            result.push_str(&format!(
+                "    \"{}\" -> \"{}\";\n",
+                escape_dot(&edge.source),
+                escape_dot(&edge.target)
+            ));
+        }
+        result.push('}');
+        result
+    }
+
+    fn to_graphml(&self) -> String {
+        let mut result = String::from(
+            "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n\
+<graphml xmlns=\"http://graphml.graphdrawing.org/xmlns\">\n",
+        );
+        for key in ["name", "library", "file", "language", "ip"] {
+            result.push_str(&format!(
+                "  <key id=\"{0}\" for=\"node\" attr.name=\"{0}\" attr.type=\"string\"/>\n",
+                key
+            ));
+        }
+        result.push_str("  <graph edgedefault=\"directed\">\n");
+        for node in &self.nodes {
+            result.push_str(&format!("    <node id=\"{}\">\n", escape_xml(&node.id)));
+            result.push_str(&format!(
+                "      <data key=\"name\">{}</data>\n",
+                escape_xml(&node.name)
+            ));
+            for (key, value) in node.attributes() {
+                if let Some(v) = value {
+                    result.push_str(&format!(
+                        "      <data key=\"{}\">{}</data>\n",
+                        key,
+                        escape_xml(v)
+                    ));
+                }
+            }
+            result.push_str("    </node>\n");
+        }
+        for edge in &self.edges {
+            result.push_str(&format!(
+                "    <edge source=\"{}\" target=\"{}\"/>\n",
+                escape_xml(&edge.source),
+                escape_xml(&edge.target)
+            ));
+        }
+        result.push_str("  </graph>\n</graphml>");
+        result
+    }
+}
+
+fn escape_dot(s: &str) -> String {
+    s.replace('\\', "\\\\").replace('"', "\\\"")
+}
+
+fn escape_xml(s: &str) -> String {
+    s.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+        .replace('\'', "&apos;")
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn sample() -> GraphExport {
+        let mut g = GraphExport::new();
+        g.add_node(
+            ExportNode::new(String::from("work.top"), String::from("top"))
+                .library(String::from("work"))
+                .file(String::from("rtl/top.vhd"))
+                .language(String::from("vhdl"))
+                .ip(String::from("soc:0.1.0")),
+        );
+        g.add_node(ExportNode::new(
+            String::from("work.\"fifo\""),
+            String::from("fifo"),
+        ));
+        g.add_edge(ExportEdge::new(
+            String::from("work.top"),
+            String::from("work.\"fifo\""),
+        ));
+        // duplicates are ignored
+        g.add_edge(ExportEdge::new(
+            String::from("work.top"),
+            String::from("work.\"fifo\""),
+        ));
+        g
+    }
+
+    #[test]
+    fn from_str() {
+        assert_eq!(ExportFormat::from_str("dot").unwrap(), ExportFormat::Dot);
+        assert_eq!(
+            ExportFormat::from_str("GraphML").unwrap(),
+            ExportFormat::GraphMl
+        );
+        assert_eq!(ExportFormat::from_str("json").unwrap(), ExportFormat::Json);
+        assert_eq!(ExportFormat::from_str("svg").is_err(), true);
+    }
+
+    #[test]
+    fn to_dot() {
+        assert_eq!(
+            sample().write(&ExportFormat::Dot),
+            r#"digraph {
+    "work.top" [label="top", library="work", file="rtl/top.vhd", language="vhdl", ip="soc:0.1.0"];
+    "work.\"fifo\"" [label="fifo"];
+    "work.top" -> "work.\"fifo\"";
+}"#
+        );
+    }
+
+    #[test]
+    fn to_graphml() {
+        let text = sample().write(&ExportFormat::GraphMl);
+        assert_eq!(text.contains("<node id=\"work.&quot;fifo&quot;\">"), true);
+        assert_eq!(
+            text.contains("<edge source=\"work.top\" target=\"work.&quot;fifo&quot;\"/>"),
+            true
+        );
+        assert_eq!(text.matches("<edge ").count(), 1);
+    }
+
+    #[test]
+    fn to_json() {
+        let value: serde_json::Value =
+            serde_json::from_str(&sample().write(&ExportFormat::Json)).unwrap();
+        assert_eq!(value["nodes"][0]["language"], "vhdl");
+        assert_eq!(value["nodes"][1]["file"], serde_json::Value::Null);
+        assert_eq!(value["edges"][0]["target"], "work.\"fifo\"");
+    }
+}
@@ -264,6 +264,16 @@ pub fn is_systemverilog(file: &str) -> bool {
     }
 }
 
+/// Checks if the `file` is a Verilog or SystemVerilog header file (ending with .vh, .svh).
+pub fn is_header(file: &str) -> bool {
+    if let Some((_, ending)) = file.rsplit_once('.') {
+        crate::util::strcmp::cmp_ascii_ignore_case(ending, "vh")
+            || crate::util::strcmp::cmp_ascii_ignore_case(ending, "svh")
+    } else {
+        false
+    }
+}
+
 /// Checks if the given file is one of the supported HDLs.
 pub fn is_hdl(file: &str) -> bool {
     is_vhdl(file) || is_verilog(file) || is_systemverilog(file)
@@ -315,6 +325,15 @@ mod test {
         assert_eq!(is_vhdl(s), false);
     }
 
+    #[test]
+    fn detect_header_files() {
+        assert_eq!(is_header("defs.vh"), true);
+        assert_eq!(is_header("defs.SVH"), true);
+        assert_eq!(is_header("defs.v"), false);
+        assert_eq!(is_header("defs.sv"), false);
+        assert_eq!(is_header("vh"), false);
+    }
+
     #[test]
     fn assemble_fileset() {
         let fset = Fileset::new().name("hello_world").pattern("*.txt").unwrap();
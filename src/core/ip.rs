@@ -28,16 +28,18 @@ use super::catalog::PkgName;
 use super::iparchive::IpArchive;
 use super::ippointer::IpPointer;
 use super::lang;
+use super::lang::cache::UnitCache;
 use super::lang::LangIdentifier;
 use super::lang::LangUnit;
 use super::lockfile::LockFile;
 use super::lockfile::IP_LOCK_FILE;
 use super::manifest::FromFile;
-use super::version::PartialVersion;
+use super::version::VersionReq;
 use super::visibility::VipList;
 use super::visibility::Visibility;
 use crate::core::lockfile::LockEntry;
 use crate::core::manifest::IP_MANIFEST_FILE;
+use crate::core::manifest::ORBIT_COMMIT_FILE;
 use crate::core::manifest::ORBIT_METADATA_FILE;
 use crate::core::manifest::ORBIT_SUM_FILE;
 use crate::core::uuid::Uuid;
@@ -113,10 +115,13 @@ pub struct Ip {
     lock: LockFile,
     /// The UUID for the [Ip].
     uuid: Uuid,
+    /// Marks if the [Ip] version was yanked from its channel.
+    yanked: bool,
 }
 
 impl From<IpPointer> for Ip {
     fn from(value: IpPointer) -> Self {
+        let yanked = value.is_yanked();
         let man = value.decouple();
         Self {
             uuid: man.get_ip().get_uuid().clone(),
@@ -124,6 +129,7 @@ impl From<IpPointer> for Ip {
             root: PathBuf::new(),
             data: man,
             lock: LockFile::new(),
+            yanked: yanked,
         }
     }
 }
@@ -147,6 +153,7 @@ impl From<IpArchive> for Ip {
             data: man,
             lock: lock,
             uuid: uuid,
+            yanked: false,
         }
     }
 }
@@ -305,6 +312,7 @@ impl Ip {
             data: man,
             lock: lock,
             uuid: uuid,
+            yanked: false,
         })
     }
 
@@ -348,6 +356,34 @@ impl Ip {
         Self::detect_all_sub(path, IP_MANIFEST_FILE, true, is_working)
     }
 
+    /// Checks if the ip's version was yanked from its channel.
+    pub fn is_yanked(&self) -> bool {
+        self.yanked
+    }
+
+    /// Returns the warning message if the ip's manifest marks it as deprecated.
+    pub fn deprecation_warning(&self) -> Option<String> {
+        self.get_man()
+            .get_ip()
+            .get_deprecated()
+            .as_ref()
+            .map(|msg| {
+                format!(
+                    "{} ip `{}` is deprecated: {}",
+                    "warning:".yellow(),
+                    self.get_man().get_ip().into_ip_spec(),
+                    msg
+                )
+            })
+    }
+
+    /// Prints a warning if the ip's manifest marks it as deprecated.
+    pub fn warn_if_deprecated(&self) -> () {
+        if let Some(msg) = self.deprecation_warning() {
+            println!("{}", msg);
+        }
+    }
+
     /// Checks the metadata file for a entry for `dynamic`.
     pub fn is_dynamic(&self) -> bool {
         self.get_mapping().is_physical() == true
@@ -368,15 +404,16 @@ impl Ip {
     }
 
     /// Creates the lookup table for the DST algorithm.
-    pub fn generate_dst_lut(&self) -> HashMap<LangIdentifier, String> {
+    pub fn generate_dst_lut(&self, cache: &mut UnitCache) -> HashMap<LangIdentifier, String> {
         // compose the lut for symbol transformation
         let mut lut = HashMap::new();
 
         if self.mapping.is_physical() == false {
             return lut;
         }
-        // @todo: read units from metadata to speed up results
-        let units = self.collect_units(true, self.has_public_list()).unwrap();
+        let units = self
+            .collect_units_cached(true, self.has_public_list(), cache)
+            .unwrap();
         let checksum = Ip::read_cache_checksum(self.get_root()).unwrap();
 
         units.into_iter().for_each(|(key, _)| {
@@ -434,7 +471,10 @@ impl Ip {
         }
         // check that all entries are valid of dependencies and dev dependencies
         for dep in self.get_man().get_deps_list(true, true) {
-            if let Some(entry) = self.get_lock().get(dep.0, dep.1.get_version()) {
+            if let Some(entry) = self
+                .get_lock()
+                .get_highest(dep.0, &AnyVersion::from(dep.1.get_version().clone()))
+            {
                 if let Some(relative_ip) = dep.1.as_ip() {
                     if &LockEntry::from((relative_ip, true)) == entry {
                         ()
@@ -498,6 +538,25 @@ impl Ip {
         }
     }
 
+    /// Gets the commit an ip was fetched from over the "git" protocol from [ORBIT_COMMIT_FILE].
+    ///
+    /// Returns `None` if the file does not exist or is unable to read into a string.
+    pub fn read_cache_commit(dir: &PathBuf) -> Option<String> {
+        match std::fs::read_to_string(dir.join(ORBIT_COMMIT_FILE)) {
+            Ok(text) => match text.trim().is_empty() {
+                true => None,
+                false => Some(text.trim().to_string()),
+            },
+            Err(_) => None,
+        }
+    }
+
+    pub fn write_cache_commit(&self, commit: &str) -> Result<(), Fault> {
+        let path = self.get_root().join(manifest::ORBIT_COMMIT_FILE);
+        std::fs::write(&path, commit.as_bytes())?;
+        Ok(())
+    }
+
     pub fn write_cache_checksum(&self, sum: &Sha256Hash) -> Result<(), Fault> {
         let path = self.get_root().join(manifest::ORBIT_SUM_FILE);
         std::fs::write(&path, sum.to_string().as_bytes())?;
@@ -557,7 +616,20 @@ impl Ip {
         force: bool,
         hide_private: bool,
     ) -> Result<HashMap<LangIdentifier, LangUnit>, CodeFault> {
-        let public_list = self.into_public_list();
+        self.collect_units_cached(force, hide_private, &mut UnitCache::new())
+    }
+
+    /// Gathers the list of primary design units for the current ip, only parsing
+    /// the files that are not already stored in the `cache`.
+    ///
+    /// If the manifest has an toml entry for `units` and `force` is set to `false`,
+    /// then it will return that list rather than go through files.
+    pub fn collect_units_cached(
+        &self,
+        force: bool,
+        hide_private: bool,
+        cache: &mut UnitCache,
+    ) -> Result<HashMap<LangIdentifier, LangUnit>, CodeFault> {
         // try to read from metadata file
         match force == false && Self::read_units_from_metadata(&self.get_root()).is_some() {
             // use precomputed result
@@ -566,61 +638,72 @@ impl Ip {
                 // collect all files
                 let files = self.gather_current_files();
 
-                let mut map = lang::collect_units(&files)?;
-
-                // work to remove files that are totally private
-                if public_list.exists() == true {
-                    // track which files are private and have no references or only private references
-                    let mut private_set: HashSet<LangIdentifier> = map
-                        .iter_mut()
-                        .filter_map(|(k, v)| {
-                            // the node is implicitly private, but so far only known to be protected
-                            if v.is_listed_public(&public_list) == false {
-                                v.set_visibility(Visibility::Protected);
-                                Some(k.clone())
-                            } else {
-                                None
-                            }
-                        })
-                        .collect();
-                    let mut visited: HashSet<LangIdentifier> = HashSet::new();
-                    map.iter().for_each(|(_k, v)| {
-                        // the node is explicitly public
-                        if v.is_listed_public(&public_list) == true {
-                            // if the reference is used by a public then it and its nesteddeps are not totally invisible
-                            let mut stack = v.get_references();
-                            while let Some(item) = stack.pop() {
-                                // remove this item from the private map
-                                private_set.remove(&item);
-                                if visited.contains(&item) == false {
-                                    if let Some(id) = map.get(&item) {
-                                        for refer in id.get_references() {
-                                            // println!("{:?}", refer);
-                                            stack.push(refer);
-                                        }
-                                    }
+                let map = lang::collect_units(&files, cache)?;
+                Ok(self.resolve_visibility(map, hide_private))
+            }
+        }
+    }
+
+    /// Sets the visibility of each unit in `map` according to the ip's public list.
+    ///
+    /// If `hide_private` is `true`, then units that are totally private are removed.
+    fn resolve_visibility(
+        &self,
+        mut map: HashMap<LangIdentifier, LangUnit>,
+        hide_private: bool,
+    ) -> HashMap<LangIdentifier, LangUnit> {
+        let public_list = self.into_public_list();
+        // work to remove files that are totally private
+        if public_list.exists() == true {
+            // track which files are private and have no references or only private references
+            let mut private_set: HashSet<LangIdentifier> = map
+                .iter_mut()
+                .filter_map(|(k, v)| {
+                    // the node is implicitly private, but so far only known to be protected
+                    if v.is_listed_public(&public_list) == false {
+                        v.set_visibility(Visibility::Protected);
+                        Some(k.clone())
+                    } else {
+                        None
+                    }
+                })
+                .collect();
+            let mut visited: HashSet<LangIdentifier> = HashSet::new();
+            map.iter().for_each(|(_k, v)| {
+                // the node is explicitly public
+                if v.is_listed_public(&public_list) == true {
+                    // if the reference is used by a public then it and its nesteddeps are not totally invisible
+                    let mut stack = v.get_references();
+                    while let Some(item) = stack.pop() {
+                        // remove this item from the private map
+                        private_set.remove(&item);
+                        if visited.contains(&item) == false {
+                            if let Some(id) = map.get(&item) {
+                                for refer in id.get_references() {
+                                    // println!("{:?}", refer);
+                                    stack.push(refer);
                                 }
-                                visited.insert(item);
                             }
                         }
-                    });
-                    // println!("totally private: {:?}", private_set);
-                    for k in &private_set {
-                        if let Some(v) = map.get_mut(k) {
-                            v.set_visibility(Visibility::Private);
-                        }
-                    }
-                    if hide_private == true {
-                        // remove totally invisible units from list
-                        map = map
-                            .into_iter()
-                            .filter(|(k, _v)| private_set.contains(k) == false)
-                            .collect();
+                        visited.insert(item);
                     }
                 }
-                Ok(map)
+            });
+            // println!("totally private: {:?}", private_set);
+            for k in &private_set {
+                if let Some(v) = map.get_mut(k) {
+                    v.set_visibility(Visibility::Private);
+                }
+            }
+            if hide_private == true {
+                // remove totally invisible units from list
+                map = map
+                    .into_iter()
+                    .filter(|(k, _v)| private_set.contains(k) == false)
+                    .collect();
             }
         }
+        map
     }
 
     pub fn read_units_from_metadata(dir: &PathBuf) -> Option<HashMap<LangIdentifier, LangUnit>> {
@@ -836,8 +919,8 @@ use crate::core::version::AnyVersion;
 pub struct PartialIpSpec(PkgPart, Option<Uuid>, AnyVersion);
 
 impl PartialIpSpec {
-    pub fn new(name: PkgPart, uuid: Option<Uuid>, version: PartialVersion) -> Self {
-        Self(name, uuid, AnyVersion::Specific(version))
+    pub fn new(name: PkgPart, uuid: Option<Uuid>, version: VersionReq) -> Self {
+        Self(name, uuid, AnyVersion::from(version))
     }
 
     pub fn get_name(&self) -> &PkgPart {
@@ -21,14 +21,30 @@ use super::{
 };
 use crate::error::LastError;
 use crate::util::anyerror::Fault;
+use crate::util::filesystem;
 use crate::{core::manifest::FromFile, error::Error};
-use std::path::PathBuf;
+use serde_derive::{Deserialize, Serialize};
+use std::collections::HashSet;
+use std::fmt::Display;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+use std::time::SystemTime;
 
 /// The ip pointer stores the manifest for an ip, to be used to grab the ip from another
 /// location not already on the user's local file system.
 #[derive(Debug, PartialEq)]
 pub struct IpPointer {
     manifest: Manifest,
+    yanked: bool,
+}
+
+impl From<Manifest> for IpPointer {
+    fn from(value: Manifest) -> Self {
+        Self {
+            manifest: value,
+            yanked: false,
+        }
+    }
 }
 
 impl IpPointer {
@@ -36,6 +52,11 @@ impl IpPointer {
         self.manifest
     }
 
+    /// Checks if the pointer's version was yanked from the channel.
+    pub fn is_yanked(&self) -> bool {
+        self.yanked
+    }
+
     /// Loads an IpPointer struct.
     pub fn read(path: PathBuf) -> Result<Self, Fault> {
         let man_path = path.join(IP_MANIFEST_FILE);
@@ -45,13 +66,22 @@ impl IpPointer {
             )))?;
         }
         let man = Manifest::from_file(&man_path)?;
-        Ok(Self { manifest: man })
+        Ok(Self {
+            yanked: path.join(ORBIT_YANK_FILE).exists(),
+            manifest: man,
+        })
     }
 
     /// Finds all Manifest files available in the provided path `path`.
     ///
+    /// The channel's index is read when it exists and is up to date, otherwise
+    /// the directories are walked to find each manifest.
+    ///
     /// Errors if on filesystem problems.
     pub fn detect_all(path: &PathBuf) -> Result<Vec<Ip>, Fault> {
+        if let Some(index) = ChannelIndex::load_current(path) {
+            return Ok(index.into_ips());
+        }
         let mut result = Vec::new();
         // walk the directory
         for mut entry in manifest::find_file(&path, IP_MANIFEST_FILE, false)? {
@@ -65,3 +95,394 @@ impl IpPointer {
         Ok(result)
     }
 }
+
+pub const CHANNEL_INDEX_FILE: &str = "Orbit.index";
+
+/// The marker file placed in a pointer's directory when its version is yanked.
+pub const ORBIT_YANK_FILE: &str = ".orbit-yanked";
+
+const INDEX_VERSION: usize = 1;
+const INDEX_COMMENT: &str =
+    "# This file is automatically @generated by Orbit.\n# It is not intended for manual editing.";
+
+/// The index of every ip pointer within a channel.
+///
+/// Storing the manifests in a single file allows a channel to be read without
+/// walking its directories and parsing each manifest individually.
+#[derive(Debug, PartialEq, Deserialize, Serialize)]
+pub struct ChannelIndex {
+    version: usize,
+    #[serde(default)]
+    ip: Vec<IndexEntry>,
+}
+
+#[derive(Debug, PartialEq, Deserialize, Serialize)]
+pub struct IndexEntry {
+    /// The pointer's directory relative to the channel's root.
+    path: String,
+    /// Excluded from new version resolution when set.
+    #[serde(default, skip_serializing_if = "is_false")]
+    yanked: bool,
+    manifest: Manifest,
+}
+
+fn is_false(b: &bool) -> bool {
+    *b == false
+}
+
+impl IndexEntry {
+    pub fn get_path(&self) -> &String {
+        &self.path
+    }
+
+    pub fn get_man(&self) -> &Manifest {
+        &self.manifest
+    }
+
+    pub fn is_yanked(&self) -> bool {
+        self.yanked
+    }
+}
+
+impl FromStr for ChannelIndex {
+    type Err = toml::de::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        toml::from_str(s)
+    }
+}
+
+impl Display for ChannelIndex {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", toml::to_string_pretty(&self).unwrap())
+    }
+}
+
+impl ChannelIndex {
+    /// Creates a new empty [ChannelIndex].
+    pub fn new() -> Self {
+        Self {
+            version: INDEX_VERSION,
+            ip: Vec::new(),
+        }
+    }
+
+    pub fn inner(&self) -> &Vec<IndexEntry> {
+        &self.ip
+    }
+
+    /// Reads the index stored at the channel's `root`.
+    ///
+    /// Returns `None` if the index does not exist or cannot be read.
+    pub fn load(root: &PathBuf) -> Option<Self> {
+        let path = root.join(CHANNEL_INDEX_FILE);
+        if path.is_file() == false {
+            return None;
+        }
+        let index = Self::from_str(&std::fs::read_to_string(&path).ok()?).ok()?;
+        match index.version == INDEX_VERSION {
+            true => Some(index),
+            false => None,
+        }
+    }
+
+    /// Reads the index stored at the channel's `root` only if it is up to date.
+    ///
+    /// The index is out of date when the channel's root, any of its top-level
+    /// directories, or any indexed pointer was modified after the index was
+    /// written. Returns `None` if the index does not exist, cannot be read, or
+    /// is out of date.
+    pub fn load_current(root: &PathBuf) -> Option<Self> {
+        let written = modified(&root.join(CHANNEL_INDEX_FILE))?;
+        let index = Self::load(root)?;
+        let is_stale = |p: &Path| modified(p).is_none_or(|t| t > written);
+
+        // a pointer was added to or removed from the channel
+        if is_stale(root) == true || top_level_dirs(root).ok()?.iter().any(|d| is_stale(d)) == true
+        {
+            return None;
+        }
+        // a pointer was edited or yanked
+        match index.ip.iter().any(|e| {
+            let dir = root.join(&e.path);
+            is_stale(&dir) || is_stale(&dir.join(IP_MANIFEST_FILE))
+        }) {
+            true => None,
+            false => Some(index),
+        }
+    }
+
+    /// Stores the pointer `ptr` at the `root`-relative directory `path`,
+    /// replacing any entry already at that directory.
+    pub fn insert(&mut self, path: &PathBuf, ptr: IpPointer) -> () {
+        let path = filesystem::into_std_str(path.clone());
+        self.remove(&PathBuf::from(&path));
+        self.ip.push(IndexEntry {
+            path: path,
+            yanked: ptr.yanked,
+            manifest: ptr.manifest,
+        });
+        self.ip.sort_by(|a, b| a.path.cmp(&b.path));
+    }
+
+    /// Removes the entry for the pointer at the `root`-relative directory `path`.
+    ///
+    /// Returns `true` if an entry was removed.
+    pub fn remove(&mut self, path: &PathBuf) -> bool {
+        let path = filesystem::into_std_str(path.clone());
+        let len = self.ip.len();
+        self.ip.retain(|e| e.path != path);
+        len != self.ip.len()
+    }
+
+    /// Updates the index at the channel's `root` to match the pointers that
+    /// exist in its directories.
+    ///
+    /// Only the parts of the channel modified since the index was last written
+    /// are read again. Entries for pointers that no longer exist are removed,
+    /// entries for pointers that were edited or yanked are read again, and only
+    /// the top-level directories that changed are walked to find new pointers.
+    /// The index is only written when it is out of date.
+    pub fn sync(root: &PathBuf) -> Result<Self, Fault> {
+        // without an index, every pointer is read
+        let (mut index, written) =
+            match (Self::load(root), modified(&root.join(CHANNEL_INDEX_FILE))) {
+                (Some(index), Some(written)) => (index, written),
+                _ => (Self::new(), SystemTime::UNIX_EPOCH),
+            };
+        let is_stale = |p: &Path| modified(p).is_none_or(|t| t > written);
+        let mut changed = written == SystemTime::UNIX_EPOCH || is_stale(root);
+
+        // refresh the entries for pointers that were removed, edited, or yanked
+        let mut entries = Vec::with_capacity(index.ip.len());
+        for e in std::mem::take(&mut index.ip) {
+            let dir = root.join(&e.path);
+            if dir.join(IP_MANIFEST_FILE).is_file() == false {
+                changed = true;
+            } else if is_stale(&dir) || is_stale(&dir.join(IP_MANIFEST_FILE)) {
+                let ptr = IpPointer::read(dir)?;
+                entries.push(IndexEntry {
+                    path: e.path,
+                    yanked: ptr.yanked,
+                    manifest: ptr.manifest,
+                });
+                changed = true;
+            } else {
+                entries.push(e);
+            }
+        }
+        index.ip = entries;
+
+        // find the new pointers within the top-level directories that changed
+        let known: HashSet<String> = index.ip.iter().map(|e| e.path.clone()).collect();
+        for dir in top_level_dirs(root)? {
+            if is_stale(&dir) == false {
+                continue;
+            }
+            changed = true;
+            for mut entry in manifest::find_file(&dir, IP_MANIFEST_FILE, false)? {
+                // remove the manifest file to access the ip's root directory
+                entry.pop();
+                let path = filesystem::remove_base(root, &entry);
+                if known.contains(&filesystem::into_std_str(path.clone())) == false {
+                    index.insert(&path, IpPointer::read(entry)?);
+                }
+            }
+        }
+        if changed == true {
+            index.save(root)?;
+        }
+        Ok(index)
+    }
+
+    /// Writes the index to the channel's `root`.
+    pub fn save(&self, root: &PathBuf) -> Result<(), Fault> {
+        std::fs::write(
+            root.join(CHANNEL_INDEX_FILE),
+            format!("{}\n{}", INDEX_COMMENT, &self.to_string()),
+        )?;
+        Ok(())
+    }
+
+    /// Converts every entry into an available [Ip].
+    pub fn into_ips(self) -> Vec<Ip> {
+        self.ip
+            .into_iter()
+            .map(|e| {
+                Ip::from(IpPointer {
+                    manifest: e.manifest,
+                    yanked: e.yanked,
+                })
+            })
+            .collect()
+    }
+}
+
+/// Returns the time the file or directory at `path` was last modified.
+fn modified(path: &Path) -> Option<SystemTime> {
+    std::fs::metadata(path).and_then(|m| m.modified()).ok()
+}
+
+/// Lists the directories directly within the channel's `root`, skipping any
+/// hidden directories such as those used for version control.
+fn top_level_dirs(root: &PathBuf) -> Result<Vec<PathBuf>, Fault> {
+    let mut dirs = Vec::new();
+    for e in std::fs::read_dir(root)? {
+        let e = e?;
+        if e.file_type()?.is_dir() == true
+            && e.file_name().to_string_lossy().starts_with('.') == false
+        {
+            dirs.push(e.path());
+        }
+    }
+    Ok(dirs)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::core::testing::{manifest_text, write_pointer};
+    use std::time::Duration;
+
+    const GATES_ID: &str = "a19df3kurd0az1q0ycj7vkqlz";
+
+    fn man_1() -> String {
+        manifest_text("gates", GATES_ID, "1.0.0", &[("adder", "2.1")])
+    }
+
+    fn man_2() -> String {
+        manifest_text("gates", GATES_ID, "1.1.0", &[])
+    }
+
+    #[test]
+    fn ut_index_insert_remove() {
+        let mut index = ChannelIndex::new();
+        index.insert(
+            &PathBuf::from("a/gates-1.1.0-a19df3kurd"),
+            IpPointer::from(Manifest::from_str(&man_2()).unwrap()),
+        );
+        index.insert(
+            &PathBuf::from("a/gates-1.0.0-a19df3kurd"),
+            IpPointer::from(Manifest::from_str(&man_1()).unwrap()),
+        );
+        // entries are kept in order by path
+        assert_eq!(index.inner().len(), 2);
+        assert_eq!(index.inner()[0].get_path(), "a/gates-1.0.0-a19df3kurd");
+
+        // replaces the entry at the same path
+        index.insert(
+            &PathBuf::from("a/gates-1.0.0-a19df3kurd"),
+            IpPointer::from(Manifest::from_str(&man_1()).unwrap()),
+        );
+        assert_eq!(index.inner().len(), 2);
+
+        // round trip through the file format
+        let text = index.to_string();
+        assert_eq!(ChannelIndex::from_str(&text).unwrap(), index);
+
+        assert_eq!(
+            index.remove(&PathBuf::from("a/gates-1.1.0-a19df3kurd")),
+            true
+        );
+        assert_eq!(
+            index.remove(&PathBuf::from("a/gates-1.1.0-a19df3kurd")),
+            false
+        );
+        assert_eq!(index.inner().len(), 1);
+    }
+
+    /// Sets every path within `root` as modified a while ago, with the index
+    /// written after the rest of the channel.
+    fn settle(root: &Path) {
+        let now = SystemTime::now();
+        fn backdate(path: &Path, time: SystemTime) {
+            if path.is_dir() == true {
+                for e in std::fs::read_dir(path).unwrap() {
+                    backdate(&e.unwrap().path(), time);
+                }
+            }
+            std::fs::File::open(path)
+                .unwrap()
+                .set_modified(time)
+                .unwrap();
+        }
+        backdate(root, now - Duration::from_secs(120));
+        let index = root.join(CHANNEL_INDEX_FILE);
+        if index.exists() == true {
+            backdate(&index, now - Duration::from_secs(60));
+        }
+    }
+
+    #[test]
+    fn ut_index_sync() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path().to_path_buf();
+        let dir_1 = write_pointer(&root, &man_1());
+        let dir_2 = write_pointer(&root, &man_2());
+
+        // creates the index from walking the directories
+        assert_eq!(ChannelIndex::load(&root), None);
+        let index = ChannelIndex::sync(&root).unwrap();
+        assert_eq!(index.inner().len(), 2);
+        assert_eq!(ChannelIndex::load(&root), Some(index));
+        settle(&root);
+        assert_eq!(ChannelIndex::load_current(&root).is_some(), true);
+        assert_eq!(IpPointer::detect_all(&root).unwrap().len(), 2);
+
+        // drops the entries for pointers that no longer exist
+        std::fs::remove_dir_all(&dir_2).unwrap();
+        let index = ChannelIndex::sync(&root).unwrap();
+        assert_eq!(index.inner().len(), 1);
+        assert_eq!(
+            index.inner()[0].get_path(),
+            "a/a19df3kurd0az1q0ycj7vkqlz-1.0.0"
+        );
+        assert_eq!(
+            index.inner()[0].get_man(),
+            &Manifest::from_str(&man_1()).unwrap()
+        );
+        assert_eq!(index.inner()[0].is_yanked(), false);
+
+        // refreshes the yanked state of existing entries
+        settle(&root);
+        std::fs::write(dir_1.join(ORBIT_YANK_FILE), "").unwrap();
+        let index = ChannelIndex::sync(&root).unwrap();
+        assert_eq!(index.inner()[0].is_yanked(), true);
+        let ips = IpPointer::detect_all(&root).unwrap();
+        assert_eq!(ips[0].is_yanked(), true);
+    }
+
+    #[test]
+    fn ut_index_stale() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path().to_path_buf();
+        let dir_1 = write_pointer(&root, &man_1());
+        ChannelIndex::sync(&root).unwrap();
+        settle(&root);
+
+        // a pointer added without updating the index is still detected
+        write_pointer(
+            &root,
+            &manifest_text("adder", "b5xt109p1pz6moqez7h8x969v", "1.0.0", &[]),
+        );
+        assert_eq!(ChannelIndex::load_current(&root), None);
+        assert_eq!(IpPointer::detect_all(&root).unwrap().len(), 2);
+
+        // only the directories that changed are read again, so an unchanged
+        // pointer keeps its indexed manifest
+        std::fs::write(dir_1.join(IP_MANIFEST_FILE), "not a manifest").unwrap();
+        for path in [dir_1.join(IP_MANIFEST_FILE), dir_1.clone()] {
+            std::fs::File::open(&path)
+                .unwrap()
+                .set_modified(SystemTime::now() - Duration::from_secs(120))
+                .unwrap();
+        }
+        let index = ChannelIndex::sync(&root).unwrap();
+        assert_eq!(index.inner().len(), 2);
+        assert_eq!(
+            index.inner()[0].get_man(),
+            &Manifest::from_str(&man_1()).unwrap()
+        );
+        assert_eq!(ChannelIndex::load_current(&root), Some(index));
+    }
+}
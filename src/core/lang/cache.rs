@@ -0,0 +1,751 @@
+//
+//  Copyright (C) 2022-2025  Chase Ruskin
+//
+//  This program is free software: you can redistribute it and/or modify
+//  it under the terms of the GNU General Public License as published by
+//  the Free Software Foundation, either version 3 of the License, or
+//  (at your option) any later version.
+//
+//  This program is distributed in the hope that it will be useful,
+//  but WITHOUT ANY WARRANTY; without even the implied warranty of
+//  MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+//  GNU General Public License for more details.
+//
+//  You should have received a copy of the GNU General Public License
+//  along with this program.  If not, see <http://www.gnu.org/licenses/>.
+//
+
+//! A persistent cache of the design units found in each HDL file.
+//!
+//! Entries are keyed by the SHA-256 hash of a file's contents, so a file is only
+//! parsed again once its contents change. The size and modification time of each
+//! file are remembered alongside its hash, so an unchanged file is not even read.
+//!
+//! The cache holds the symbols needed to build the graph of design units, so the
+//! compile order is still computed on every plan but without parsing any files.
+
+use super::lexer::Position;
+use super::node::{FileSymbols, SubUnitSymbol, UnitSymbol};
+use super::reference::CompoundIdentifier;
+use super::verilog::preprocess::Define;
+use super::{sv, verilog, vhdl};
+use super::{Lang, LangIdentifier, LangUnit, SharedData, VerilogIdentifier};
+use super::{SystemVerilogPrimaryUnit, VerilogPrimaryUnit, VhdlPrimaryUnit};
+use crate::util::anyerror::Fault;
+use crate::util::sha256;
+use serde_derive::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+use std::path::PathBuf;
+use std::str::FromStr;
+use std::time::UNIX_EPOCH;
+
+pub const UNIT_CACHE_FILE: &str = ".orbit-units";
+
+/// A reference to another design unit.
+#[derive(Debug, PartialEq, Serialize, Deserialize, Clone)]
+pub struct CachedRef {
+    language: Lang,
+    identifier: String,
+}
+
+impl From<&LangIdentifier> for CachedRef {
+    fn from(value: &LangIdentifier) -> Self {
+        Self {
+            language: match value {
+                LangIdentifier::Vhdl(_) => Lang::Vhdl,
+                LangIdentifier::Verilog(_) => Lang::Verilog,
+                LangIdentifier::SystemVerilog(_) => Lang::SystemVerilog,
+            },
+            identifier: match value {
+                // keep the symbol that marks a system name so it can be read back
+                LangIdentifier::Verilog(VerilogIdentifier::System(s))
+                | LangIdentifier::SystemVerilog(VerilogIdentifier::System(s)) => {
+                    format!("${}", s)
+                }
+                _ => value.to_string(),
+            },
+        }
+    }
+}
+
+impl CachedRef {
+    fn to_identifier(&self) -> Option<LangIdentifier> {
+        match self.language {
+            Lang::Vhdl => Some(LangIdentifier::Vhdl(
+                vhdl::token::identifier::Identifier::from_str(&self.identifier).ok()?,
+            )),
+            Lang::Verilog => Some(LangIdentifier::Verilog(
+                verilog::token::identifier::Identifier::from_str(&self.identifier).ok()?,
+            )),
+            Lang::SystemVerilog => Some(LangIdentifier::SystemVerilog(
+                sv::token::identifier::Identifier::from_str(&self.identifier).ok()?,
+            )),
+        }
+    }
+}
+
+/// A reference to another design unit that may be qualified by its library.
+#[derive(Debug, PartialEq, Serialize, Deserialize, Clone)]
+struct CachedCompound {
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    library: Option<CachedRef>,
+    unit: CachedRef,
+}
+
+impl From<&CompoundIdentifier> for CachedCompound {
+    fn from(value: &CompoundIdentifier) -> Self {
+        Self {
+            library: value.get_prefix().map(CachedRef::from),
+            unit: CachedRef::from(value.get_suffix()),
+        }
+    }
+}
+
+impl CachedCompound {
+    fn to_compound(&self) -> Option<CompoundIdentifier> {
+        let unit = self.unit.to_identifier()?;
+        match &self.library {
+            Some(lib) => Some(CompoundIdentifier::new(lib.to_identifier()?, unit)),
+            None => Some(CompoundIdentifier::new_minimal(unit)),
+        }
+    }
+}
+
+/// The details of a primary design unit that are needed without its full symbol.
+#[derive(Debug, PartialEq, Serialize, Deserialize, Clone)]
+pub struct CachedUnit {
+    identifier: String,
+    #[serde(rename = "type")]
+    shape: String,
+    position: Position,
+    component: bool,
+    usable: bool,
+    references: Vec<CachedRef>,
+}
+
+impl From<&LangUnit> for CachedUnit {
+    fn from(value: &LangUnit) -> Self {
+        Self {
+            identifier: value.get_name().to_string(),
+            shape: value.to_string(),
+            position: value.get_position().clone(),
+            component: value.is_component(),
+            usable: value.is_usable_component(),
+            references: value.get_references().iter().map(CachedRef::from).collect(),
+        }
+    }
+}
+
+impl CachedUnit {
+    pub fn get_position(&self) -> &Position {
+        &self.position
+    }
+
+    pub fn is_component(&self) -> bool {
+        self.component
+    }
+
+    pub fn is_usable_component(&self) -> bool {
+        self.usable
+    }
+
+    pub fn get_references(&self) -> Vec<LangIdentifier> {
+        self.references
+            .iter()
+            .filter_map(|r| r.to_identifier())
+            .collect()
+    }
+
+    /// Creates the design unit written in `lang` that was found in the `source` file.
+    pub fn to_lang_unit(&self, lang: &Lang, source: &str) -> Option<LangUnit> {
+        let sd = SharedData::with_cached(self.clone());
+        match lang {
+            Lang::Vhdl => Some(LangUnit::Vhdl(
+                VhdlPrimaryUnit::from_parts(&self.identifier, &self.shape, source)?,
+                sd,
+            )),
+            Lang::Verilog => Some(LangUnit::Verilog(
+                VerilogPrimaryUnit::from_parts(&self.identifier, &self.shape, source)?,
+                sd,
+            )),
+            Lang::SystemVerilog => Some(LangUnit::SystemVerilog(
+                SystemVerilogPrimaryUnit::from_parts(&self.identifier, &self.shape, source)?,
+                sd,
+            )),
+        }
+    }
+}
+
+/// The details of a primary design unit that are needed to place it within the
+/// graph of design units.
+#[derive(Debug, PartialEq, Serialize, Deserialize, Clone)]
+struct CachedSymbol {
+    identifier: CachedRef,
+    component: bool,
+    testbench: bool,
+    references: Vec<CachedCompound>,
+    instances: Vec<CachedCompound>,
+}
+
+impl From<&UnitSymbol> for CachedSymbol {
+    fn from(value: &UnitSymbol) -> Self {
+        Self {
+            identifier: CachedRef::from(value.get_name()),
+            component: value.is_component(),
+            testbench: value.is_testbench(),
+            references: value.get_refs().iter().map(CachedCompound::from).collect(),
+            instances: value
+                .get_edge_list_entities()
+                .iter()
+                .map(CachedCompound::from)
+                .collect(),
+        }
+    }
+}
+
+impl CachedSymbol {
+    fn to_symbol(&self) -> Option<UnitSymbol> {
+        Some(UnitSymbol::new(
+            self.identifier.to_identifier()?,
+            self.component,
+            self.testbench,
+            to_compounds(&self.references)?.into_iter().collect(),
+            to_compounds(&self.instances)?,
+        ))
+    }
+}
+
+/// The details of a secondary design unit that are needed to connect it to its
+/// owner within the graph of design units.
+#[derive(Debug, PartialEq, Serialize, Deserialize, Clone)]
+struct CachedSubUnit {
+    owner: String,
+    edges: Vec<CachedCompound>,
+    instances: Vec<CachedCompound>,
+}
+
+impl From<&SubUnitSymbol> for CachedSubUnit {
+    fn from(value: &SubUnitSymbol) -> Self {
+        Self {
+            owner: value.get_entity().to_string(),
+            edges: value
+                .get_edge_list()
+                .iter()
+                .map(CachedCompound::from)
+                .collect(),
+            instances: value
+                .get_edge_list_entities()
+                .iter()
+                .map(CachedCompound::from)
+                .collect(),
+        }
+    }
+}
+
+impl CachedSubUnit {
+    fn to_symbol(&self) -> Option<SubUnitSymbol> {
+        Some(SubUnitSymbol::new(
+            vhdl::token::identifier::Identifier::from_str(&self.owner).ok()?,
+            to_compounds(&self.edges)?,
+            to_compounds(&self.instances)?,
+        ))
+    }
+}
+
+fn to_compounds(list: &Vec<CachedCompound>) -> Option<Vec<CompoundIdentifier>> {
+    list.iter().map(|c| c.to_compound()).collect()
+}
+
+/// The symbols found in a single file after it was preprocessed with a set of macros.
+#[derive(Debug, PartialEq, Serialize, Deserialize, Clone)]
+struct CachedSymbols {
+    units: Vec<CachedSymbol>,
+    subunits: Vec<CachedSubUnit>,
+    includes: Vec<String>,
+    /// The header files expanded while reading the file, with their checksums.
+    #[serde(default)]
+    headers: Vec<CachedHeader>,
+    /// The include directives whose header files could not be found.
+    #[serde(default)]
+    missing: Vec<CachedInclude>,
+}
+
+/// An include directive whose header file could not be found when a source file
+/// was read.
+#[derive(Debug, PartialEq, Serialize, Deserialize, Clone)]
+struct CachedInclude {
+    /// The header file that holds the directive (none for the source file).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    from: Option<String>,
+    path: String,
+}
+
+/// A header file that was expanded into a source file when it was read.
+#[derive(Debug, PartialEq, Serialize, Deserialize, Clone)]
+struct CachedHeader {
+    path: String,
+    checksum: String,
+}
+
+impl From<&FileSymbols> for CachedSymbols {
+    fn from(value: &FileSymbols) -> Self {
+        Self {
+            units: value.get_units().iter().map(CachedSymbol::from).collect(),
+            subunits: value
+                .get_subunits()
+                .iter()
+                .map(CachedSubUnit::from)
+                .collect(),
+            includes: value.get_includes().clone(),
+            headers: Vec::new(),
+            missing: Vec::new(),
+        }
+    }
+}
+
+impl CachedSymbols {
+    fn to_file_symbols(&self) -> Option<FileSymbols> {
+        Some(FileSymbols::new(
+            self.units
+                .iter()
+                .map(|u| u.to_symbol())
+                .collect::<Option<Vec<UnitSymbol>>>()?,
+            self.subunits
+                .iter()
+                .map(|u| u.to_symbol())
+                .collect::<Option<Vec<SubUnitSymbol>>>()?,
+            self.includes.clone(),
+            self.headers.iter().map(|h| h.path.clone()).collect(),
+        ))
+    }
+}
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+struct CachedFile {
+    language: Lang,
+    /// The primary design units, once they have been collected.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    units: Option<Vec<CachedUnit>>,
+    /// The symbols for the graph, keyed by the macros defined when the file was read.
+    #[serde(default)]
+    symbols: HashMap<String, CachedSymbols>,
+}
+
+impl CachedFile {
+    fn new(language: Lang) -> Self {
+        Self {
+            language: language,
+            units: None,
+            symbols: HashMap::new(),
+        }
+    }
+}
+
+/// The state of a file on disk when its contents were last hashed.
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+struct FileStamp {
+    modified: u64,
+    size: u64,
+    checksum: String,
+}
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+pub struct UnitCache {
+    /// The version of orbit that wrote the cache.
+    version: String,
+    /// The last known checksum for each file path.
+    stamps: HashMap<String, FileStamp>,
+    files: HashMap<String, CachedFile>,
+    /// The directory to save the cache to (no directory keeps it in memory).
+    #[serde(skip)]
+    root: Option<PathBuf>,
+    #[serde(skip)]
+    modified: bool,
+}
+
+impl UnitCache {
+    /// Creates an empty cache that is only kept in memory and never saved.
+    ///
+    /// Entries can still be inserted, but files are never hashed to look them up.
+    pub fn new() -> Self {
+        Self {
+            version: env!("CARGO_PKG_VERSION").to_string(),
+            stamps: HashMap::new(),
+            files: HashMap::new(),
+            root: None,
+            modified: false,
+        }
+    }
+
+    /// Loads the cache stored in the directory `root`.
+    ///
+    /// A missing, unreadable, or outdated cache file starts an empty cache.
+    pub fn open(root: &PathBuf) -> Self {
+        let stored = std::fs::read_to_string(root.join(UNIT_CACHE_FILE))
+            .ok()
+            .and_then(|contents| serde_json::from_str::<Self>(&contents).ok())
+            .filter(|cache| cache.version == env!("CARGO_PKG_VERSION"));
+        let mut cache = stored.unwrap_or(Self::new());
+        cache.root = Some(root.clone());
+        cache
+    }
+
+    /// Returns the key to look up the entries for the file at `path`.
+    ///
+    /// The file's contents are only hashed again if its size or modification time
+    /// changed. Returns `None` for a cache that is never saved, since there is
+    /// nothing to look up.
+    pub fn checksum(&mut self, path: &str) -> Result<Option<String>, std::io::Error> {
+        if self.root.is_none() == true {
+            return Ok(None);
+        }
+        let meta = std::fs::metadata(path)?;
+        let modified = meta
+            .modified()
+            .ok()
+            .and_then(|t| t.duration_since(UNIX_EPOCH).ok())
+            .map(|d| d.as_nanos() as u64);
+        if let Some(stamp) = self.stamps.get(path) {
+            if Some(stamp.modified) == modified && stamp.size == meta.len() {
+                return Ok(Some(stamp.checksum.clone()));
+            }
+        }
+        let checksum = sha256::compute_sha256(&std::fs::read(path)?).to_string();
+        if let Some(m) = modified {
+            self.stamps.insert(
+                path.to_string(),
+                FileStamp {
+                    modified: m,
+                    size: meta.len(),
+                    checksum: checksum.clone(),
+                },
+            );
+            self.modified = true;
+        }
+        Ok(Some(checksum))
+    }
+
+    /// Returns the primary design units stored for the file with the checksum `key`
+    /// written in `lang`.
+    pub fn get_units(&self, key: &str, lang: &Lang) -> Option<&Vec<CachedUnit>> {
+        self.files
+            .get(key)
+            .filter(|entry| &entry.language == lang)
+            .and_then(|entry| entry.units.as_ref())
+    }
+
+    /// Stores the primary design `units` for the file with the checksum `key` written in `lang`.
+    pub fn insert_units(&mut self, key: String, lang: Lang, units: Vec<CachedUnit>) {
+        self.entry(key, lang).units = Some(units);
+        self.modified = true;
+    }
+
+    /// Returns the symbols stored for the file with the checksum `key` written
+    /// in `lang` when it was read with the macros `defines`.
+    ///
+    /// The symbols are not returned if any header file expanded into the file
+    /// has changed since they were stored, or if `found` can now find the header
+    /// of an include directive that was missing. The arguments to `found` are the
+    /// header file that holds the directive (`None` for the file itself) and the
+    /// path written in the directive.
+    pub fn get_symbols(
+        &mut self,
+        key: &str,
+        lang: &Lang,
+        defines: &[Define],
+        found: &mut dyn FnMut(Option<&str>, &str) -> bool,
+    ) -> Option<FileSymbols> {
+        let symbols = self
+            .files
+            .get(key)
+            .filter(|entry| &entry.language == lang)
+            .and_then(|entry| entry.symbols.get(&Self::scope(lang, defines)))?
+            .clone();
+        for header in &symbols.headers {
+            if self.checksum(&header.path).ok().flatten().as_ref() != Some(&header.checksum) {
+                return None;
+            }
+        }
+        if symbols
+            .missing
+            .iter()
+            .any(|m| found(m.from.as_deref(), &m.path))
+        {
+            return None;
+        }
+        symbols.to_file_symbols()
+    }
+
+    /// Stores the `symbols` for the file with the checksum `key` written in `lang`
+    /// when it was read with the macros `defines`.
+    ///
+    /// The include directives in `missing` are written as `(header, path)` pairs,
+    /// where `header` is the file that holds the directive (`None` for the file
+    /// itself) and `path` could not be found.
+    pub fn insert_symbols(
+        &mut self,
+        key: String,
+        lang: Lang,
+        defines: &[Define],
+        symbols: &FileSymbols,
+        missing: &[(Option<String>, String)],
+    ) {
+        let mut cached = CachedSymbols::from(symbols);
+        cached.missing = missing
+            .iter()
+            .map(|(from, path)| CachedInclude {
+                from: from.clone(),
+                path: path.clone(),
+            })
+            .collect();
+        for path in symbols.get_headers() {
+            match self.checksum(path) {
+                Ok(Some(checksum)) => cached.headers.push(CachedHeader {
+                    path: path.clone(),
+                    checksum: checksum,
+                }),
+                // the symbols cannot be checked against the header later
+                _ => return,
+            }
+        }
+        let scope = Self::scope(&lang, defines);
+        self.entry(key, lang).symbols.insert(scope, cached);
+        self.modified = true;
+    }
+
+    /// Returns the entry for the checksum `key`, replacing any entry that was
+    /// stored for a different language.
+    fn entry(&mut self, key: String, lang: Lang) -> &mut CachedFile {
+        let entry = self
+            .files
+            .entry(key)
+            .or_insert_with(|| CachedFile::new(lang.clone()));
+        if entry.language != lang {
+            *entry = CachedFile::new(lang);
+        }
+        entry
+    }
+
+    /// Identifies the set of macros that affects reading a file written in `lang`.
+    fn scope(lang: &Lang, defines: &[Define]) -> String {
+        match lang {
+            Lang::Vhdl => String::new(),
+            _ => defines
+                .iter()
+                .map(|d| d.to_string())
+                .collect::<Vec<String>>()
+                .join(" "),
+        }
+    }
+
+    /// Writes the cache to its directory if any entries changed.
+    ///
+    /// Entries for files that no longer exist are dropped.
+    pub fn save(&mut self) -> Result<(), Fault> {
+        let root = match &self.root {
+            Some(r) => r.clone(),
+            None => return Ok(()),
+        };
+        let count = self.stamps.len() + self.files.len();
+        self.stamps.retain(|path, _| PathBuf::from(path).exists());
+        let checksums: HashSet<&String> = self.stamps.values().map(|s| &s.checksum).collect();
+        self.files.retain(|key, _| checksums.contains(key));
+        if self.modified == false && self.stamps.len() + self.files.len() == count {
+            return Ok(());
+        }
+        std::fs::create_dir_all(&root)?;
+        std::fs::write(root.join(UNIT_CACHE_FILE), serde_json::to_string(&self)?)?;
+        self.modified = false;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::util::filesystem;
+
+    const VHDL_CODE: &str = r#"
+entity adder is
+    port (a, b : in bit; s : out bit);
+end entity;
+
+architecture rtl of adder is
+begin
+    u0: entity work.half_adder;
+end architecture;
+"#;
+
+    #[test]
+    fn cached_unit_matches_parsed_unit() {
+        let units = vhdl::primaryunit::parse_units("adder.vhd", VHDL_CODE).unwrap();
+        let parsed = LangUnit::Vhdl(units.into_values().next().unwrap(), SharedData::new());
+
+        let cached = CachedUnit::from(&parsed)
+            .to_lang_unit(&Lang::Vhdl, "adder.vhd")
+            .unwrap();
+        assert_eq!(cached.get_name(), parsed.get_name());
+        assert_eq!(cached.to_string(), "entity");
+        assert_eq!(cached.get_source_file(), "adder.vhd");
+        assert_eq!(cached.get_position(), parsed.get_position());
+        assert_eq!(cached.is_component(), true);
+        assert_eq!(cached.is_usable_component(), true);
+        assert_eq!(cached.get_references(), parsed.get_references());
+        assert_eq!(
+            cached.get_references(),
+            vec![LangIdentifier::from_str("half_adder").unwrap()]
+        );
+    }
+
+    #[test]
+    fn unit_cache_round_trip() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path().to_path_buf();
+        let source = filesystem::into_std_str(root.join("adder.vhd"));
+        std::fs::write(&source, VHDL_CODE).unwrap();
+
+        let units = vhdl::primaryunit::parse_units(&source, VHDL_CODE).unwrap();
+        let parsed = LangUnit::Vhdl(units.into_values().next().unwrap(), SharedData::new());
+
+        let mut cache = UnitCache::open(&root);
+        let key = cache.checksum(&source).unwrap().unwrap();
+        assert_eq!(
+            key,
+            sha256::compute_sha256(VHDL_CODE.as_bytes()).to_string()
+        );
+        assert_eq!(cache.get_units(&key, &Lang::Vhdl), None);
+        cache.insert_units(key.clone(), Lang::Vhdl, vec![CachedUnit::from(&parsed)]);
+        cache.save().unwrap();
+
+        let mut cache = UnitCache::open(&root);
+        assert_eq!(cache.checksum(&source).unwrap(), Some(key.clone()));
+        assert_eq!(cache.get_units(&key, &Lang::Vhdl).unwrap().len(), 1);
+        // the same contents must be parsed again for a different language
+        assert_eq!(cache.get_units(&key, &Lang::Verilog), None);
+
+        // entries are dropped once their files no longer exist
+        std::fs::remove_file(&source).unwrap();
+        cache.save().unwrap();
+        let cache = UnitCache::open(&root);
+        assert_eq!(cache.get_units(&key, &Lang::Vhdl), None);
+    }
+
+    #[test]
+    fn memory_cache_stores_nothing() {
+        let dir = tempfile::tempdir().unwrap();
+        let source = filesystem::into_std_str(dir.path().join("adder.vhd"));
+        std::fs::write(&source, VHDL_CODE).unwrap();
+
+        let mut cache = UnitCache::new();
+        assert_eq!(cache.checksum(&source).unwrap(), None);
+        cache.save().unwrap();
+        assert_eq!(dir.path().join(UNIT_CACHE_FILE).exists(), false);
+    }
+
+    #[test]
+    fn cached_symbols_match_parsed_symbols() {
+        let symbols = FileSymbols::from_vhdl(
+            vhdl::symbols::VHDLParser::read(VHDL_CODE)
+                .unwrap()
+                .into_symbols(),
+        );
+        assert_eq!(symbols.get_units().len(), 1);
+        assert_eq!(symbols.get_subunits().len(), 1);
+        assert_eq!(
+            CachedSymbols::from(&symbols).to_file_symbols(),
+            Some(symbols)
+        );
+
+        let code = "`include \"defs.vh\"\nmodule top; adder u0 (.a(x)); initial $display(\"hi\"); endmodule\n";
+        let parsed =
+            verilog::symbols::VerilogParser::read(code, &[], &mut verilog::preprocess::no_includes)
+                .unwrap();
+        let includes = parsed.get_includes().clone();
+        let symbols = FileSymbols::from_verilog(parsed.into_symbols(), includes, Vec::new());
+        assert_eq!(symbols.get_includes(), &vec![String::from("defs.vh")]);
+        assert_eq!(
+            symbols.get_units()[0].get_edge_list_entities(),
+            &vec![CompoundIdentifier::new_minimal_verilog(
+                VerilogIdentifier::from_str("adder").unwrap()
+            )]
+        );
+        assert_eq!(
+            CachedSymbols::from(&symbols).to_file_symbols(),
+            Some(symbols)
+        );
+    }
+
+    #[test]
+    fn symbols_are_kept_per_set_of_macros() {
+        let symbols = FileSymbols::new(Vec::new(), Vec::new(), Vec::new(), Vec::new());
+        let defines = vec![Define::from_str("WIDTH=8").unwrap()];
+
+        let mut cache = UnitCache::new();
+        cache.insert_symbols(String::from("abc"), Lang::Verilog, &defines, &symbols, &[]);
+        assert_eq!(
+            cache.get_symbols("abc", &Lang::Verilog, &defines, &mut |_, _| false),
+            Some(symbols.clone())
+        );
+        assert_eq!(
+            cache.get_symbols("abc", &Lang::Verilog, &[], &mut |_, _| false),
+            None
+        );
+        // macros do not change how vhdl is read
+        cache.insert_symbols(String::from("def"), Lang::Vhdl, &[], &symbols, &[]);
+        assert_eq!(
+            cache.get_symbols("def", &Lang::Vhdl, &defines, &mut |_, _| false),
+            Some(symbols)
+        );
+    }
+
+    #[test]
+    fn symbols_are_dropped_when_headers_change() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path().to_path_buf();
+        let header = filesystem::into_std_str(root.join("defs.vh"));
+        std::fs::write(&header, "`define USE_FIFO\n").unwrap();
+
+        let symbols = FileSymbols::new(
+            Vec::new(),
+            Vec::new(),
+            vec![String::from("defs.vh")],
+            vec![header.clone()],
+        );
+        let mut cache = UnitCache::open(&root);
+        cache.insert_symbols(String::from("abc"), Lang::Verilog, &[], &symbols, &[]);
+        assert_eq!(
+            cache.get_symbols("abc", &Lang::Verilog, &[], &mut |_, _| false),
+            Some(symbols)
+        );
+
+        std::fs::write(&header, "`define USE_REG_FILE\n").unwrap();
+        assert_eq!(
+            cache.get_symbols("abc", &Lang::Verilog, &[], &mut |_, _| false),
+            None
+        );
+    }
+
+    #[test]
+    fn symbols_are_dropped_when_missing_headers_appear() {
+        let symbols = FileSymbols::new(
+            Vec::new(),
+            Vec::new(),
+            vec![String::from("defs.vh")],
+            Vec::new(),
+        );
+        let missing = vec![(None, String::from("defs.vh"))];
+
+        let mut cache = UnitCache::new();
+        cache.insert_symbols(String::from("abc"), Lang::Verilog, &[], &symbols, &missing);
+        assert_eq!(
+            cache.get_symbols("abc", &Lang::Verilog, &[], &mut |_, _| false),
+            Some(symbols)
+        );
+        // the header can now be found from the file that includes it
+        let mut found = |from: Option<&str>, path: &str| from.is_none() && path == "defs.vh";
+        assert_eq!(
+            cache.get_symbols("abc", &Lang::Verilog, &[], &mut found),
+            None
+        );
+    }
+}
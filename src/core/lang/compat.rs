@@ -0,0 +1,346 @@
+//
+//  Copyright (C) 2022-2025  Chase Ruskin
+//
+//  This program is free software: you can redistribute it and/or modify
+//  it under the terms of the GNU General Public License as published by
+//  the Free Software Foundation, either version 3 of the License, or
+//  (at your option) any later version.
+//
+//  This program is distributed in the hope that it will be useful,
+//  but WITHOUT ANY WARRANTY; without even the implied warranty of
+//  MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+//  GNU General Public License for more details.
+//
+//  You should have received a copy of the GNU General Public License
+//  along with this program.  If not, see <http://www.gnu.org/licenses/>.
+//
+
+//! Compares the public interfaces of two versions of an ip to detect changes
+//! that break existing instantiations.
+
+use super::sv::token::keyword::Keyword as SvKeyword;
+use super::verilog::symbols::module::Module;
+use super::vhdl::symbols::entity::Entity;
+use super::vhdl::token::keyword::Keyword as VhdlKeyword;
+use super::{LangIdentifier, LangUnit};
+use std::collections::HashMap;
+use std::fmt::Display;
+
+/// The direction of a port, independent of the language that declared it.
+#[derive(Debug, PartialEq, Clone, Copy)]
+enum PortMode {
+    In,
+    Out,
+    InOut,
+    Buffer,
+    Linkage,
+    Ref,
+}
+
+impl PortMode {
+    fn from_vhdl(keyword: &VhdlKeyword) -> Self {
+        match keyword {
+            VhdlKeyword::Out => Self::Out,
+            VhdlKeyword::Inout => Self::InOut,
+            VhdlKeyword::Buffer => Self::Buffer,
+            VhdlKeyword::Linkage => Self::Linkage,
+            _ => Self::In,
+        }
+    }
+
+    fn from_verilog(keyword: &SvKeyword) -> Self {
+        match keyword {
+            SvKeyword::Output => Self::Out,
+            SvKeyword::Inout => Self::InOut,
+            SvKeyword::Ref => Self::Ref,
+            _ => Self::In,
+        }
+    }
+}
+
+/// A generic/parameter or port declared in a unit's interface.
+#[derive(Debug, PartialEq)]
+struct Signal {
+    name: String,
+    mode: PortMode,
+    /// The mode as it is written in the source code.
+    keyword: String,
+    has_default: bool,
+}
+
+/// The generics/parameters and ports of a design unit.
+#[derive(Debug, PartialEq)]
+pub struct UnitInterface {
+    generics: Vec<Signal>,
+    ports: Vec<Signal>,
+}
+
+impl UnitInterface {
+    pub fn from_entity(entity: &Entity) -> Self {
+        let convert = |list: &super::vhdl::interface::InterfaceDeclarations| {
+            list.iter()
+                .map(|d| Signal {
+                    // vhdl identifiers are case-insensitive
+                    name: d.get_identifier().to_string().to_lowercase(),
+                    mode: PortMode::from_vhdl(d.get_mode().unwrap_or(&VhdlKeyword::In)),
+                    keyword: d.get_mode().unwrap_or(&VhdlKeyword::In).to_string(),
+                    has_default: d.get_default().is_some(),
+                })
+                .collect()
+        };
+        Self {
+            generics: convert(&entity.get_generics().0),
+            ports: convert(&entity.get_ports().0),
+        }
+    }
+
+    pub fn from_module(module: &Module) -> Self {
+        let convert = |list: &Vec<super::verilog::interface::Port>| {
+            list.iter()
+                .map(|p| Signal {
+                    name: p.get_name().to_string(),
+                    mode: PortMode::from_verilog(p.get_mode().unwrap_or(&SvKeyword::Input)),
+                    keyword: p.get_mode().unwrap_or(&SvKeyword::Input).to_string(),
+                    has_default: p.get_default().is_some(),
+                })
+                .collect()
+        };
+        Self {
+            generics: convert(module.get_parameters()),
+            ports: convert(module.get_ports()),
+        }
+    }
+
+    /// Collects the interface of the `unit` if it is an entity or module.
+    pub fn from_unit(unit: &LangUnit) -> Option<Self> {
+        match unit {
+            LangUnit::Vhdl(_, _) => Some(Self::from_entity(unit.get_vhdl_symbol()?.as_entity()?)),
+            LangUnit::Verilog(_, _) => {
+                Some(Self::from_module(unit.get_verilog_symbol()?.as_module()?))
+            }
+            LangUnit::SystemVerilog(_, _) => Some(Self::from_module(
+                unit.get_systemverilog_symbol()?.as_module()?,
+            )),
+        }
+    }
+}
+
+/// A change to a public design unit that can break code that uses it.
+#[derive(Debug, PartialEq)]
+pub enum BreakingChange {
+    UnitRemoved(String),
+    GenericRemoved(String, String),
+    GenericAdded(String, String),
+    PortRemoved(String, String),
+    PortAdded(String, String),
+    PortModeChanged(String, String, String, String),
+}
+
+impl Display for BreakingChange {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::UnitRemoved(u) => write!(f, "unit \"{}\" was removed", u),
+            Self::GenericRemoved(u, g) => {
+                write!(f, "unit \"{}\" removed generic \"{}\"", u, g)
+            }
+            Self::GenericAdded(u, g) => write!(
+                f,
+                "unit \"{}\" added generic \"{}\" without a default value",
+                u, g
+            ),
+            Self::PortRemoved(u, p) => write!(f, "unit \"{}\" removed port \"{}\"", u, p),
+            Self::PortAdded(u, p) => write!(
+                f,
+                "unit \"{}\" added input port \"{}\" without a default value",
+                u, p
+            ),
+            Self::PortModeChanged(u, p, old, new) => write!(
+                f,
+                "unit \"{}\" changed port \"{}\" from {} to {}",
+                u, p, old, new
+            ),
+        }
+    }
+}
+
+/// Collects the public units from `units`, storing the interface of each
+/// entity and module.
+pub fn collect_interfaces(
+    units: &HashMap<LangIdentifier, LangUnit>,
+) -> HashMap<LangIdentifier, Option<UnitInterface>> {
+    units
+        .iter()
+        .filter(|(_, u)| u.get_visibility().is_public() == true)
+        .map(|(k, u)| (k.clone(), UnitInterface::from_unit(u)))
+        .collect()
+}
+
+/// Compares the list of signals from an `old` interface to a `new` interface.
+fn compare_signals(
+    unit: &str,
+    old: &[Signal],
+    new: &[Signal],
+    is_port: bool,
+    changes: &mut Vec<BreakingChange>,
+) {
+    for o in old {
+        match new.iter().find(|n| n.name == o.name) {
+            Some(n) => {
+                if is_port == true && n.mode != o.mode {
+                    changes.push(BreakingChange::PortModeChanged(
+                        unit.to_string(),
+                        o.name.clone(),
+                        o.keyword.clone(),
+                        n.keyword.clone(),
+                    ));
+                }
+            }
+            None => changes.push(match is_port {
+                true => BreakingChange::PortRemoved(unit.to_string(), o.name.clone()),
+                false => BreakingChange::GenericRemoved(unit.to_string(), o.name.clone()),
+            }),
+        }
+    }
+    for n in new {
+        // only new inputs are required to be connected
+        let is_required = n.has_default == false && (is_port == false || n.mode == PortMode::In);
+        if is_required == true && old.iter().find(|o| o.name == n.name).is_none() {
+            changes.push(match is_port {
+                true => BreakingChange::PortAdded(unit.to_string(), n.name.clone()),
+                false => BreakingChange::GenericAdded(unit.to_string(), n.name.clone()),
+            });
+        }
+    }
+}
+
+/// Finds the changes between the public units of an `old` version and a `new`
+/// version that break existing code.
+///
+/// A removed port or generic is reported as removed even when it was renamed.
+pub fn find_breaking_changes(
+    old: &HashMap<LangIdentifier, Option<UnitInterface>>,
+    new: &HashMap<LangIdentifier, Option<UnitInterface>>,
+) -> Vec<BreakingChange> {
+    let mut names: Vec<&LangIdentifier> = old.keys().collect();
+    names.sort_by_key(|k| k.to_string());
+
+    let mut changes = Vec::new();
+    for name in names {
+        let unit = name.to_string();
+        match new.get(name) {
+            Some(n) => {
+                if let (Some(o), Some(n)) = (old.get(name).unwrap(), n) {
+                    compare_signals(&unit, &o.generics, &n.generics, false, &mut changes);
+                    compare_signals(&unit, &o.ports, &n.ports, true, &mut changes);
+                }
+            }
+            None => changes.push(BreakingChange::UnitRemoved(unit)),
+        }
+    }
+    changes
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::core::lang::vhdl::symbols::{VHDLParser, VhdlSymbol};
+
+    fn interfaces(code: &str) -> HashMap<LangIdentifier, Option<UnitInterface>> {
+        let syms = VHDLParser::read(code).unwrap().into_symbols();
+        syms.iter()
+            .filter_map(|s| match s {
+                VhdlSymbol::Entity(e) => Some((
+                    LangIdentifier::Vhdl(e.get_name().clone()),
+                    Some(UnitInterface::from_entity(e)),
+                )),
+                _ => None,
+            })
+            .collect()
+    }
+
+    const OLD: &str = r#"
+entity adder is
+    generic(WIDTH: positive := 8);
+    port(a, b: in bit_vector(WIDTH-1 downto 0); cin: in bit; sum: out bit_vector(WIDTH-1 downto 0));
+end entity;
+
+entity mux is
+    port(sel: in bit; y: out bit);
+end entity;
+"#;
+
+    #[test]
+    fn ut_no_breaking_changes() {
+        let new = r#"
+entity ADDER is
+    generic(WIDTH: positive := 8; SIGNED_MATH: boolean := false);
+    port(A, b: in bit_vector(WIDTH-1 downto 0); cin: in bit := '0'; sum: out bit_vector(WIDTH-1 downto 0); cout: out bit);
+end entity;
+
+entity mux is
+    port(sel: in bit; y: out bit);
+end entity;
+"#;
+        assert_eq!(
+            find_breaking_changes(&interfaces(OLD), &interfaces(new)),
+            Vec::new()
+        );
+    }
+
+    #[test]
+    fn ut_breaking_changes() {
+        let new = r#"
+entity adder is
+    generic(WIDTH: positive; DEPTH: positive);
+    port(a, b: in bit_vector(WIDTH-1 downto 0); carry_in: in bit; sum: inout bit_vector(WIDTH-1 downto 0));
+end entity;
+"#;
+        let changes: Vec<String> = find_breaking_changes(&interfaces(OLD), &interfaces(new))
+            .iter()
+            .map(|c| c.to_string())
+            .collect();
+        assert_eq!(
+            changes,
+            vec![
+                "unit \"adder\" added generic \"depth\" without a default value",
+                "unit \"adder\" removed port \"cin\"",
+                "unit \"adder\" changed port \"sum\" from out to inout",
+                "unit \"adder\" added input port \"carry_in\" without a default value",
+                "unit \"mux\" was removed",
+            ]
+        );
+    }
+
+    #[test]
+    fn ut_compare_modes_across_languages() {
+        let module = r#"
+module adder #(parameter width = 8) (
+    input [width-1:0] a, b,
+    input cin,
+    output [width-1:0] sum,
+    output cout
+);
+endmodule
+"#;
+        let symbols = crate::core::lang::verilog::symbols::VerilogParser::read(
+            module,
+            &[],
+            &mut crate::core::lang::verilog::preprocess::no_includes,
+        )
+        .unwrap()
+        .into_symbols();
+        let old = interfaces(OLD);
+        let mut new = HashMap::new();
+        for name in old.keys() {
+            new.insert(
+                name.clone(),
+                match name.to_string() == "adder" {
+                    true => Some(UnitInterface::from_module(symbols[0].as_module().unwrap())),
+                    false => interfaces(OLD).remove(name).unwrap(),
+                },
+            );
+        }
+        // "in" and "input" are the same mode
+        assert_eq!(find_breaking_changes(&old, &new), Vec::new());
+    }
+}
@@ -26,11 +26,20 @@ pub mod node;
 
 pub mod reference;
 
+pub mod cache;
+
+pub mod translate;
+
+pub mod testbench;
+
+pub mod compat;
+
 use crate::error::Error;
 use crate::error::Hint;
 use crate::util::anyerror::AnyError;
 use crate::util::anyerror::CodeFault;
 use crate::util::filesystem;
+use cache::{CachedUnit, UnitCache};
 use lexer::Position;
 use serde_derive::Deserialize;
 use serde_derive::Serialize;
@@ -155,7 +164,7 @@ impl FromStr for Lang {
         match s {
             "vhdl" => Ok(Self::Vhdl),
             "verilog" => Ok(Self::Verilog),
-            "systemverilog" => Ok(Self::SystemVerilog),
+            "systemverilog" | "sv" => Ok(Self::SystemVerilog),
             _ => Err(AnyError(format!("unsupported language {:?}", s))),
         }
     }
@@ -183,15 +192,29 @@ pub trait Code {
 #[derive(Debug, PartialEq)]
 pub struct SharedData {
     visibility: Visibility,
+    /// Details about the unit when it was loaded from the unit cache rather than parsed.
+    cached: Option<CachedUnit>,
 }
 
 impl SharedData {
     pub fn new() -> Self {
         Self {
             visibility: Visibility::default(),
+            cached: None,
+        }
+    }
+
+    pub fn with_cached(cached: CachedUnit) -> Self {
+        Self {
+            visibility: Visibility::default(),
+            cached: Some(cached),
         }
     }
 
+    pub fn get_cached(&self) -> Option<&CachedUnit> {
+        self.cached.as_ref()
+    }
+
     pub fn set_visibility(&mut self, v: Visibility) {
         self.visibility = v;
     }
@@ -210,6 +233,9 @@ pub enum LangUnit {
 
 impl LangUnit {
     pub fn is_usable_component(&self) -> bool {
+        if let Some(cached) = self.get_cached() {
+            return cached.is_usable_component();
+        }
         match &self {
             Self::Verilog(m, _) => m.get_unit().is_usable_component().is_some(),
             Self::Vhdl(m, _) => m.get_unit().is_usable_component().is_some(),
@@ -218,6 +244,9 @@ impl LangUnit {
     }
 
     pub fn is_component(&self) -> bool {
+        if let Some(cached) = self.get_cached() {
+            return cached.is_component();
+        }
         match &self {
             Self::Verilog(m, _) => m.get_unit().get_symbol().unwrap().as_module().is_some(),
             Self::SystemVerilog(m, _) => m.get_unit().get_symbol().unwrap().as_module().is_some(),
@@ -225,6 +254,15 @@ impl LangUnit {
         }
     }
 
+    /// Returns the details of the unit if it was loaded from the unit cache.
+    pub fn get_cached(&self) -> Option<&CachedUnit> {
+        match &self {
+            Self::Vhdl(_, sd) => sd.get_cached(),
+            Self::Verilog(_, sd) => sd.get_cached(),
+            Self::SystemVerilog(_, sd) => sd.get_cached(),
+        }
+    }
+
     /// Checks if the module is public.
     pub fn is_listed_public(&self, plist: &VipList) -> bool {
         plist.is_included(self.get_source_file())
@@ -265,6 +303,9 @@ impl LangUnit {
 
     /// Returns the position where the design element was found in its source file.
     pub fn get_position(&self) -> &Position {
+        if let Some(cached) = self.get_cached() {
+            return cached.get_position();
+        }
         match &self {
             Self::Vhdl(u, _) => u.get_unit().get_symbol().unwrap().get_position(),
             Self::Verilog(u, _) => u.get_unit().get_symbol().unwrap().get_position(),
@@ -311,6 +352,9 @@ impl LangUnit {
     }
 
     pub fn get_references(&self) -> Vec<LangIdentifier> {
+        if let Some(cached) = self.get_cached() {
+            return cached.get_references();
+        }
         match &self {
             Self::Vhdl(u, _) => match u.get_unit().get_symbol() {
                 Some(sym) => sym
@@ -374,6 +418,10 @@ impl LangUnit {
                 VerilogPrimaryUnit::from_toml(tbl)?,
                 SharedData::new(),
             )),
+            "systemverilog" => Some(Self::SystemVerilog(
+                SystemVerilogPrimaryUnit::from_toml(tbl)?,
+                SharedData::new(),
+            )),
             _ => panic!("unknown entry in serialized toml table {}", entry),
         }
     }
@@ -487,84 +535,102 @@ impl Display for LangIdentifier {
     }
 }
 
-pub fn collect_units(files: &Vec<String>) -> Result<HashMap<LangIdentifier, LangUnit>, CodeFault> {
-    // collect the VHDL units
-    let vhdl_units = vhdl::primaryunit::collect_units(&files)?;
-
-    // collect the Verilog units
-    let verilog_units = verilog::primaryunit::collect_units(&files)?;
-
-    // collect the SystemVerilog units
-    let systemverilog_units = sv::primaryunit::collect_units(&files)?;
+/// Gathers the primary design units from the `files`, reusing the units stored in
+/// the `cache` for any file whose contents have not changed since it was last parsed.
+pub fn collect_units(
+    files: &Vec<String>,
+    cache: &mut UnitCache,
+) -> Result<HashMap<LangIdentifier, LangUnit>, CodeFault> {
+    let mut results: HashMap<LangIdentifier, LangUnit> = HashMap::new();
+    for source_file in files {
+        let lang = if crate::core::fileset::is_vhdl(&source_file) == true {
+            Lang::Vhdl
+        } else if crate::core::fileset::is_verilog(&source_file) == true {
+            Lang::Verilog
+        } else if crate::core::fileset::is_systemverilog(&source_file) == true {
+            Lang::SystemVerilog
+        } else {
+            continue;
+        };
+        let key = cache
+            .checksum(source_file)
+            .map_err(|e| CodeFault(Some(source_file.to_string()), Box::new(e)))?;
+
+        let cached = key.as_ref().and_then(|k| cache.get_units(k, &lang));
+        let units: Vec<LangUnit> = match cached {
+            Some(entries) => entries
+                .iter()
+                .filter_map(|e| e.to_lang_unit(&lang, &source_file))
+                .collect(),
+            None => {
+                let contents = read_to_string(source_file)?;
+                let units: Vec<LangUnit> = match lang {
+                    Lang::Vhdl => vhdl::primaryunit::parse_units(&source_file, &contents)?
+                        .into_values()
+                        .map(|u| LangUnit::Vhdl(u, SharedData::new()))
+                        .collect(),
+                    Lang::Verilog => verilog::primaryunit::parse_units(&source_file, &contents)?
+                        .into_values()
+                        .map(|u| LangUnit::Verilog(u, SharedData::new()))
+                        .collect(),
+                    Lang::SystemVerilog => sv::primaryunit::parse_units(&source_file, &contents)?
+                        .into_values()
+                        .map(|u| LangUnit::SystemVerilog(u, SharedData::new()))
+                        .collect(),
+                };
+                // remember the units for the next time
+                if let Some(k) = key {
+                    cache.insert_units(
+                        k,
+                        lang,
+                        units.iter().map(CachedUnit::from).collect(),
+                    );
+                }
+                units
+            }
+        };
 
-    // merge the two results into a common struct
-    let mut results =
-        HashMap::with_capacity(vhdl_units.len() + verilog_units.len() + systemverilog_units.len());
-    for (k, v) in vhdl_units {
-        results.insert(
-            LangIdentifier::Vhdl(k),
-            LangUnit::Vhdl(v, SharedData::new()),
-        );
+        // push to the global list (ensure there are zero duplicate names)
+        for unit in units {
+            if let Some(existing_unit) = results.get(&unit.get_name()) {
+                return Err(duplicate_unit_error(existing_unit, &unit));
+            }
+            results.insert(unit.get_name(), unit);
+        }
     }
+    Ok(results)
+}
 
-    // add verilog units and check for duplicates across languages
-    for (k, v) in verilog_units {
-        let existing = results.insert(
-            LangIdentifier::Verilog(k),
-            LangUnit::Verilog(v, SharedData::new()),
-        );
-        if let Some(existing_unit) = existing {
-            let old_unit = results.get(&existing_unit.get_name()).unwrap();
-            // return duplicate id error
-            let current_dir = std::env::current_dir().unwrap();
-            let location_1 = filesystem::remove_base(
-                &current_dir,
-                &PathBuf::from(existing_unit.get_source_file()),
-            );
-            let location_2 =
-                filesystem::remove_base(&current_dir, &PathBuf::from(old_unit.get_source_file()));
-            return Err(CodeFault(
-                None,
-                Box::new(Error::DuplicateIdentifiersCrossLang(
-                    existing_unit.get_name().to_string(),
-                    filesystem::into_std_str(location_1),
-                    existing_unit.get_position().clone(),
-                    filesystem::into_std_str(location_2),
-                    old_unit.get_position().clone(),
-                    Hint::ResolveDuplicateIds1,
-                )),
-            ))?;
-        }
-    }
-
-    // add sv units and check for duplicates across languages
-    for (k, v) in systemverilog_units {
-        let existing = results.insert(
-            LangIdentifier::SystemVerilog(k),
-            LangUnit::SystemVerilog(v, SharedData::new()),
+/// Creates the error for two design units that share the same name.
+fn duplicate_unit_error(existing_unit: &LangUnit, new_unit: &LangUnit) -> CodeFault {
+    if existing_unit.get_lang() == new_unit.get_lang() {
+        return CodeFault(
+            None,
+            Box::new(vhdl::primaryunit::HdlNamingError::DuplicateIdentifier(
+                existing_unit.get_name().to_string(),
+                PathBuf::from(existing_unit.get_source_file()),
+                existing_unit.get_position().clone(),
+                PathBuf::from(new_unit.get_source_file()),
+                new_unit.get_position().clone(),
+            )),
         );
-        if let Some(existing_unit) = existing {
-            let old_unit = results.get(&existing_unit.get_name()).unwrap();
-            // return duplicate id error
-            let current_dir = std::env::current_dir().unwrap();
-            let location_1 = filesystem::remove_base(
-                &current_dir,
-                &PathBuf::from(existing_unit.get_source_file()),
-            );
-            let location_2 =
-                filesystem::remove_base(&current_dir, &PathBuf::from(old_unit.get_source_file()));
-            return Err(CodeFault(
-                None,
-                Box::new(Error::DuplicateIdentifiersCrossLang(
-                    existing_unit.get_name().to_string(),
-                    filesystem::into_std_str(location_1),
-                    existing_unit.get_position().clone(),
-                    filesystem::into_std_str(location_2),
-                    old_unit.get_position().clone(),
-                    Hint::ResolveDuplicateIds1,
-                )),
-            ))?;
-        }
     }
-    Ok(results)
+    let current_dir = std::env::current_dir().unwrap();
+    let location_1 =
+        filesystem::remove_base(&current_dir, &PathBuf::from(new_unit.get_source_file()));
+    let location_2 = filesystem::remove_base(
+        &current_dir,
+        &PathBuf::from(existing_unit.get_source_file()),
+    );
+    CodeFault(
+        None,
+        Box::new(Error::DuplicateIdentifiersCrossLang(
+            new_unit.get_name().to_string(),
+            filesystem::into_std_str(location_1),
+            new_unit.get_position().clone(),
+            filesystem::into_std_str(location_2),
+            existing_unit.get_position().clone(),
+            Hint::ResolveDuplicateIds1,
+        )),
+    )
 }
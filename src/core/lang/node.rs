@@ -21,81 +21,309 @@ use crate::core::lang::vhdl::symbols::VhdlSymbol;
 use crate::util::anyerror::AnyError;
 use colored::Colorize;
 
-use super::reference::RefSet;
+use super::reference::{CompoundIdentifier, RefSet};
 use super::sv::symbols::SystemVerilogSymbol;
-use super::verilog::symbols::module::Module;
 use super::verilog::symbols::VerilogSymbol;
-use super::{Lang, LangIdentifier, VhdlIdentifier};
+use super::{Lang, LangIdentifier, VerilogIdentifier, VhdlIdentifier};
+
+/// The details of a primary design unit that are needed to place it within the
+/// graph of design units.
+#[derive(Debug, PartialEq, Clone)]
+pub struct UnitSymbol {
+    name: LangIdentifier,
+    component: bool,
+    testbench: bool,
+    refs: RefSet,
+    /// The units instantiated within this unit's body, in sorted order and listed
+    /// once per instance.
+    entities: Vec<CompoundIdentifier>,
+}
+
+impl UnitSymbol {
+    pub fn new(
+        name: LangIdentifier,
+        component: bool,
+        testbench: bool,
+        refs: RefSet,
+        entities: Vec<CompoundIdentifier>,
+    ) -> Self {
+        Self {
+            name: name,
+            component: component,
+            testbench: testbench,
+            refs: refs,
+            entities: entities,
+        }
+    }
+
+    pub fn get_name(&self) -> &LangIdentifier {
+        &self.name
+    }
+
+    pub fn is_component(&self) -> bool {
+        self.component
+    }
+
+    pub fn is_testbench(&self) -> bool {
+        self.testbench
+    }
+
+    pub fn get_refs(&self) -> &RefSet {
+        &self.refs
+    }
+
+    /// Returns the list of compound identifiers that were parsed from instantiations.
+    pub fn get_edge_list_entities(&self) -> &Vec<CompoundIdentifier> {
+        &self.entities
+    }
+
+    fn from_vhdl(sym: &VhdlSymbol) -> Self {
+        Self::new(
+            LangIdentifier::Vhdl(sym.get_name().unwrap().clone()),
+            sym.as_entity().is_some(),
+            sym.as_entity().is_some_and(|e| e.is_testbench()),
+            sym.get_refs().clone(),
+            Vec::new(),
+        )
+    }
+
+    fn from_verilog(sym: &VerilogSymbol) -> Self {
+        Self::new(
+            LangIdentifier::Verilog(sym.as_name().unwrap().clone()),
+            sym.as_module().is_some(),
+            sym.as_module().is_some_and(|m| m.is_testbench()),
+            sym.get_refs().clone(),
+            sym.as_module()
+                .map(|m| m.get_edge_list_entities())
+                .unwrap_or_default(),
+        )
+    }
+
+    fn from_systemverilog(sym: &SystemVerilogSymbol) -> Self {
+        let name = sym.as_name().unwrap().clone();
+        Self::new(
+            // only modules are identified as systemverilog units in the graph
+            match sym {
+                SystemVerilogSymbol::Module(_) => LangIdentifier::SystemVerilog(name),
+                _ => LangIdentifier::Verilog(name),
+            },
+            sym.as_module().is_some(),
+            sym.as_module().is_some_and(|m| m.is_testbench()),
+            sym.get_refs().clone(),
+            sym.as_module()
+                .map(|m| m.get_edge_list_entities())
+                .unwrap_or_default(),
+        )
+    }
+}
+
+/// The details of a secondary design unit that are needed to connect it to its
+/// owner within the graph of design units.
+#[derive(Debug, PartialEq, Clone)]
+pub struct SubUnitSymbol {
+    owner: VhdlIdentifier,
+    /// The references and instantiations within this unit, in sorted order.
+    edges: Vec<CompoundIdentifier>,
+    /// The units instantiated within this unit's body, in sorted order and listed
+    /// once per instance.
+    entities: Vec<CompoundIdentifier>,
+}
+
+impl SubUnitSymbol {
+    pub fn new(
+        owner: VhdlIdentifier,
+        edges: Vec<CompoundIdentifier>,
+        entities: Vec<CompoundIdentifier>,
+    ) -> Self {
+        Self {
+            owner: owner,
+            edges: edges,
+            entities: entities,
+        }
+    }
+
+    pub fn get_entity(&self) -> &VhdlIdentifier {
+        &self.owner
+    }
+
+    /// Returns an ordered list of compound indentifiers for consist graph building.
+    pub fn get_edge_list(&self) -> &Vec<CompoundIdentifier> {
+        &self.edges
+    }
+
+    /// Returns the list of compound identifiers that were parsed from entity instantiations.
+    pub fn get_edge_list_entities(&self) -> &Vec<CompoundIdentifier> {
+        &self.entities
+    }
+
+    fn from_sub(sub: SubUnit) -> Self {
+        Self::new(
+            sub.get_entity().clone(),
+            sub.get_edge_list().into_iter().cloned().collect(),
+            sub.get_edge_list_entities().into_iter().cloned().collect(),
+        )
+    }
+}
+
+/// The symbols found in a single HDL source file, reduced to what is needed to
+/// build the graph of design units.
+#[derive(Debug, PartialEq, Clone)]
+pub struct FileSymbols {
+    units: Vec<UnitSymbol>,
+    subunits: Vec<SubUnitSymbol>,
+    /// The paths written in the file's include directives.
+    includes: Vec<String>,
+    /// The paths of the header files expanded while reading the file.
+    headers: Vec<String>,
+}
+
+impl FileSymbols {
+    pub fn new(
+        units: Vec<UnitSymbol>,
+        subunits: Vec<SubUnitSymbol>,
+        includes: Vec<String>,
+        headers: Vec<String>,
+    ) -> Self {
+        Self {
+            units: units,
+            subunits: subunits,
+            includes: includes,
+            headers: headers,
+        }
+    }
+
+    pub fn from_vhdl(symbols: Vec<VhdlSymbol>) -> Self {
+        let mut units = Vec::new();
+        let mut subunits = Vec::new();
+        for sym in symbols {
+            match sym {
+                VhdlSymbol::Entity(_) | VhdlSymbol::Package(_) | VhdlSymbol::Context(_) => {
+                    units.push(UnitSymbol::from_vhdl(&sym))
+                }
+                VhdlSymbol::Architecture(arch) => {
+                    subunits.push(SubUnitSymbol::from_sub(SubUnit::from_arch(arch)))
+                }
+                VhdlSymbol::Configuration(cfg) => {
+                    subunits.push(SubUnitSymbol::from_sub(SubUnit::from_config(cfg)))
+                }
+                // package bodies are usually in same design file as package
+                VhdlSymbol::PackageBody(pb) => {
+                    subunits.push(SubUnitSymbol::from_sub(SubUnit::from_body(pb)))
+                }
+            }
+        }
+        Self::new(units, subunits, Vec::new(), Vec::new())
+    }
+
+    pub fn from_verilog(
+        symbols: Vec<VerilogSymbol>,
+        includes: Vec<String>,
+        headers: Vec<String>,
+    ) -> Self {
+        Self::new(
+            symbols.iter().map(UnitSymbol::from_verilog).collect(),
+            Vec::new(),
+            includes,
+            headers,
+        )
+    }
+
+    pub fn from_systemverilog(
+        symbols: Vec<SystemVerilogSymbol>,
+        includes: Vec<String>,
+        headers: Vec<String>,
+    ) -> Self {
+        Self::new(
+            symbols.iter().map(UnitSymbol::from_systemverilog).collect(),
+            Vec::new(),
+            includes,
+            headers,
+        )
+    }
+
+    pub fn get_units(&self) -> &Vec<UnitSymbol> {
+        &self.units
+    }
+
+    pub fn get_subunits(&self) -> &Vec<SubUnitSymbol> {
+        &self.subunits
+    }
+
+    pub fn get_includes(&self) -> &Vec<String> {
+        &self.includes
+    }
+
+    pub fn get_headers(&self) -> &Vec<String> {
+        &self.headers
+    }
+
+    pub fn into_parts(self) -> (Vec<UnitSymbol>, Vec<SubUnitSymbol>, Vec<String>) {
+        (self.units, self.subunits, self.includes)
+    }
+}
 
 #[derive(Debug, PartialEq)]
 pub enum HdlSymbol {
-    Verilog(VerilogSymbol),
-    Vhdl(VhdlSymbol),
-    SystemVerilog(SystemVerilogSymbol),
+    Verilog(UnitSymbol),
+    Vhdl(UnitSymbol),
+    SystemVerilog(UnitSymbol),
     BlackBox(String),
+    /// A Verilog or SystemVerilog file included by other source files, named by
+    /// its path within its ip, along with the directories to search to find it.
+    Header(String, Vec<String>),
 }
 
 impl HdlSymbol {
     pub fn get_name(&self) -> LangIdentifier {
         match &self {
-            Self::Verilog(v) => LangIdentifier::Verilog(v.as_name().unwrap().clone()),
-            Self::SystemVerilog(v) => LangIdentifier::SystemVerilog(v.as_name().unwrap().clone()),
-            Self::Vhdl(v) => LangIdentifier::Vhdl(v.get_name().unwrap().clone()),
+            Self::Verilog(u) | Self::SystemVerilog(u) | Self::Vhdl(u) => u.get_name().clone(),
             Self::BlackBox(s) => LangIdentifier::Vhdl(VhdlIdentifier::Basic(s.to_string())),
+            Self::Header(s, _) => LangIdentifier::Verilog(VerilogIdentifier::Basic(s.to_string())),
         }
     }
 
     /// Checks if this symbol is a component/module/entity.
     pub fn is_component(&self) -> bool {
         match &self {
-            Self::Verilog(v) => v.as_module().is_some(),
-            Self::Vhdl(v) => v.as_entity().is_some(),
-            Self::SystemVerilog(v) => v.as_module().is_some(),
+            Self::Verilog(u) | Self::SystemVerilog(u) | Self::Vhdl(u) => u.is_component(),
             Self::BlackBox(_) => true,
+            Self::Header(_, _) => false,
         }
     }
 
     pub fn get_refs(&self) -> Option<&RefSet> {
         match &self {
-            Self::Verilog(v) => Some(v.get_refs()),
-            Self::Vhdl(v) => Some(v.get_refs()),
-            Self::SystemVerilog(v) => Some(v.get_refs()),
+            Self::Verilog(u) | Self::SystemVerilog(u) | Self::Vhdl(u) => Some(u.get_refs()),
             Self::BlackBox(_) => None,
+            Self::Header(_, _) => None,
+        }
+    }
+
+    /// Returns the include directories if this symbol is a header file.
+    pub fn get_include_dirs(&self) -> Option<&Vec<String>> {
+        match &self {
+            Self::Header(_, dirs) => Some(dirs),
+            _ => None,
         }
     }
 
-    pub fn as_module(&self) -> Option<&Module> {
+    /// Returns the units instantiated by this symbol if it is a Verilog or
+    /// SystemVerilog module.
+    pub fn get_edge_list_entities(&self) -> Option<&Vec<CompoundIdentifier>> {
         match &self {
-            Self::Verilog(v) => v.as_module(),
-            Self::SystemVerilog(v) => v.as_module(),
+            Self::Verilog(u) | Self::SystemVerilog(u) => match u.is_component() {
+                true => Some(u.get_edge_list_entities()),
+                false => None,
+            },
             _ => None,
         }
     }
 
     pub fn is_testbench(&self) -> bool {
         match &self {
-            Self::Verilog(v) => {
-                if let Some(m) = v.as_module() {
-                    m.is_testbench()
-                } else {
-                    false
-                }
-            }
-            Self::SystemVerilog(v) => {
-                if let Some(m) = v.as_module() {
-                    m.is_testbench()
-                } else {
-                    false
-                }
-            }
-            Self::Vhdl(v) => {
-                if let Some(e) = v.as_entity() {
-                    e.is_testbench()
-                } else {
-                    false
-                }
-            }
+            Self::Verilog(u) | Self::SystemVerilog(u) | Self::Vhdl(u) => u.is_testbench(),
             Self::BlackBox(_) => false,
+            Self::Header(_, _) => false,
         }
     }
 }
@@ -122,6 +350,7 @@ impl<'a> HdlNode<'a> {
             HdlSymbol::Vhdl(_) => Lang::Vhdl,
             HdlSymbol::SystemVerilog(_) => Lang::SystemVerilog,
             HdlSymbol::BlackBox(_) => Lang::Vhdl,
+            HdlSymbol::Header(_, _) => self.files.first().unwrap().get_language().clone(),
         }
     }
 
@@ -177,20 +406,20 @@ impl<'a> HdlNode<'a> {
 
 #[derive(Debug, PartialEq)]
 pub struct SubUnitNode<'a> {
-    sub: SubUnit,
+    sub: SubUnitSymbol,
     file: &'a IpFileNode<'a>,
 }
 
 impl<'a> SubUnitNode<'a> {
-    pub fn new(unit: SubUnit, file: &'a IpFileNode<'a>) -> Self {
+    pub fn new(unit: SubUnitSymbol, file: &'a IpFileNode<'a>) -> Self {
         Self {
             sub: unit,
             file: file,
         }
     }
 
-    /// References the architecture struct.
-    pub fn get_sub(&self) -> &SubUnit {
+    /// References the secondary design unit.
+    pub fn get_sub(&self) -> &SubUnitSymbol {
         &self.sub
     }
 
@@ -65,6 +65,13 @@ impl CompoundIdentifier {
         }
     }
 
+    pub fn new_minimal(suffix: LangIdentifier) -> Self {
+        Self {
+            prefix: None,
+            suffix: suffix,
+        }
+    }
+
     pub fn new_minimal_vhdl(suffix: VhdlIdentifier) -> Self {
         Self {
             prefix: None,
@@ -205,12 +205,14 @@ impl Class {
         })
     }
 
-    fn parse_class_body<I>(tokens: &mut Peekable<I>) -> Result<(RefSet, RefSet), VerilogError>
+    fn parse_class_body<I>(
+        tokens: &mut Peekable<I>,
+    ) -> Result<(RefSet, Vec<CompoundIdentifier>), VerilogError>
     where
         I: Iterator<Item = Token<SystemVerilogToken>>,
     {
         let mut refs = RefSet::new();
-        let mut deps = RefSet::new();
+        let mut deps = Vec::new();
 
         while let Some(t) = tokens.next() {
             // expecting `endclass`
@@ -35,6 +35,7 @@ use crate::core::lang::parser::{Parse, Symbol};
 use crate::core::lang::reference::{CompoundIdentifier, RefSet};
 use crate::core::lang::sv::token::keyword::Keyword;
 use crate::core::lang::sv::token::token::SystemVerilogToken;
+use crate::core::lang::verilog::preprocess::{self, Define, IncludeLoader};
 use crate::core::lang::verilog::symbols::VerilogSymbol;
 use std::str::FromStr;
 
@@ -108,6 +109,13 @@ impl SystemVerilogSymbol {
         }
     }
 
+    pub fn as_package(&self) -> Option<&Package> {
+        match &self {
+            Self::Package(p) => Some(p),
+            _ => None,
+        }
+    }
+
     pub fn get_refs(&self) -> &RefSet {
         match &self {
             Self::Module(m) => m.get_refs(),
@@ -138,6 +146,8 @@ impl SystemVerilogSymbol {
 #[derive(Debug, PartialEq)]
 pub struct SystemVerilogParser {
     symbols: Vec<Symbol<SystemVerilogSymbol>>,
+    includes: Vec<String>,
+    headers: Vec<String>,
 }
 
 impl SystemVerilogParser {
@@ -150,21 +160,68 @@ impl SystemVerilogParser {
                 .into_iter()
                 .filter_map(|f| if f.is_ok() { Some(f.unwrap()) } else { None })
                 .collect(),
+            includes: Vec::new(),
+            headers: Vec::new(),
         }
     }
 
     /// Reports an error if one is discovered in the list of symbols or in the tokenizing.
-    pub fn read(s: &str) -> Result<Self, SystemVerilogError> {
-        let symbols = SystemVerilogParser::parse(
-            SystemVerilogTokenizer::from_str(&s)?
-                .into_tokens()
-                .into_iter()
-                .filter(|s| s.as_type().is_comment() == false)
-                .collect(),
-        );
+    ///
+    /// The source code is preprocessed with the macros from `defines` before
+    /// being parsed, where included headers are found with `load`.
+    pub fn read(
+        s: &str,
+        defines: &[Define],
+        load: &mut IncludeLoader,
+    ) -> Result<Self, SystemVerilogError> {
+        let tokens = SystemVerilogTokenizer::from_str(&s)?
+            .into_tokens()
+            .into_iter()
+            .filter(|s| s.as_type().is_comment() == false)
+            .collect();
+        let code = preprocess::preprocess(tokens, defines, load)?;
+        let includes = code.get_includes().clone();
+        let headers = code.get_headers().clone();
+        let symbols = SystemVerilogParser::parse(code.into_tokens());
+        let result: Result<Vec<Symbol<SystemVerilogSymbol>>, SystemVerilogError> =
+            symbols.into_iter().collect();
+        Ok(Self {
+            symbols: result?,
+            includes: includes,
+            headers: headers,
+        })
+    }
+
+    /// Reports an error if one is discovered in the list of symbols or in the tokenizing.
+    ///
+    /// The source code within every branch of a conditional compilation directive
+    /// is parsed, and included headers are not expanded.
+    pub fn read_every_branch(s: &str) -> Result<Self, SystemVerilogError> {
+        let tokens = SystemVerilogTokenizer::from_str(s)?
+            .into_tokens()
+            .into_iter()
+            .filter(|s| s.as_type().is_comment() == false)
+            .collect();
+        let code = preprocess::preprocess_every_branch(tokens)?;
+        let includes = code.get_includes().clone();
+        let symbols = SystemVerilogParser::parse(code.into_tokens());
         let result: Result<Vec<Symbol<SystemVerilogSymbol>>, SystemVerilogError> =
             symbols.into_iter().collect();
-        Ok(Self { symbols: result? })
+        Ok(Self {
+            symbols: result?,
+            includes: includes,
+            headers: Vec::new(),
+        })
+    }
+
+    /// Returns the paths of the files included by the source code.
+    pub fn get_includes(&self) -> &Vec<String> {
+        &self.includes
+    }
+
+    /// Returns the file paths of the headers that were expanded into the source code.
+    pub fn get_headers(&self) -> &Vec<String> {
+        &self.headers
     }
 
     pub fn into_symbols(self) -> Vec<SystemVerilogSymbol> {
@@ -559,7 +616,9 @@ case ({MEM_SIZE, MEM_WIDTH})
     endcase
 endmodule
         "#;
-        let symbols = SystemVerilogParser::read(&code).unwrap().into_symbols();
+        let symbols = SystemVerilogParser::read(&code, &[], &mut preprocess::no_includes)
+            .unwrap()
+            .into_symbols();
         let sub_mod_instances = symbols
             .first()
             .unwrap()
@@ -578,4 +637,101 @@ endmodule
             ]
         );
     }
+
+    #[test]
+    fn module_lists_each_instance() {
+        let code = r#"
+module top(input logic x, input logic y);
+    adder u0 (.a(x)), u1 (.a(y));
+    adder #(.N(8)) u2 (.a(x));
+    half h0 (.a(y));
+endmodule
+        "#;
+        let symbols = SystemVerilogParser::read(&code, &[], &mut preprocess::no_includes)
+            .unwrap()
+            .into_symbols();
+        let sub_mod_instances: Vec<String> = symbols
+            .first()
+            .unwrap()
+            .as_module()
+            .unwrap()
+            .get_edge_list_entities()
+            .into_iter()
+            .map(|c| c.to_string())
+            .collect();
+        assert_eq!(sub_mod_instances, vec!["adder", "adder", "adder", "half"]);
+    }
+
+    #[test]
+    fn package_declarations() {
+        let code = r#"
+package util_pkg;
+  parameter int DEPTH = 16;
+  localparam logic [7:0] MASK = 8'hFF;
+  typedef struct packed { logic a; logic [3:0] b; } pair_t;
+  function automatic int add(input int a, input int b);
+    return a + b;
+  endfunction : add
+  class packet extends base;
+    function new();
+    endfunction
+  endclass
+endpackage
+"#;
+        let symbols = SystemVerilogParser::read(&code, &[], &mut preprocess::no_includes)
+            .unwrap()
+            .into_symbols();
+        let decls = symbols
+            .first()
+            .unwrap()
+            .as_package()
+            .unwrap()
+            .get_declarations();
+        assert_eq!(
+            decls
+                .iter()
+                .map(|d| (*d.get_kind(), d.get_name().to_string()))
+                .collect::<Vec<(package::DeclarationKind, String)>>(),
+            vec![
+                (package::DeclarationKind::Parameter, String::from("DEPTH")),
+                (package::DeclarationKind::Parameter, String::from("MASK")),
+                (package::DeclarationKind::Typedef, String::from("pair_t")),
+                (package::DeclarationKind::Function, String::from("add")),
+                (package::DeclarationKind::Class, String::from("packet")),
+            ]
+        );
+        assert_eq!(decls[1].get_code(), "localparam logic [7:0] MASK = 8'hFF");
+        assert_eq!(
+            decls[2].get_code(),
+            "typedef struct packed {logic a; logic [3:0] b;} pair_t"
+        );
+        assert_eq!(
+            decls[3].get_code(),
+            "function automatic int add(input int a, input int b)"
+        );
+    }
+
+    #[test]
+    fn units_from_every_branch() {
+        let code = r#"
+`ifdef SIM
+module model_ram(input logic clk);
+endmodule
+`else
+module ram(input logic clk);
+endmodule
+`endif
+        "#;
+        let names = |parser: SystemVerilogParser| -> Vec<String> {
+            parser
+                .into_symbols()
+                .iter()
+                .map(|s| s.as_name().unwrap().to_string())
+                .collect()
+        };
+        let parser = SystemVerilogParser::read(&code, &[], &mut preprocess::no_includes).unwrap();
+        assert_eq!(names(parser), vec!["ram"]);
+        let parser = SystemVerilogParser::read_every_branch(&code).unwrap();
+        assert_eq!(names(parser), vec!["model_ram", "ram"]);
+    }
 }
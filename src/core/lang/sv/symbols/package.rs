@@ -15,8 +15,11 @@
 //  along with this program.  If not, see <http://www.gnu.org/licenses/>.
 //
 
+use std::fmt::Display;
 use std::iter::Peekable;
 
+use serde_derive::Serialize;
+
 use crate::core::lang::{
     lexer::{Position, Token},
     reference::RefSet,
@@ -26,18 +29,191 @@ use crate::core::lang::{
             identifier::Identifier, keyword::Keyword, operator::Operator, token::SystemVerilogToken,
         },
     },
-    verilog::symbols::VerilogSymbol,
+    verilog::symbols::{Statement, VerilogSymbol},
 };
 
 use super::SystemVerilogSymbol;
 
-#[derive(Debug, PartialEq)]
+#[derive(Debug, PartialEq, Serialize)]
 pub struct Package {
+    #[serde(rename = "identifier")]
     name: Identifier,
+    declarations: Vec<Declaration>,
+    #[serde(skip_serializing)]
     refs: RefSet,
+    #[serde(skip_serializing)]
     pos: Position,
 }
 
+#[derive(Debug, PartialEq, Clone, Copy, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum DeclarationKind {
+    Parameter,
+    Typedef,
+    Function,
+    Task,
+    Class,
+}
+
+impl Display for DeclarationKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}",
+            match self {
+                Self::Parameter => "parameters",
+                Self::Typedef => "typedefs",
+                Self::Function => "functions",
+                Self::Task => "tasks",
+                Self::Class => "classes",
+            }
+        )
+    }
+}
+
+/// A publicly visible declaration within a package.
+#[derive(Debug, PartialEq, Serialize)]
+pub struct Declaration {
+    kind: DeclarationKind,
+    #[serde(rename = "identifier")]
+    name: Identifier,
+    code: String,
+}
+
+impl Declaration {
+    pub fn get_kind(&self) -> &DeclarationKind {
+        &self.kind
+    }
+
+    pub fn get_name(&self) -> &Identifier {
+        &self.name
+    }
+
+    pub fn get_code(&self) -> &str {
+        &self.code
+    }
+}
+
+/// The declaration currently being collected across multiple statements.
+enum Pending {
+    /// A declaration with braces that contain terminators, such as a struct typedef.
+    Braced(DeclarationKind, Vec<SystemVerilogToken>),
+    /// A declaration whose body is skipped until the ending keyword.
+    Body(Keyword),
+}
+
+/// Counts the braces that are opened but not yet closed within the `tokens`.
+fn open_braces(tokens: &[SystemVerilogToken]) -> isize {
+    tokens.iter().fold(0, |acc, t| {
+        if t.check_delimiter(&Operator::ConcatL) {
+            acc + 1
+        } else if t.check_delimiter(&Operator::ConcatR) {
+            acc - 1
+        } else {
+            acc
+        }
+    })
+}
+
+/// Writes the `tokens` as SystemVerilog code on a single line.
+fn tokens_to_code(tokens: &[SystemVerilogToken]) -> String {
+    let is_tight_after = |t: &SystemVerilogToken| {
+        t.as_delimiter().is_some_and(|d| {
+            matches!(
+                d,
+                Operator::ParenL
+                    | Operator::BrackL
+                    | Operator::ConcatL
+                    | Operator::Dot
+                    | Operator::ScopeResolution
+                    | Operator::Pound
+            )
+        })
+    };
+    let is_tight_before = |t: &SystemVerilogToken| {
+        t.as_delimiter().is_some_and(|d| {
+            matches!(
+                d,
+                Operator::ParenL
+                    | Operator::ParenR
+                    | Operator::BrackR
+                    | Operator::ConcatR
+                    | Operator::Comma
+                    | Operator::Terminator
+                    | Operator::Dot
+                    | Operator::ScopeResolution
+            )
+        })
+    };
+    let mut result = String::new();
+    let mut prev: Option<&SystemVerilogToken> = None;
+    // ranges within brackets are written without spaces around the ':'
+    let mut brackets = 0;
+    for t in tokens {
+        let is_range_colon = brackets > 0 && t.check_delimiter(&Operator::Colon);
+        if t.check_delimiter(&Operator::BrackL) {
+            brackets += 1;
+        } else if t.check_delimiter(&Operator::BrackR) {
+            brackets -= 1;
+        }
+        if is_range_colon == true
+            || prev.is_some_and(|p| brackets > 0 && p.check_delimiter(&Operator::Colon))
+        {
+            result.push_str(&t.to_string());
+            prev = Some(t);
+            continue;
+        }
+        if let Some(p) = prev {
+            // keep the opening parenthesis of a call attached to its name
+            let is_call = t.check_delimiter(&Operator::ParenL) && p.as_identifier().is_some();
+            let is_paren = t.check_delimiter(&Operator::ParenL);
+            if is_tight_after(p) == false
+                && (is_tight_before(t) == false || (is_paren == true && is_call == false))
+            {
+                result.push(' ');
+            }
+        }
+        result.push_str(&t.to_string());
+        prev = Some(t);
+    }
+    result
+}
+
+impl Declaration {
+    /// Creates a declaration of the given `kind` from its `tokens`.
+    fn with(kind: DeclarationKind, tokens: Vec<SystemVerilogToken>) -> Option<Self> {
+        // ignore the final terminator
+        let end = tokens
+            .iter()
+            .rposition(|t| t.check_delimiter(&Operator::Terminator))
+            .unwrap_or(tokens.len());
+        let code = &tokens[..end];
+        let name = match kind {
+            // the last name before the assignment
+            DeclarationKind::Parameter => code
+                .iter()
+                .take_while(|t| t.check_delimiter(&Operator::BlockAssign) == false)
+                .filter_map(|t| t.as_identifier())
+                .last(),
+            // the last name of the declaration
+            DeclarationKind::Typedef => code.iter().filter_map(|t| t.as_identifier()).next_back(),
+            // the last name before the arguments
+            DeclarationKind::Function | DeclarationKind::Task => code
+                .iter()
+                .take_while(|t| t.check_delimiter(&Operator::ParenL) == false)
+                .filter_map(|t| t.as_identifier())
+                .last(),
+            // the first name after the keyword
+            DeclarationKind::Class => code.iter().filter_map(|t| t.as_identifier()).next(),
+        }?;
+        Some(Self {
+            kind: kind,
+            name: name.clone(),
+            code: tokens_to_code(code),
+        })
+    }
+}
+
 impl Package {
     pub fn get_name(&self) -> &Identifier {
         &self.name
@@ -51,6 +227,11 @@ impl Package {
         &self.refs
     }
 
+    /// Accesses the publicly visible declarations of the package.
+    pub fn get_declarations(&self) -> &Vec<Declaration> {
+        &self.declarations
+    }
+
     pub fn extend_refs(&mut self, refs: RefSet) {
         self.refs.extend(refs);
     }
@@ -86,6 +267,8 @@ impl Package {
         }
 
         let mut refs = RefSet::new();
+        let mut declarations = Vec::new();
+        let mut pending: Option<Pending> = None;
 
         // parse until finding `endpackage`
         while let Some(t) = tokens.next() {
@@ -100,14 +283,77 @@ impl Package {
                 refs.extend(i_refs);
             } else if let Some(stmt) = VerilogSymbol::into_next_statement(t, tokens)? {
                 // println!("{}", statement_to_string(&stmt));
+                pending = Self::collect_declaration(&stmt, pending, &mut declarations);
                 VerilogSymbol::handle_statement(stmt, None, None, &mut refs, None)?;
             }
         }
 
         Ok(Package {
             name: name,
+            declarations: declarations,
             refs: refs,
             pos: pos,
         })
     }
+
+    /// Records the declaration made by the statement `stmt`, if any, and returns
+    /// the declaration that is still being collected.
+    fn collect_declaration(
+        stmt: &Statement,
+        pending: Option<Pending>,
+        declarations: &mut Vec<Declaration>,
+    ) -> Option<Pending> {
+        let tokens: Vec<SystemVerilogToken> = stmt.iter().map(|t| t.as_type().clone()).collect();
+        match pending {
+            // skip the statements within the body
+            Some(Pending::Body(end)) => match tokens.iter().any(|t| t.check_keyword(&end)) {
+                true => None,
+                false => Some(Pending::Body(end)),
+            },
+            Some(Pending::Braced(kind, mut code)) => {
+                code.extend(tokens);
+                match open_braces(&code) > 0 {
+                    true => Some(Pending::Braced(kind, code)),
+                    false => {
+                        declarations.extend(Declaration::with(kind, code));
+                        None
+                    }
+                }
+            }
+            None => {
+                // skip qualifiers in front of the declaration
+                let start = tokens.iter().position(|t| {
+                    [
+                        Keyword::Virtual,
+                        Keyword::Static,
+                        Keyword::Automatic,
+                        Keyword::Extern,
+                    ]
+                    .iter()
+                    .any(|k| t.check_keyword(k))
+                        == false
+                })?;
+                let (kind, end) = match tokens[start].as_keyword()? {
+                    Keyword::Parameter | Keyword::Localparam => (DeclarationKind::Parameter, None),
+                    Keyword::Typedef => (DeclarationKind::Typedef, None),
+                    Keyword::Function => (DeclarationKind::Function, Some(Keyword::Endfunction)),
+                    Keyword::Task => (DeclarationKind::Task, Some(Keyword::Endtask)),
+                    Keyword::Class => (DeclarationKind::Class, Some(Keyword::Endclass)),
+                    _ => return None,
+                };
+                let is_extern = tokens[..start]
+                    .iter()
+                    .any(|t| t.check_keyword(&Keyword::Extern));
+                match open_braces(&tokens) > 0 {
+                    true => return Some(Pending::Braced(kind, tokens)),
+                    false => declarations.extend(Declaration::with(kind, tokens)),
+                }
+                match is_extern {
+                    // extern declarations do not have a body
+                    true => None,
+                    false => end.map(Pending::Body),
+                }
+            }
+        }
+    }
 }
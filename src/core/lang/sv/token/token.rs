@@ -39,6 +39,8 @@ pub enum SystemVerilogToken {
     Keyword(Keyword),
     Directive(String),
     StringLiteral(String),
+    /// A backslash at the end of a line that joins it to the next line.
+    LineContinuation,
     EOF,
 }
 
@@ -52,6 +54,7 @@ impl From<VerilogToken> for SystemVerilogToken {
                 Self::Operator(Operator::transform(&t.to_string()).unwrap())
             }
             VerilogToken::StringLiteral(t) => Self::StringLiteral(t),
+            VerilogToken::LineContinuation => Self::LineContinuation,
             VerilogToken::EOF => Self::EOF,
             VerilogToken::Number(t) => Self::Number(t),
             VerilogToken::Keyword(t) => {
@@ -74,6 +77,7 @@ impl Display for SystemVerilogToken {
                 Self::Keyword(k) => k.to_string(),
                 Self::StringLiteral(s) => format!("\"{}\"", s.to_string()),
                 Self::Directive(d) => format!("`{}", d.to_string()),
+                Self::LineContinuation => String::from("\\"),
                 Self::EOF => String::new(),
             }
         )
@@ -96,6 +100,10 @@ impl SystemVerilogToken {
         }
     }
 
+    pub fn is_line_continuation(&self) -> bool {
+        matches!(self, SystemVerilogToken::LineContinuation)
+    }
+
     pub fn is_directive(&self) -> bool {
         match self {
             SystemVerilogToken::Directive(_) => true,
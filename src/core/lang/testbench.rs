@@ -0,0 +1,409 @@
+//
+//  Copyright (C) 2022-2025  Chase Ruskin
+//
+//  This program is free software: you can redistribute it and/or modify
+//  it under the terms of the GNU General Public License as published by
+//  the Free Software Foundation, either version 3 of the License, or
+//  (at your option) any later version.
+//
+//  This program is distributed in the hope that it will be useful,
+//  but WITHOUT ANY WARRANTY; without even the implied warranty of
+//  MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+//  GNU General Public License for more details.
+//
+//  You should have received a copy of the GNU General Public License
+//  along with this program.  If not, see <http://www.gnu.org/licenses/>.
+//
+
+//! Generates testbench skeletons that instantiate a design unit as the device
+//! under test.
+//!
+//! Clock and reset inputs are detected by their names, such as `clk`, `sys_clk`,
+//! `rst`, and `rst_n`.
+
+use super::sv::format::SystemVerilogFormat;
+use super::sv::token::keyword::Keyword as SvKeyword;
+use super::sv::token::token::SystemVerilogToken;
+use super::verilog::interface;
+use super::verilog::symbols::module::Module;
+use super::vhdl::format::VhdlFormat;
+use super::vhdl::symbols::entity::Entity;
+use super::vhdl::token::keyword::Keyword as VhdlKeyword;
+use super::vhdl::token::Identifier as VhdlIdentifier;
+
+/// The clock period (in nanoseconds) of the generated clocks.
+const CLK_PERIOD: usize = 10;
+
+/// The number of clock periods to hold the reset signals asserted.
+const RESET_CYCLES: usize = 4;
+
+/// Removes any affixes that only label a port's direction, such as `_i` or `in_`.
+fn strip_direction(name: &str) -> Vec<String> {
+    name.to_lowercase()
+        .split('_')
+        .filter(|p| p.is_empty() == false && p != &"i" && p != &"in")
+        .map(|p| p.to_string())
+        .collect()
+}
+
+/// Checks if the port `name` is a clock signal.
+pub fn is_clock(name: &str) -> bool {
+    let name = strip_direction(name).concat();
+    name.ends_with("clk") || name.ends_with("clock")
+}
+
+/// Checks if the port `name` is a reset signal.
+///
+/// One of the `_`-separated parts of the name must be a reset, such as `rst`,
+/// `reset`, `arst`, `nrst`, or `rstn`, so names like `burst` are not resets.
+pub fn is_reset(name: &str) -> bool {
+    strip_direction(name).iter().any(|part| {
+        ["", "a", "s", "n"].iter().any(|prefix| {
+            ["rst", "reset"].iter().any(|word| {
+                ["", "n", "b"]
+                    .iter()
+                    .any(|suffix| part == &format!("{}{}{}", prefix, word, suffix))
+            })
+        })
+    })
+}
+
+/// Checks if the reset port `name` is asserted when low.
+pub fn is_active_low(name: &str) -> bool {
+    let parts = strip_direction(name);
+    let name = parts.concat();
+    parts.iter().any(|p| p == "n" || p == "b")
+        || name.ends_with("rstn")
+        || name.ends_with("resetn")
+        || name.starts_with("nrst")
+        || name.starts_with("nreset")
+}
+
+/// Indents every non-empty line of `text` by `depth` levels of `tab_size` spaces.
+fn indent(text: &str, tab_size: u8, depth: usize) -> String {
+    let spaces = " ".repeat(tab_size as usize * depth);
+    text.lines()
+        .map(|l| match l.is_empty() {
+            true => String::from("\n"),
+            false => format!("{}{}\n", spaces, l),
+        })
+        .collect::<Vec<String>>()
+        .join("")
+}
+
+/// Generates a VHDL testbench for the `entity`.
+///
+/// The device under test is instantiated directly from `library` when it is
+/// provided, otherwise it is declared and instantiated as a component.
+pub fn into_vhdl_testbench(
+    entity: &Entity,
+    library: &Option<VhdlIdentifier>,
+    inst: &Option<VhdlIdentifier>,
+    fmt: &VhdlFormat,
+    signal_prefix: &str,
+    signal_suffix: &str,
+) -> String {
+    let tab = fmt.get_tab_size();
+    let name = entity.get_name().to_string();
+
+    // collect the single-bit inputs that can be driven as clocks and resets
+    let mut clocks = Vec::new();
+    let mut resets = Vec::new();
+    for port in entity.get_ports().0.iter() {
+        let is_input = match port.get_mode() {
+            Some(m) => m == &VhdlKeyword::In,
+            None => true,
+        };
+        let is_bit = match port.get_datatype().as_slice() {
+            [t] => {
+                ["std_logic", "std_ulogic", "bit"].contains(&t.to_string().to_lowercase().as_str())
+            }
+            _ => false,
+        };
+        if is_input == false || is_bit == false {
+            continue;
+        }
+        let id = port.get_identifier().to_string();
+        let signal = format!("{}{}{}", signal_prefix, id, signal_suffix);
+        if is_clock(&id) == true {
+            clocks.push(signal);
+        } else if is_reset(&id) == true {
+            resets.push((signal, is_active_low(&id)));
+        }
+    }
+
+    let mut result =
+        String::from("library ieee;\nuse ieee.std_logic_1164.all;\nuse ieee.numeric_std.all;\n");
+    if let Some(lib) = library {
+        if lib.to_string().to_lowercase() != "work" {
+            result.push_str(&format!("\nlibrary {};\n", lib));
+        }
+    }
+    result.push_str(&format!(
+        "\nentity {0}_tb is\nend entity;\n\narchitecture sim of {0}_tb is\n\n",
+        name
+    ));
+
+    // declarations
+    if library.is_none() {
+        result.push_str(&indent(&entity.into_component(fmt), tab, 1));
+        result.push('\n');
+    }
+    let constants = entity.into_constants(fmt, "", "");
+    if constants.is_empty() == false {
+        result.push_str(&indent(&constants, tab, 1));
+        result.push('\n');
+    }
+    let signals = entity.into_signals(fmt, signal_prefix, signal_suffix);
+    if signals.is_empty() == false {
+        result.push_str(&indent(&signals, tab, 1));
+        result.push('\n');
+    }
+    result.push_str(&indent(
+        &format!(
+            "constant CLK_PERIOD : time := {} ns;\nsignal sim_done : boolean := false;\n",
+            CLK_PERIOD
+        ),
+        tab,
+        1,
+    ));
+    result.push_str("\nbegin\n\n");
+
+    // device under test
+    result.push_str(&indent(
+        &entity.into_instance(inst, library, fmt, signal_prefix, signal_suffix, "", ""),
+        tab,
+        1,
+    ));
+
+    // clock generation
+    for clk in &clocks {
+        result.push('\n');
+        result.push_str(&indent(&format!("{}_gen : process\nbegin", clk), tab, 1));
+        result.push_str(&indent("while sim_done = false loop", tab, 2));
+        result.push_str(&indent(
+            &format!(
+                "{0} <= '0';\nwait for CLK_PERIOD / 2;\n{0} <= '1';\nwait for CLK_PERIOD / 2;",
+                clk
+            ),
+            tab,
+            3,
+        ));
+        result.push_str(&indent("end loop;\nwait;", tab, 2));
+        result.push_str(&indent("end process;", tab, 1));
+    }
+
+    // stimulus and end of simulation
+    result.push('\n');
+    result.push_str(&indent("stimulus : process\nbegin", tab, 1));
+    if resets.is_empty() == false {
+        for (rst, active_low) in &resets {
+            let level = if *active_low == true { '0' } else { '1' };
+            result.push_str(&indent(&format!("{} <= '{}';", rst, level), tab, 2));
+        }
+        result.push_str(&indent(
+            &format!("wait for CLK_PERIOD * {};", RESET_CYCLES),
+            tab,
+            2,
+        ));
+        for (rst, active_low) in &resets {
+            let level = if *active_low == true { '1' } else { '0' };
+            result.push_str(&indent(&format!("{} <= '{}';", rst, level), tab, 2));
+        }
+    }
+    result.push_str(&indent(
+        "-- TODO: drive the inputs and check the outputs\nwait for CLK_PERIOD;\n",
+        tab,
+        2,
+    ));
+    result.push_str(&indent(
+        "-- stop the simulation\nsim_done <= true;\nreport \"simulation complete\";\nwait;",
+        tab,
+        2,
+    ));
+    result.push_str(&indent("end process;", tab, 1));
+    result.push_str("\nend architecture;\n");
+    result
+}
+
+/// Generates a Verilog or SystemVerilog testbench (matching the language of the
+/// `module`) for the `module`.
+pub fn into_verilog_testbench(
+    module: &Module,
+    inst: &Option<VhdlIdentifier>,
+    fmt: &SystemVerilogFormat,
+    signal_prefix: &str,
+    signal_suffix: &str,
+) -> String {
+    let tab = fmt.get_tab_size();
+    let is_sv = module.get_language() == "systemverilog";
+    // inputs are driven by the testbench, so they must be variables instead of nets
+    let var = match is_sv {
+        true => SvKeyword::Logic,
+        false => SvKeyword::Reg,
+    };
+
+    let ports: Vec<interface::Port> = module
+        .get_ports()
+        .iter()
+        .map(|p| {
+            let mut p = p.clone();
+            if p.get_mode().is_none_or(|m| m == &SvKeyword::Input) && p.get_data_type().is_none() {
+                p.set_data_type(SystemVerilogToken::Keyword(var.clone()));
+            }
+            p
+        })
+        .collect();
+
+    // collect the single-bit inputs that can be driven as clocks and resets
+    let mut clocks = Vec::new();
+    let mut resets = Vec::new();
+    for port in &ports {
+        if port.get_mode().is_some_and(|m| m != &SvKeyword::Input) || port.get_range().is_some() {
+            continue;
+        }
+        let id = port.get_name().to_string();
+        let signal = format!("{}{}{}", signal_prefix, id, signal_suffix);
+        if is_clock(&id) == true {
+            clocks.push(signal);
+        } else if is_reset(&id) == true {
+            resets.push((signal, is_active_low(&id)));
+        }
+    }
+
+    let mut result = format!(
+        "`timescale 1ns / 1ps\n\nmodule {}_tb;\n\n",
+        module.get_name()
+    );
+
+    // declarations
+    let param_spacer = match fmt.is_auto_name_aligned() {
+        true => Some(interface::longest_port_decl(
+            false,
+            module.get_parameters(),
+            fmt,
+        )),
+        false => None,
+    };
+    let mut decls = String::new();
+    module.get_parameters().iter().for_each(|p| {
+        decls.push_str(&p.into_declaration(false, &param_spacer, "", "", fmt));
+        decls.push_str(";\n");
+    });
+    if decls.is_empty() == false {
+        result.push_str(&indent(&decls, tab, 1));
+        result.push('\n');
+    }
+    let port_spacer = match fmt.is_auto_name_aligned() {
+        true => Some(interface::longest_port_decl(false, &ports, fmt)),
+        false => None,
+    };
+    let mut decls = String::new();
+    ports.iter().for_each(|p| {
+        decls.push_str(&p.into_declaration(false, &port_spacer, signal_prefix, signal_suffix, fmt));
+        decls.push_str(";\n");
+    });
+    if decls.is_empty() == false {
+        result.push_str(&indent(&decls, tab, 1));
+        result.push('\n');
+    }
+    result.push_str(&indent(
+        &format!("localparam CLK_PERIOD = {};\n", CLK_PERIOD),
+        tab,
+        1,
+    ));
+
+    // device under test
+    result.push('\n');
+    result.push_str(&indent(
+        &module.into_instance(inst, signal_prefix, signal_suffix, fmt),
+        tab,
+        1,
+    ));
+
+    // clock generation
+    for clk in &clocks {
+        result.push('\n');
+        result.push_str(&indent(&format!("initial {} = 1'b0;", clk), tab, 1));
+        result.push_str(&indent(
+            &format!("always #(CLK_PERIOD / 2) {0} = ~{0};", clk),
+            tab,
+            1,
+        ));
+    }
+
+    // stimulus and end of simulation
+    result.push('\n');
+    result.push_str(&indent("initial begin", tab, 1));
+    if resets.is_empty() == false {
+        for (rst, active_low) in &resets {
+            let level = if *active_low == true { '0' } else { '1' };
+            result.push_str(&indent(&format!("{} = 1'b{};", rst, level), tab, 2));
+        }
+        result.push_str(&indent(
+            &format!("#(CLK_PERIOD * {});", RESET_CYCLES),
+            tab,
+            2,
+        ));
+        for (rst, active_low) in &resets {
+            let level = if *active_low == true { '1' } else { '0' };
+            result.push_str(&indent(&format!("{} = 1'b{};", rst, level), tab, 2));
+        }
+    }
+    result.push_str(&indent(
+        "// TODO: drive the inputs and check the outputs\n#(CLK_PERIOD);\n",
+        tab,
+        2,
+    ));
+    result.push_str(&indent(
+        "// stop the simulation\n$display(\"simulation complete\");\n$finish;",
+        tab,
+        2,
+    ));
+    result.push_str(&indent("end", tab, 1));
+    result.push_str("\nendmodule\n");
+    result
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn detect_clocks() {
+        assert_eq!(is_clock("clk"), true);
+        assert_eq!(is_clock("CLK_I"), true);
+        assert_eq!(is_clock("i_sys_clk"), true);
+        assert_eq!(is_clock("clock"), true);
+        assert_eq!(is_clock("aclk"), true);
+        assert_eq!(is_clock("clk_en"), false);
+        assert_eq!(is_clock("data_in"), false);
+    }
+
+    #[test]
+    fn detect_resets() {
+        assert_eq!(is_reset("rst"), true);
+        assert_eq!(is_reset("rst_n"), true);
+        assert_eq!(is_reset("areset_i"), true);
+        assert_eq!(is_reset("nrst"), true);
+        assert_eq!(is_reset("i_sys_rstn"), true);
+        assert_eq!(is_reset("clk"), false);
+        assert_eq!(is_reset("burst"), false);
+        assert_eq!(is_reset("first"), false);
+        assert_eq!(is_reset("preset_val"), false);
+        assert_eq!(is_reset("burst_len_i"), false);
+
+        assert_eq!(is_active_low("rst"), false);
+        assert_eq!(is_active_low("reset_i"), false);
+        assert_eq!(is_active_low("rst_n"), true);
+        assert_eq!(is_active_low("RSTN"), true);
+        assert_eq!(is_active_low("aresetn"), true);
+        assert_eq!(is_active_low("nrst"), true);
+        assert_eq!(is_active_low("rst_n_i"), true);
+    }
+
+    #[test]
+    fn indent_lines() {
+        assert_eq!(indent("a\n\nb", 2, 2), "    a\n\n    b\n");
+    }
+}
@@ -0,0 +1,614 @@
+//
+//  Copyright (C) 2022-2025  Chase Ruskin
+//
+//  This program is free software: you can redistribute it and/or modify
+//  it under the terms of the GNU General Public License as published by
+//  the Free Software Foundation, either version 3 of the License, or
+//  (at your option) any later version.
+//
+//  This program is distributed in the hope that it will be useful,
+//  but WITHOUT ANY WARRANTY; without even the implied warranty of
+//  MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+//  GNU General Public License for more details.
+//
+//  You should have received a copy of the GNU General Public License
+//  along with this program.  If not, see <http://www.gnu.org/licenses/>.
+//
+
+//! Translates the interface of a design unit written in one language into an
+//! equivalent unit written in another language, so it can be instantiated across
+//! language boundaries.
+//!
+//! The translated interface is written as source code and parsed again, which
+//! lets the existing code generators for each language display it.
+
+use super::sv::symbols::{SystemVerilogParser, SystemVerilogSymbol};
+use super::sv::token::keyword::Keyword as SvKeyword;
+use super::sv::token::operator::Operator;
+use super::sv::token::token::SystemVerilogToken;
+use super::verilog::interface::Port;
+use super::verilog::preprocess;
+use super::verilog::symbols::module::Module;
+use super::verilog::symbols::{VerilogParser, VerilogSymbol};
+use super::vhdl::interface::InterfaceDeclaration;
+use super::vhdl::symbols::entity::Entity;
+use super::vhdl::symbols::{VHDLParser, VhdlSymbol};
+use super::vhdl::token::delimiter::Delimiter;
+use super::vhdl::token::keyword::Keyword as VhdlKeyword;
+use super::vhdl::token::literal::AbstLiteral;
+use super::vhdl::token::VhdlToken;
+use super::Lang;
+
+/// Creates a VHDL entity with the same interface as the Verilog or SystemVerilog `module`.
+pub fn into_vhdl_entity(module: &Module) -> Option<Entity> {
+    let code = module_to_vhdl(module);
+    VHDLParser::read(&code)
+        .ok()?
+        .into_symbols()
+        .into_iter()
+        .find_map(|s| match s {
+            VhdlSymbol::Entity(e) => Some(e),
+            _ => None,
+        })
+}
+
+/// Creates a Verilog or SystemVerilog module (as determined by `lang`) with the same
+/// interface as the VHDL `entity`.
+pub fn into_module(entity: &Entity, lang: &Lang) -> Option<Module> {
+    let is_sv = lang == &Lang::SystemVerilog;
+    let code = entity_to_module(entity, is_sv);
+    match is_sv {
+        true => SystemVerilogParser::read(&code, &[], &mut preprocess::no_includes)
+            .ok()?
+            .into_symbols()
+            .into_iter()
+            .find_map(|s| match s {
+                SystemVerilogSymbol::Module(m) => Some(m),
+                _ => None,
+            }),
+        false => VerilogParser::read(&code, &[], &mut preprocess::no_includes)
+            .ok()?
+            .into_symbols()
+            .into_iter()
+            .find_map(|s| match s {
+                VerilogSymbol::Module(m) => Some(m),
+                _ => None,
+            }),
+    }
+}
+
+/// Writes the source code for a VHDL entity declaration equivalent to the `module`.
+fn module_to_vhdl(module: &Module) -> String {
+    let mut result = format!("entity {} is\n", module.get_name());
+    let generics: Vec<String> = module.get_parameters().iter().map(vhdl_generic).collect();
+    if generics.is_empty() == false {
+        result.push_str(&format!("generic (\n{}\n);\n", generics.join(";\n")));
+    }
+    let ports: Vec<String> = module.get_ports().iter().map(vhdl_port).collect();
+    if ports.is_empty() == false {
+        result.push_str(&format!("port (\n{}\n);\n", ports.join(";\n")));
+    }
+    result.push_str("end entity;\n");
+    result
+}
+
+/// Writes the source code for a module declaration equivalent to the `entity`.
+fn entity_to_module(entity: &Entity, is_sv: bool) -> String {
+    let mut result = format!("module {}", entity.get_name());
+    let params: Vec<String> = entity
+        .get_generics()
+        .0
+        .iter()
+        .filter(|g| {
+            g.get_initial_keyword().is_none()
+                || g.get_initial_keyword() == Some(&VhdlKeyword::Constant)
+        })
+        .map(|g| verilog_parameter(g, is_sv))
+        .collect();
+    if params.is_empty() == false {
+        result.push_str(&format!(" #(\n{}\n)", params.join(",\n")));
+    }
+    let ports: Vec<String> = entity
+        .get_ports()
+        .0
+        .iter()
+        .map(|p| verilog_port(p, is_sv))
+        .collect();
+    result.push_str(&format!(" (\n{}\n);\nendmodule\n", ports.join(",\n")));
+    result
+}
+
+/* Verilog to VHDL */
+
+fn vhdl_generic(param: &Port) -> String {
+    let default = param.get_default();
+    let datatype = match param.get_range() {
+        Some(range) => vhdl_vector_type("std_logic_vector", range),
+        None => match param.get_data_type() {
+            Some(SystemVerilogToken::Keyword(SvKeyword::Real)) => String::from("real"),
+            Some(SystemVerilogToken::Keyword(SvKeyword::String)) => String::from("string"),
+            Some(SystemVerilogToken::Keyword(SvKeyword::Bit))
+            | Some(SystemVerilogToken::Keyword(SvKeyword::Logic))
+            | Some(SystemVerilogToken::Keyword(SvKeyword::Reg)) => String::from("std_logic"),
+            Some(_) => String::from("integer"),
+            // determine the type from the default value
+            None => match default.and_then(|d| d.first()) {
+                Some(SystemVerilogToken::StringLiteral(_)) => String::from("string"),
+                Some(SystemVerilogToken::Number(n)) if n.to_string().contains('.') => {
+                    String::from("real")
+                }
+                _ => String::from("integer"),
+            },
+        },
+    };
+    let is_vector = param.get_range().is_some();
+    match default.and_then(|d| vhdl_expr(d, is_vector)) {
+        Some(d) => format!("{} : {} := {}", param.get_name(), datatype, d),
+        None => format!("{} : {}", param.get_name(), datatype),
+    }
+}
+
+fn vhdl_port(port: &Port) -> String {
+    let mode = match port.get_mode() {
+        Some(SvKeyword::Output) => "out",
+        Some(SvKeyword::Inout) | Some(SvKeyword::Ref) => "inout",
+        _ => "in",
+    };
+    let datatype = match port.get_data_type() {
+        Some(SystemVerilogToken::Keyword(SvKeyword::Integer))
+        | Some(SystemVerilogToken::Keyword(SvKeyword::Int)) => String::from("integer"),
+        Some(SystemVerilogToken::Keyword(SvKeyword::Real)) => String::from("real"),
+        Some(SystemVerilogToken::Keyword(SvKeyword::Bit)) => match port.get_range() {
+            Some(range) => vhdl_vector_type("bit_vector", range),
+            None => String::from("bit"),
+        },
+        // keep the names of user-defined types
+        Some(SystemVerilogToken::Identifier(id)) => id.to_string(),
+        _ => match port.get_range() {
+            Some(range) => match port.is_signed() {
+                true => vhdl_vector_type("signed", range),
+                false => vhdl_vector_type("std_logic_vector", range),
+            },
+            None => String::from("std_logic"),
+        },
+    };
+    format!("{} : {} {}", port.get_name(), mode, datatype)
+}
+
+/// Writes the first packed dimension of `range` as the constraint of the VHDL `base` type.
+fn vhdl_vector_type(base: &str, range: &[SystemVerilogToken]) -> String {
+    let (msb, lsb) = match split_verilog_range(range) {
+        Some(r) => r,
+        None => return String::from(base),
+    };
+    // an ascending range uses 'to' instead of 'downto'
+    let direction = match (verilog_int(msb), verilog_int(lsb)) {
+        (Some(m), Some(l)) if m < l => "to",
+        _ => "downto",
+    };
+    let as_vhdl = |t: &[SystemVerilogToken]| vhdl_expr(t, false).unwrap_or(join_verilog(t));
+    format!("{}({} {} {})", base, as_vhdl(msb), direction, as_vhdl(lsb))
+}
+
+/// Separates the left and right bounds of the first dimension in a packed `range`.
+fn split_verilog_range(
+    range: &[SystemVerilogToken],
+) -> Option<(&[SystemVerilogToken], &[SystemVerilogToken])> {
+    if range.first()?.check_delimiter(&Operator::BrackL) == false {
+        return None;
+    }
+    let mut depth = 0;
+    let mut colon = None;
+    for (i, t) in range.iter().enumerate() {
+        if t.check_delimiter(&Operator::BrackL) || t.check_delimiter(&Operator::ParenL) {
+            depth += 1;
+        } else if t.check_delimiter(&Operator::BrackR) || t.check_delimiter(&Operator::ParenR) {
+            depth -= 1;
+            if depth == 0 {
+                return Some((&range[1..colon?], &range[colon? + 1..i]));
+            }
+        } else if depth == 1 && colon.is_none() && t.check_delimiter(&Operator::Colon) {
+            colon = Some(i);
+        }
+    }
+    None
+}
+
+fn verilog_int(tokens: &[SystemVerilogToken]) -> Option<i64> {
+    match tokens {
+        [SystemVerilogToken::Number(n)] => n.to_string().replace('_', "").parse().ok(),
+        _ => None,
+    }
+}
+
+/// Translates a Verilog constant expression into a VHDL expression, if every token
+/// has an equivalent in VHDL.
+fn vhdl_expr(tokens: &[SystemVerilogToken], is_vector: bool) -> Option<String> {
+    let mut result = Vec::new();
+    for t in tokens {
+        result.push(match t {
+            SystemVerilogToken::Identifier(id) => id.to_string(),
+            SystemVerilogToken::StringLiteral(s) => format!("\"{}\"", s),
+            SystemVerilogToken::Number(n) => {
+                let n = n.to_string().replace('_', "");
+                match n.split_once('\'') {
+                    Some((_, based)) => {
+                        let based = based.trim_start_matches(['s', 'S']);
+                        let (base, digits) = based.split_at(based.len().min(1));
+                        match is_vector {
+                            true => match base.to_ascii_lowercase().as_str() {
+                                "b" => format!("\"{}\"", digits),
+                                "h" => format!("x\"{}\"", digits),
+                                "o" => format!("o\"{}\"", digits),
+                                _ => return None,
+                            },
+                            false => match base.to_ascii_lowercase().as_str() {
+                                "b" => format!("2#{}#", digits),
+                                "h" => format!("16#{}#", digits),
+                                "o" => format!("8#{}#", digits),
+                                "d" => digits.to_string(),
+                                _ => return None,
+                            },
+                        }
+                    }
+                    None => n,
+                }
+            }
+            SystemVerilogToken::Operator(op) => match op {
+                Operator::Plus
+                | Operator::Minus
+                | Operator::Mult
+                | Operator::Div
+                | Operator::Pow
+                | Operator::ParenL
+                | Operator::ParenR => op.to_string(),
+                _ => return None,
+            },
+            _ => return None,
+        });
+    }
+    match result.is_empty() {
+        true => None,
+        false => Some(result.concat()),
+    }
+}
+
+fn join_verilog(tokens: &[SystemVerilogToken]) -> String {
+    tokens
+        .iter()
+        .map(|t| t.to_string())
+        .collect::<Vec<String>>()
+        .join("")
+}
+
+/* VHDL to Verilog */
+
+fn verilog_parameter(generic: &InterfaceDeclaration, is_sv: bool) -> String {
+    let (base, range) = split_vhdl_type(generic.get_datatype());
+    let datatype = match base.as_str() {
+        "integer" | "natural" | "positive" => match is_sv {
+            true => String::from("int "),
+            false => String::from("integer "),
+        },
+        "real" => String::from("real "),
+        "string" if is_sv == true => String::from("string "),
+        "boolean" | "std_logic" | "std_ulogic" | "bit" if is_sv == true => String::from("bit "),
+        _ => match &range {
+            Some(r) => match is_sv {
+                true => format!("logic{} {} ", verilog_signing(&base), r),
+                false => format!("{}{} ", verilog_signing(&base), r)
+                    .trim_start()
+                    .to_string(),
+            },
+            None => String::new(),
+        },
+    };
+    let is_vector = range.is_some();
+    match generic
+        .get_default()
+        .and_then(|d| verilog_expr(d, is_vector, is_sv))
+    {
+        Some(d) => format!("parameter {}{} = {}", datatype, generic.get_identifier(), d),
+        None => format!("parameter {}{}", datatype, generic.get_identifier()),
+    }
+}
+
+fn verilog_port(port: &InterfaceDeclaration, is_sv: bool) -> String {
+    let mode = match port.get_mode() {
+        Some(VhdlKeyword::Out) | Some(VhdlKeyword::Buffer) => "output",
+        Some(VhdlKeyword::Inout) => "inout",
+        _ => "input",
+    };
+    let (base, range) = split_vhdl_type(port.get_datatype());
+    let net = match is_sv {
+        true => "logic",
+        false => "wire",
+    };
+    let datatype = match base.as_str() {
+        "integer" | "natural" | "positive" => match is_sv {
+            true => String::from("int"),
+            false => String::from("integer"),
+        },
+        "real" => String::from("real"),
+        "std_logic" | "std_ulogic" | "bit" | "boolean" => String::from(net),
+        "std_logic_vector" | "std_ulogic_vector" | "bit_vector" | "unsigned" | "signed" => {
+            match range {
+                Some(r) => format!("{}{} {}", net, verilog_signing(&base), r),
+                None => String::from(net),
+            }
+        }
+        // keep the names of user-defined types
+        _ => base.clone(),
+    };
+    format!("{} {} {}", mode, datatype, port.get_identifier())
+}
+
+fn verilog_signing(base: &str) -> &'static str {
+    match base {
+        "signed" => " signed",
+        _ => "",
+    }
+}
+
+/// Separates a VHDL subtype indication into its lowercase type name (without any
+/// library or package prefix) and its index constraint written as a Verilog
+/// packed dimension.
+fn split_vhdl_type(tokens: &[VhdlToken]) -> (String, Option<String>) {
+    let mut iter = tokens.iter().peekable();
+    let mut base = String::new();
+    while let Some(t) = iter.next() {
+        match t {
+            VhdlToken::Identifier(id) => base = id.to_string().to_lowercase(),
+            VhdlToken::Delimiter(Delimiter::Dot) => (),
+            _ => break,
+        }
+        if iter
+            .peek()
+            .is_some_and(|n| n.check_delimiter(&Delimiter::ParenL))
+        {
+            break;
+        }
+    }
+    // find the index constraint within the parentheses
+    if iter
+        .next()
+        .is_some_and(|t| t.check_delimiter(&Delimiter::ParenL))
+        == false
+    {
+        return (base, None);
+    }
+    let mut depth = 1;
+    let mut left = Vec::new();
+    let mut right = Vec::new();
+    let mut found_direction = false;
+    for t in iter {
+        if t.check_delimiter(&Delimiter::ParenL) {
+            depth += 1;
+        } else if t.check_delimiter(&Delimiter::ParenR) {
+            depth -= 1;
+            if depth == 0 {
+                break;
+            }
+        } else if depth == 1
+            && (t.check_keyword(&VhdlKeyword::Downto) || t.check_keyword(&VhdlKeyword::To))
+        {
+            found_direction = true;
+            continue;
+        }
+        match found_direction {
+            true => right.push(t.clone()),
+            false => left.push(t.clone()),
+        }
+    }
+    match found_direction {
+        true => {
+            let as_verilog =
+                |t: &[VhdlToken]| verilog_expr(t, false, false).unwrap_or(join_vhdl(t));
+            (
+                base,
+                Some(format!("[{}:{}]", as_verilog(&left), as_verilog(&right))),
+            )
+        }
+        false => (base, None),
+    }
+}
+
+/// Translates a VHDL static expression into a Verilog expression, if every token
+/// has an equivalent in Verilog.
+fn verilog_expr(tokens: &[VhdlToken], is_vector: bool, is_sv: bool) -> Option<String> {
+    // handle aggregates of a single bit value, such as '(others => '0')'; the fill
+    // literals only exist in SystemVerilog
+    if let [VhdlToken::Delimiter(Delimiter::ParenL), VhdlToken::Keyword(VhdlKeyword::Others), VhdlToken::Delimiter(Delimiter::Arrow), VhdlToken::CharLiteral(c), VhdlToken::Delimiter(Delimiter::ParenR)] =
+        tokens
+    {
+        return match (is_sv, c.0.as_str()) {
+            (true, _) => Some(format!("'{}", c.0)),
+            (false, "0") => Some(String::from("0")),
+            (false, _) => None,
+        };
+    }
+    let mut result = Vec::new();
+    for t in tokens {
+        result.push(match t {
+            VhdlToken::Identifier(id) => match id.to_string().to_lowercase().as_str() {
+                "true" => String::from("1"),
+                "false" => String::from("0"),
+                _ => id.to_string(),
+            },
+            VhdlToken::AbstLiteral(AbstLiteral::Decimal(n)) => n.clone(),
+            VhdlToken::AbstLiteral(AbstLiteral::Based(n)) => {
+                let mut parts = n.split('#');
+                let base = parts.next()?;
+                let digits = parts.next()?;
+                match base {
+                    "2" => format!("'b{}", digits),
+                    "8" => format!("'o{}", digits),
+                    "16" => format!("'h{}", digits),
+                    _ => return None,
+                }
+            }
+            VhdlToken::CharLiteral(c) => format!("1'b{}", c.0),
+            VhdlToken::StrLiteral(s) => match is_vector {
+                true => format!("{}'b{}", s.len(), s),
+                false => format!("\"{}\"", s),
+            },
+            VhdlToken::BitStrLiteral(b) => {
+                let b = b.to_string();
+                let (base, digits) = b.split_once('"')?;
+                let digits = digits.trim_end_matches('"');
+                match base.to_ascii_lowercase().as_str() {
+                    "b" => format!("'b{}", digits),
+                    "o" => format!("'o{}", digits),
+                    "x" => format!("'h{}", digits),
+                    _ => return None,
+                }
+            }
+            VhdlToken::Delimiter(d) => match d {
+                Delimiter::Plus
+                | Delimiter::Dash
+                | Delimiter::Star
+                | Delimiter::FwdSlash
+                | Delimiter::DoubleStar
+                | Delimiter::ParenL
+                | Delimiter::ParenR => d.to_string(),
+                _ => return None,
+            },
+            _ => return None,
+        });
+    }
+    match result.is_empty() {
+        true => None,
+        false => Some(result.concat()),
+    }
+}
+
+fn join_vhdl(tokens: &[VhdlToken]) -> String {
+    tokens
+        .iter()
+        .map(|t| t.to_string())
+        .collect::<Vec<String>>()
+        .join("")
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::core::lang::sv::format::SystemVerilogFormat;
+    use crate::core::lang::vhdl::format::VhdlFormat;
+
+    const VERILOG_CODE: &str = r#"
+module fifo #(
+    parameter WIDTH = 8,
+    parameter real GAIN = 1.5,
+    parameter[3:0] INIT = 4'hA
+) (
+    input wire clk,
+    input wire[WIDTH-1:0] data_in,
+    output reg signed[0:7] level,
+    inout integer count
+);
+endmodule
+"#;
+
+    const VHDL_CODE: &str = r#"
+entity fifo is
+    generic (
+        WIDTH : positive := 8;
+        SIM   : boolean := false;
+        INIT  : std_logic_vector(3 downto 0) := x"A"
+    );
+    port (
+        clk     : in  std_logic;
+        data_in : in  std_logic_vector(WIDTH-1 downto 0);
+        level   : out ieee.numeric_std.signed(0 to 7);
+        count   : buffer natural;
+        bus_i   : inout bus_t
+    );
+end entity;
+"#;
+
+    /// Removes the escape sequences that color the text `s` in a terminal.
+    fn strip_colors(s: &str) -> String {
+        let mut result = String::with_capacity(s.len());
+        let mut chars = s.chars();
+        while let Some(c) = chars.next() {
+            match c {
+                // skip the sequence up to and including its final letter
+                '\x1b' => {
+                    chars.find(|c| c.is_ascii_alphabetic());
+                }
+                _ => result.push(c),
+            }
+        }
+        result
+    }
+
+    #[test]
+    fn verilog_module_to_vhdl_component() {
+        let module = VerilogParser::read(VERILOG_CODE, &[], &mut preprocess::no_includes)
+            .unwrap()
+            .into_symbols()
+            .remove(0);
+        let entity = into_vhdl_entity(module.as_module().unwrap()).unwrap();
+        assert_eq!(
+            strip_colors(&entity.into_component(&VhdlFormat::new())),
+            r#"component fifo
+  generic(
+    WIDTH : integer := 8;
+    GAIN  : real := 1.5;
+    INIT  : std_logic_vector(3 downto 0) := x"A"
+  );
+  port(
+    clk     : in std_logic;
+    data_in : in std_logic_vector(WIDTH-1 downto 0);
+    level   : out signed(0 to 7);
+    count   : inout integer
+  );
+end component;
+"#
+        );
+    }
+
+    #[test]
+    fn vhdl_entity_to_systemverilog_module() {
+        let entity = match VHDLParser::read(VHDL_CODE)
+            .unwrap()
+            .into_symbols()
+            .remove(0)
+        {
+            VhdlSymbol::Entity(e) => e,
+            _ => panic!("expecting an entity"),
+        };
+        let module = into_module(&entity, &Lang::SystemVerilog).unwrap();
+        assert_eq!(
+            module.into_declaration(&SystemVerilogFormat::new()),
+            r#"module fifo #(
+  parameter int WIDTH = 8,
+  parameter bit SIM = 0,
+  parameter logic[3:0] INIT = 'hA
+) (
+  input logic clk,
+  input logic[WIDTH-1:0] data_in,
+  output logic signed[0:7] level,
+  output int count,
+  inout bus_t bus_i
+);"#
+        );
+
+        let module = into_module(&entity, &Lang::Verilog).unwrap();
+        assert_eq!(
+            module.into_declaration(&SystemVerilogFormat::new()),
+            r#"module fifo #(
+  parameter integer WIDTH = 8,
+  parameter SIM = 0,
+  parameter[3:0] INIT = 'hA
+) (
+  input wire clk,
+  input wire[WIDTH-1:0] data_in,
+  output wire signed[0:7] level,
+  output integer count,
+  inout bus_t bus_i
+);"#
+        );
+    }
+}
@@ -62,4 +62,8 @@ pub enum VerilogError {
     UnhandledAssignInDecl,
     #[error("expecting identifier for the module's name")]
     ModuleNameIsNotIdentifier,
+    #[error("failed to include \"{0}\": {1}")]
+    IncludeFailed(String, String),
+    #[error("failed to include \"{0}\": headers are nested more than {1} levels deep")]
+    IncludeTooDeep(String, usize),
 }
@@ -22,7 +22,7 @@ use super::super::sv::token::{
 };
 use serde_derive::Serialize;
 
-#[derive(Debug, PartialEq)]
+#[derive(Debug, PartialEq, Clone)]
 pub struct Expr(Option<Vec<SystemVerilogToken>>);
 
 impl serde::Serialize for Expr {
@@ -275,7 +275,7 @@ fn default_mode() -> Keyword {
     Keyword::Input
 }
 
-#[derive(Debug, PartialEq)]
+#[derive(Debug, PartialEq, Clone)]
 pub struct DataType {
     net: Option<Keyword>,
     is_signed: bool,
@@ -330,7 +330,7 @@ impl serde::Serialize for DataType {
     }
 }
 
-#[derive(Debug, PartialEq, Serialize)]
+#[derive(Debug, PartialEq, Clone, Serialize)]
 pub struct Port {
     #[serde(skip_serializing)]
     is_param: bool,
@@ -677,6 +677,27 @@ impl Port {
         &self.name
     }
 
+    pub fn get_mode(&self) -> Option<&Keyword> {
+        self.mode.as_ref()
+    }
+
+    pub fn get_data_type(&self) -> Option<&SystemVerilogToken> {
+        self.data_type.data.as_ref()
+    }
+
+    pub fn is_signed(&self) -> bool {
+        self.data_type.is_signed
+    }
+
+    /// Returns the packed dimensions of the port's data type.
+    pub fn get_range(&self) -> Option<&Vec<SystemVerilogToken>> {
+        self.data_type.range.0.as_ref()
+    }
+
+    pub fn get_default(&self) -> Option<&Vec<SystemVerilogToken>> {
+        self.value.0.as_ref()
+    }
+
     pub fn as_user_defined_data_type(&self) -> Option<&Identifier> {
         match &self.data_type.data {
             Some(t) => match t.as_identifier() {
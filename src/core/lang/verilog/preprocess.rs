@@ -0,0 +1,802 @@
+//
+//  Copyright (C) 2022-2025  Chase Ruskin
+//
+//  This program is free software: you can redistribute it and/or modify
+//  it under the terms of the GNU General Public License as published by
+//  the Free Software Foundation, either version 3 of the License, or
+//  (at your option) any later version.
+//
+//  This program is distributed in the hope that it will be useful,
+//  but WITHOUT ANY WARRANTY; without even the implied warranty of
+//  MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+//  GNU General Public License for more details.
+//
+//  You should have received a copy of the GNU General Public License
+//  along with this program.  If not, see <http://www.gnu.org/licenses/>.
+//
+
+//! Resolves the conditional compilation and file inclusion directives of
+//! Verilog and SystemVerilog source code.
+
+use super::error::VerilogError;
+use super::token::identifier::Identifier;
+use super::token::keyword::Keyword;
+use super::token::token::VerilogToken;
+use super::token::tokenizer::VerilogTokenizer;
+use crate::core::lang::lexer::Token;
+use crate::core::lang::sv::token::keyword::Keyword as SvKeyword;
+use crate::core::lang::sv::token::token::SystemVerilogToken;
+use crate::core::lang::sv::token::tokenizer::SystemVerilogTokenizer;
+use crate::util::anyerror::AnyError;
+use serde_derive::{Deserialize, Serialize};
+use std::collections::HashSet;
+use std::fmt::Display;
+use std::str::FromStr;
+
+/// A text macro defined before any source code is read, written as `NAME` or
+/// `NAME=VALUE`.
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
+#[serde(try_from = "String", into = "String")]
+pub struct Define {
+    name: String,
+    value: Option<String>,
+}
+
+impl Define {
+    pub fn get_name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn get_value(&self) -> Option<&String> {
+        self.value.as_ref()
+    }
+}
+
+impl FromStr for Define {
+    type Err = AnyError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (name, value) = match s.split_once('=') {
+            Some((n, v)) => (n.trim(), Some(v.trim().to_string())),
+            None => (s.trim(), None),
+        };
+        let mut chars = name.chars();
+        let is_valid = match chars.next() {
+            Some(c) => {
+                (c.is_ascii_alphabetic() || c == '_')
+                    && chars.all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '$')
+            }
+            None => false,
+        };
+        match is_valid {
+            true => Ok(Self {
+                name: name.to_string(),
+                value: value,
+            }),
+            false => Err(AnyError(format!(
+                "invalid macro name \"{}\"; expecting NAME or NAME=VALUE",
+                name
+            ))),
+        }
+    }
+}
+
+impl TryFrom<String> for Define {
+    type Error = AnyError;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        Self::from_str(&value)
+    }
+}
+
+impl From<Define> for String {
+    fn from(value: Define) -> Self {
+        value.to_string()
+    }
+}
+
+impl Display for Define {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match &self.value {
+            Some(v) => write!(f, "{}={}", self.name, v),
+            None => write!(f, "{}", self.name),
+        }
+    }
+}
+
+/// Merges the `overrides` into the `defines`, where a macro in `overrides`
+/// replaces a macro of the same name in `defines`.
+pub fn merge_defines(defines: &[Define], overrides: &[Define]) -> Vec<Define> {
+    let mut result: Vec<Define> = defines
+        .iter()
+        .filter(|d| overrides.iter().find(|o| o.name == d.name).is_none())
+        .cloned()
+        .collect();
+    result.extend(overrides.iter().cloned());
+    result
+}
+
+/// The maximum number of nested header files expanded by include directives.
+pub const MAX_INCLUDE_DEPTH: usize = 32;
+
+/// Finds and reads the header file for an include directive.
+///
+/// The arguments are the header file that holds the directive (`None` for the
+/// file being read) and the path written in the directive. Returns the header's
+/// file path and source code, or `None` if the header cannot be found.
+pub type IncludeLoader<'a> =
+    dyn FnMut(Option<&str>, &str) -> Result<Option<(String, String)>, String> + 'a;
+
+/// An include loader that never finds any header files.
+pub fn no_includes(_: Option<&str>, _: &str) -> Result<Option<(String, String)>, String> {
+    Ok(None)
+}
+
+/// A token that can pass through the preprocessor.
+pub trait Preprocess: Sized {
+    fn as_directive(&self) -> Option<&str>;
+
+    fn as_name(&self) -> Option<&Identifier>;
+
+    fn as_string_literal(&self) -> Option<&str>;
+
+    fn is_continuation(&self) -> bool;
+
+    /// Checks if the token begins the declaration of a design element.
+    fn opens_unit(&self) -> bool;
+
+    /// Checks if the token ends the declaration of a design element.
+    fn closes_unit(&self) -> bool;
+
+    fn is_end(&self) -> bool;
+
+    /// Splits the source code `s` into tokens, excluding comments.
+    fn tokenize(s: &str) -> Result<Vec<Token<Self>>, VerilogError>;
+}
+
+impl Preprocess for VerilogToken {
+    fn as_directive(&self) -> Option<&str> {
+        match self {
+            Self::Directive(d) => Some(d.as_str()),
+            _ => None,
+        }
+    }
+
+    fn as_name(&self) -> Option<&Identifier> {
+        self.as_identifier()
+    }
+
+    fn as_string_literal(&self) -> Option<&str> {
+        match self {
+            Self::StringLiteral(s) => Some(s.as_str()),
+            _ => None,
+        }
+    }
+
+    fn is_continuation(&self) -> bool {
+        self.is_line_continuation()
+    }
+
+    fn opens_unit(&self) -> bool {
+        matches!(
+            self,
+            Self::Keyword(
+                Keyword::Module | Keyword::Macromodule | Keyword::Primitive | Keyword::Config
+            )
+        )
+    }
+
+    fn closes_unit(&self) -> bool {
+        matches!(
+            self,
+            Self::Keyword(Keyword::Endmodule | Keyword::Endprimitive | Keyword::Endconfig)
+        )
+    }
+
+    fn is_end(&self) -> bool {
+        self.is_eof()
+    }
+
+    fn tokenize(s: &str) -> Result<Vec<Token<Self>>, VerilogError> {
+        Ok(VerilogTokenizer::from_str(s)?
+            .into_tokens()
+            .into_iter()
+            .filter(|t| t.as_type().is_comment() == false)
+            .collect())
+    }
+}
+
+impl Preprocess for SystemVerilogToken {
+    fn as_directive(&self) -> Option<&str> {
+        match self {
+            Self::Directive(d) => Some(d.as_str()),
+            _ => None,
+        }
+    }
+
+    fn as_name(&self) -> Option<&Identifier> {
+        self.as_identifier()
+    }
+
+    fn as_string_literal(&self) -> Option<&str> {
+        match self {
+            Self::StringLiteral(s) => Some(s.as_str()),
+            _ => None,
+        }
+    }
+
+    fn is_continuation(&self) -> bool {
+        self.is_line_continuation()
+    }
+
+    fn opens_unit(&self) -> bool {
+        matches!(
+            self,
+            Self::Keyword(
+                SvKeyword::Module
+                    | SvKeyword::Macromodule
+                    | SvKeyword::Interface
+                    | SvKeyword::Program
+                    | SvKeyword::Package
+                    | SvKeyword::Primitive
+                    | SvKeyword::Checker
+                    | SvKeyword::Config
+            )
+        )
+    }
+
+    fn closes_unit(&self) -> bool {
+        matches!(
+            self,
+            Self::Keyword(
+                SvKeyword::Endmodule
+                    | SvKeyword::Endinterface
+                    | SvKeyword::Endprogram
+                    | SvKeyword::Endpackage
+                    | SvKeyword::Endprimitive
+                    | SvKeyword::Endchecker
+                    | SvKeyword::Endconfig
+            )
+        )
+    }
+
+    fn is_end(&self) -> bool {
+        self.is_eof()
+    }
+
+    fn tokenize(s: &str) -> Result<Vec<Token<Self>>, VerilogError> {
+        Ok(SystemVerilogTokenizer::from_str(s)?
+            .into_tokens()
+            .into_iter()
+            .filter(|t| t.as_type().is_comment() == false)
+            .collect())
+    }
+}
+
+/// A single `ifdef`/`ifndef` block.
+struct Branch {
+    /// The enclosing block is active.
+    parent: bool,
+    /// One of the block's branches has already been active.
+    taken: bool,
+    /// The current branch is active.
+    active: bool,
+}
+
+/// The source code tokens after being preprocessed.
+#[derive(Debug, PartialEq)]
+pub struct Preprocessed<T> {
+    tokens: Vec<Token<T>>,
+    includes: Vec<String>,
+    headers: Vec<String>,
+}
+
+impl<T> Preprocessed<T> {
+    pub fn into_tokens(self) -> Vec<Token<T>> {
+        self.tokens
+    }
+
+    /// Returns the paths of the files included within active branches, in order.
+    ///
+    /// Only the include directives written in the source code are listed, not
+    /// the ones within the headers they include.
+    pub fn get_includes(&self) -> &Vec<String> {
+        &self.includes
+    }
+
+    /// Returns the file paths of every header that was expanded, including
+    /// nested headers.
+    pub fn get_headers(&self) -> &Vec<String> {
+        &self.headers
+    }
+}
+
+/// The state shared between a file and the headers it includes.
+struct Preprocessor<'a, 'b> {
+    macros: HashSet<String>,
+    /// Every branch of an `ifdef`/`ifndef` block is active.
+    every_branch: bool,
+    headers: Vec<String>,
+    load: &'a mut IncludeLoader<'b>,
+}
+
+/// Evaluates the conditional compilation directives in `tokens` with the macros
+/// from `defines`.
+///
+/// Tokens within inactive branches are removed along with the `define`, `undef`,
+/// `ifdef`, `ifndef`, `elsif`, `else`, `endif`, and `include` directives. All
+/// other directives are kept.
+///
+/// The header of an active `include` directive is found with `load` and expanded
+/// in place, sharing its macros with the including file. A header included within
+/// a design element has its tokens inserted into the element. A header included
+/// outside of any design element only contributes its macros, since the design
+/// elements it declares belong to the header file itself.
+pub fn preprocess<T: Preprocess>(
+    tokens: Vec<Token<T>>,
+    defines: &[Define],
+    load: &mut IncludeLoader,
+) -> Result<Preprocessed<T>, VerilogError> {
+    let mut state = Preprocessor {
+        macros: defines.iter().map(|d| d.name.clone()).collect(),
+        every_branch: false,
+        headers: Vec::new(),
+        load: load,
+    };
+    state.run(tokens)
+}
+
+/// Removes the conditional compilation directives in `tokens` while keeping the
+/// tokens within every branch, so the design elements declared in any branch
+/// can be found without knowing which macros are defined.
+///
+/// Include directives are not expanded.
+pub fn preprocess_every_branch<T: Preprocess>(
+    tokens: Vec<Token<T>>,
+) -> Result<Preprocessed<T>, VerilogError> {
+    let mut state = Preprocessor {
+        macros: HashSet::new(),
+        every_branch: true,
+        headers: Vec::new(),
+        load: &mut no_includes,
+    };
+    state.run(tokens)
+}
+
+impl<'a, 'b> Preprocessor<'a, 'b> {
+    /// Preprocesses the `tokens` of the file being read.
+    fn run<T: Preprocess>(
+        &mut self,
+        tokens: Vec<Token<T>>,
+    ) -> Result<Preprocessed<T>, VerilogError> {
+        let mut result = Vec::with_capacity(tokens.len());
+        let includes = self.expand(tokens, None, 0, false, &mut result)?;
+        Ok(Preprocessed {
+            tokens: result,
+            includes: includes,
+            headers: std::mem::take(&mut self.headers),
+        })
+    }
+
+    /// Writes the active `tokens` of the file at `path` into `result`, and returns
+    /// the paths of the include directives found in active branches.
+    ///
+    /// The tokens of the file are only written when `within_unit` is set or the
+    /// file is at `depth` 0.
+    fn expand<T: Preprocess>(
+        &mut self,
+        tokens: Vec<Token<T>>,
+        path: Option<&str>,
+        depth: usize,
+        within_unit: bool,
+        result: &mut Vec<Token<T>>,
+    ) -> Result<Vec<String>, VerilogError> {
+        let mut branches: Vec<Branch> = Vec::new();
+        let mut includes = Vec::new();
+        let mut within_unit = within_unit;
+        let keep = depth == 0 || within_unit == true;
+
+        let mut tokens = tokens.into_iter().peekable();
+        while let Some(t) = tokens.next() {
+            let active = branches.last().is_none_or(|b| b.active);
+            let directive = match t.as_type().as_directive() {
+                Some(d) => d.to_string(),
+                None => {
+                    if active == true {
+                        if t.as_type().opens_unit() == true {
+                            within_unit = true;
+                        } else if t.as_type().closes_unit() == true {
+                            within_unit = false;
+                        }
+                    }
+                    // keep the end of the file being read
+                    let is_end = t.as_type().is_end();
+                    if keep == true
+                        && ((active == true && is_end == false) || (is_end == true && depth == 0))
+                    {
+                        result.push(t);
+                    }
+                    continue;
+                }
+            };
+            let line = t.locate().line();
+            match directive.as_str() {
+                "ifdef" | "ifndef" => {
+                    let name = take_name(&mut tokens, line);
+                    let is_defined = name.is_some_and(|n| self.macros.contains(&n));
+                    let on = active && (self.every_branch || is_defined == (directive == "ifdef"));
+                    branches.push(Branch {
+                        parent: active,
+                        taken: on,
+                        active: on,
+                    });
+                }
+                "elsif" => {
+                    let name = take_name(&mut tokens, line);
+                    if let Some(b) = branches.last_mut() {
+                        let on = b.parent
+                            && (self.every_branch
+                                || (b.taken == false
+                                    && name.is_some_and(|n| self.macros.contains(&n))));
+                        b.active = on;
+                        b.taken = b.taken || on;
+                    }
+                }
+                "else" => {
+                    if let Some(b) = branches.last_mut() {
+                        b.active = b.parent && (self.every_branch || b.taken == false);
+                        b.taken = true;
+                    }
+                }
+                "endif" => {
+                    branches.pop();
+                }
+                "define" => {
+                    let name = take_name(&mut tokens, line);
+                    skip_line(&mut tokens, line);
+                    if let Some(n) = name {
+                        if active == true {
+                            self.macros.insert(n);
+                        }
+                    }
+                }
+                "undef" => {
+                    let name = take_name(&mut tokens, line);
+                    if let Some(n) = name {
+                        if active == true {
+                            self.macros.remove(&n);
+                        }
+                    }
+                }
+                "undefineall" => {
+                    if active == true {
+                        self.macros.clear();
+                    }
+                }
+                "include" => {
+                    let include = match tokens.peek() {
+                        Some(p) if p.locate().line() == line => {
+                            p.as_type().as_string_literal().map(|s| s.to_string())
+                        }
+                        _ => None,
+                    };
+                    if let Some(p) = include {
+                        tokens.next();
+                        if active == true {
+                            self.include(&p, path, depth, within_unit && keep, result)?;
+                            includes.push(p);
+                        }
+                    }
+                }
+                _ => {
+                    if active == true && keep == true {
+                        result.push(t);
+                    }
+                }
+            }
+        }
+        Ok(includes)
+    }
+
+    /// Expands the header referenced by the `include` directive within the file
+    /// at `path`.
+    fn include<T: Preprocess>(
+        &mut self,
+        include: &str,
+        path: Option<&str>,
+        depth: usize,
+        within_unit: bool,
+        result: &mut Vec<Token<T>>,
+    ) -> Result<(), VerilogError> {
+        if depth >= MAX_INCLUDE_DEPTH {
+            return Err(VerilogError::IncludeTooDeep(
+                include.to_string(),
+                MAX_INCLUDE_DEPTH,
+            ));
+        }
+        let (header, contents) = match (self.load)(path, include) {
+            Ok(Some(h)) => h,
+            Ok(None) => return Ok(()),
+            Err(e) => return Err(VerilogError::IncludeFailed(include.to_string(), e)),
+        };
+        let tokens = T::tokenize(&contents)
+            .map_err(|e| VerilogError::IncludeFailed(include.to_string(), e.to_string()))?;
+        if self.headers.contains(&header) == false {
+            self.headers.push(header.clone());
+        }
+        self.expand(tokens, Some(&header), depth + 1, within_unit, result)?;
+        Ok(())
+    }
+}
+
+/// Consumes the identifier following a directive if it is on the same `line`.
+fn take_name<T: Preprocess, I>(tokens: &mut std::iter::Peekable<I>, line: usize) -> Option<String>
+where
+    I: Iterator<Item = Token<T>>,
+{
+    let name = match tokens.peek() {
+        Some(t) if t.locate().line() == line => t.as_type().as_name().map(|n| n.to_string()),
+        _ => None,
+    };
+    if name.is_some() {
+        tokens.next();
+    }
+    name
+}
+
+/// Consumes the remaining tokens on the `line`, including any lines joined by a
+/// trailing backslash.
+fn skip_line<T: Preprocess, I>(tokens: &mut std::iter::Peekable<I>, line: usize)
+where
+    I: Iterator<Item = Token<T>>,
+{
+    let mut line = line;
+    let mut continued = false;
+    while let Some(t) = tokens.peek() {
+        if t.as_type().is_end() == true || (t.locate().line() > line && continued == false) {
+            break;
+        }
+        let t = tokens.next().unwrap();
+        line = t.locate().line();
+        continued = t.as_type().is_continuation();
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::core::lang::verilog::symbols::VerilogParser;
+
+    const CODE: &str = r#"
+`define USE_FIFO
+module top;
+`ifdef USE_FIFO
+    fifo u0 (.clk(clk));
+`else
+    reg_file u1 (.clk(clk));
+`endif
+`ifndef SIM
+    pll u2 (.clk(clk));
+`elsif FAST
+    fast_model u3 (.clk(clk));
+`else
+    slow_model u4 (.clk(clk));
+`endif
+`include "defs.vh"
+endmodule
+"#;
+
+    fn instances(defines: &[Define]) -> (Vec<String>, Vec<String>) {
+        let parser = VerilogParser::read(CODE, defines, &mut no_includes).unwrap();
+        let includes = parser.get_includes().clone();
+        let symbols = parser.into_symbols();
+        let mut deps: Vec<String> = symbols
+            .first()
+            .unwrap()
+            .as_module()
+            .unwrap()
+            .get_edge_list_entities()
+            .into_iter()
+            .map(|d| d.to_string())
+            .collect();
+        deps.sort();
+        (deps, includes)
+    }
+
+    #[test]
+    fn define_from_str() {
+        assert_eq!(
+            Define::from_str("WIDTH=8").unwrap(),
+            Define {
+                name: String::from("WIDTH"),
+                value: Some(String::from("8"))
+            }
+        );
+        assert_eq!(Define::from_str("SIM").unwrap().get_value(), None);
+        assert_eq!(Define::from_str("8BIT").is_err(), true);
+        assert_eq!(Define::from_str("=1").is_err(), true);
+    }
+
+    #[test]
+    fn merge() {
+        let defines = vec![
+            Define::from_str("A=1").unwrap(),
+            Define::from_str("B").unwrap(),
+        ];
+        let overrides = vec![Define::from_str("A=2").unwrap()];
+        assert_eq!(
+            merge_defines(&defines, &overrides)
+                .iter()
+                .map(|d| d.to_string())
+                .collect::<Vec<String>>(),
+            vec!["B", "A=2"]
+        );
+    }
+
+    #[test]
+    fn drops_inactive_branches() {
+        let (deps, includes) = instances(&[]);
+        assert_eq!(deps, vec!["fifo", "pll"]);
+        assert_eq!(includes, vec!["defs.vh"]);
+
+        let (deps, _) = instances(&[Define::from_str("SIM").unwrap()]);
+        assert_eq!(deps, vec!["fifo", "slow_model"]);
+
+        let (deps, _) = instances(&[
+            Define::from_str("SIM").unwrap(),
+            Define::from_str("FAST=1").unwrap(),
+        ]);
+        assert_eq!(deps, vec!["fast_model", "fifo"]);
+    }
+
+    #[test]
+    fn keeps_every_branch() {
+        let parser = VerilogParser::read_every_branch(CODE).unwrap();
+        assert_eq!(parser.get_includes(), &vec![String::from("defs.vh")]);
+        let mut deps = deps_of(parser);
+        deps.sort();
+        assert_eq!(
+            deps,
+            vec!["fast_model", "fifo", "pll", "reg_file", "slow_model"]
+        );
+    }
+
+    #[test]
+    fn nested_branches() {
+        let code = r#"
+module top;
+`ifdef A
+  `ifdef B
+    ab u0 (.clk(clk));
+  `else
+    a_only u1 (.clk(clk));
+  `endif
+  `include "a.vh"
+`else
+  `undef B
+  `ifdef B
+    never u2 (.clk(clk));
+  `endif
+  none u3 (.clk(clk));
+`endif
+endmodule
+"#;
+        let parser =
+            VerilogParser::read(code, &[Define::from_str("B").unwrap()], &mut no_includes).unwrap();
+        assert_eq!(parser.get_includes().is_empty(), true);
+        let symbols = parser.into_symbols();
+        let deps: Vec<String> = symbols
+            .first()
+            .unwrap()
+            .as_module()
+            .unwrap()
+            .get_edge_list_entities()
+            .into_iter()
+            .map(|d| d.to_string())
+            .collect();
+        assert_eq!(deps, vec!["none"]);
+    }
+
+    fn deps_of(parser: VerilogParser) -> Vec<String> {
+        parser
+            .into_symbols()
+            .first()
+            .unwrap()
+            .as_module()
+            .unwrap()
+            .get_edge_list_entities()
+            .into_iter()
+            .map(|d| d.to_string())
+            .collect()
+    }
+
+    #[test]
+    fn expands_header_macros() {
+        let code = r#"
+`include "cfg.vh"
+module top;
+`ifdef USE_FIFO
+    fifo u0 (.clk(clk));
+`else
+    reg_file u1 (.clk(clk));
+`endif
+endmodule
+"#;
+        // units declared in a header outside of a design element are not repeated
+        let header = "`define USE_FIFO\nmodule helper; endmodule\n";
+        let mut load = |from: Option<&str>, path: &str| {
+            assert_eq!(from, None);
+            assert_eq!(path, "cfg.vh");
+            Ok(Some((String::from("/ip/cfg.vh"), String::from(header))))
+        };
+        let parser = VerilogParser::read(code, &[], &mut load).unwrap();
+        assert_eq!(parser.get_includes(), &vec![String::from("cfg.vh")]);
+        assert_eq!(parser.get_headers(), &vec![String::from("/ip/cfg.vh")]);
+        let symbols = parser.into_symbols();
+        assert_eq!(symbols.len(), 1);
+        assert_eq!(
+            symbols[0]
+                .as_module()
+                .unwrap()
+                .get_edge_list_entities()
+                .iter()
+                .map(|d| d.to_string())
+                .collect::<Vec<String>>(),
+            vec!["fifo"]
+        );
+    }
+
+    #[test]
+    fn splices_header_within_module() {
+        let code = r#"
+module top;
+`include "inst.vh"
+endmodule
+"#;
+        let mut load = |from: Option<&str>, path: &str| match (from, path) {
+            (None, "inst.vh") => Ok(Some((
+                String::from("/ip/inst.vh"),
+                String::from("`include \"pll.vh\"\n"),
+            ))),
+            (Some("/ip/inst.vh"), "pll.vh") => Ok(Some((
+                String::from("/ip/pll.vh"),
+                String::from("pll u2 (.clk(clk));\n"),
+            ))),
+            _ => Ok(None),
+        };
+        let parser = VerilogParser::read(code, &[], &mut load).unwrap();
+        assert_eq!(
+            parser.get_headers(),
+            &vec![String::from("/ip/inst.vh"), String::from("/ip/pll.vh")]
+        );
+        assert_eq!(deps_of(parser), vec!["pll"]);
+    }
+
+    #[test]
+    fn limits_include_depth() {
+        let code = "`include \"loop.vh\"\nmodule top; endmodule\n";
+        let mut load = |_: Option<&str>, _: &str| {
+            Ok(Some((
+                String::from("/ip/loop.vh"),
+                String::from("`include \"loop.vh\"\n"),
+            )))
+        };
+        assert!(matches!(
+            VerilogParser::read(code, &[], &mut load),
+            Err(VerilogError::IncludeTooDeep(_, MAX_INCLUDE_DEPTH))
+        ));
+
+        let mut load = |_: Option<&str>, _: &str| Err(String::from("not readable"));
+        assert!(matches!(
+            VerilogParser::read(code, &[], &mut load),
+            Err(VerilogError::IncludeFailed(_, _))
+        ));
+    }
+
+    #[test]
+    fn skips_continued_lines() {
+        let code = "module top;\n`define INST fifo \\\n    u9 (.clk(clk));\n    reg_file u1 (.clk(clk));\nendmodule\n";
+        let parser = VerilogParser::read(code, &[], &mut no_includes).unwrap();
+        assert_eq!(deps_of(parser), vec!["reg_file"]);
+    }
+}
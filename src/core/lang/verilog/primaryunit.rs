@@ -15,12 +15,10 @@
 //  along with this program.  If not, see <http://www.gnu.org/licenses/>.
 //
 
+use super::preprocess;
 use super::{symbols::VerilogSymbol, token::identifier::Identifier};
-use crate::core::lang;
-use crate::core::lang::vhdl::primaryunit::HdlNamingError;
 use crate::{core::lang::verilog::symbols::VerilogParser, util::anyerror::CodeFault};
 use std::collections::HashMap;
-use std::path::PathBuf;
 use std::str::FromStr;
 
 #[derive(PartialEq, Hash, Eq, Debug)]
@@ -51,12 +49,22 @@ impl PrimaryUnit {
 
     /// Deserializes the data from a toml inline table.
     pub fn from_toml(tbl: &toml_edit::InlineTable) -> Option<Self> {
+        Self::from_parts(
+            tbl.get("identifier")?.as_str()?,
+            tbl.get("type")?.as_str()?,
+            "",
+        )
+    }
+
+    /// Creates a primary unit without a symbol from its `identifier`, `shape`, and
+    /// `source` file.
+    pub fn from_parts(identifier: &str, shape: &str, source: &str) -> Option<Self> {
         let unit = Unit {
-            name: Identifier::from_str(tbl.get("identifier")?.as_str()?).unwrap(),
+            name: Identifier::from_str(identifier).ok()?,
             symbol: None,
-            source: String::new(),
+            source: source.to_string(),
         };
-        let shape = match tbl.get("type")?.as_str()? {
+        let shape = match shape {
             "module" => PrimaryShape::Module,
             "config" => PrimaryShape::Config,
             "primitive" => PrimaryShape::Primitive,
@@ -127,15 +135,18 @@ impl PartialEq for Unit {
 
 impl Eq for Unit {}
 
-fn analyze(source_file: &str) -> Result<HashMap<Identifier, PrimaryUnit>, CodeFault> {
-    if crate::core::fileset::is_verilog(&source_file) == false {
-        return Ok(HashMap::new());
-    }
-
-    // println!("parse verilog: {:?}", source_file);
-    // parse text into Verilog symbols
-    let contents = lang::read_to_string(&source_file)?;
-    let symbols = match VerilogParser::read(&contents) {
+/// Parses the `contents` of the `source_file` into its primary design units.
+///
+/// Units declared within any branch of a conditional compilation directive are
+/// found. If the code from every branch cannot be parsed together, only the
+/// branches active without any macros defined are read.
+pub fn parse_units(
+    source_file: &str,
+    contents: &str,
+) -> Result<HashMap<Identifier, PrimaryUnit>, CodeFault> {
+    let parsed = VerilogParser::read_every_branch(contents)
+        .or_else(|_| VerilogParser::read(contents, &[], &mut preprocess::no_includes));
+    let symbols = match parsed {
         Ok(s) => s.into_symbols(),
         Err(e) => Err(CodeFault(Some(source_file.to_string()), Box::new(e)))?,
     };
@@ -185,39 +196,3 @@ fn analyze(source_file: &str) -> Result<HashMap<Identifier, PrimaryUnit>, CodeFa
         .collect();
     Ok(units)
 }
-
-pub fn collect_units(files: &Vec<String>) -> Result<HashMap<Identifier, PrimaryUnit>, CodeFault> {
-    let mut all_results: HashMap<Identifier, PrimaryUnit> = HashMap::new();
-    // iterate through all source files
-    let divided_results: Result<Vec<_>, _> = files
-        .iter()
-        .map(|source_file| analyze(source_file))
-        .collect();
-
-    for pri_unit in divided_results? {
-        for (_key, primary) in pri_unit {
-            let pri_src = PathBuf::from(primary.get_unit().get_source_file());
-            let pri_pos = primary
-                .get_unit()
-                .get_symbol()
-                .as_ref()
-                .unwrap()
-                .get_position()
-                .clone();
-            // push to the global list (ensure there are zero duplicate names)
-            if let Some(dupe) = all_results.insert(primary.get_name().clone(), primary) {
-                return Err(CodeFault(
-                    None,
-                    Box::new(HdlNamingError::DuplicateIdentifier(
-                        dupe.get_name().to_string(),
-                        PathBuf::from(dupe.get_unit().get_source_file()),
-                        dupe.get_unit().get_symbol().unwrap().get_position().clone(),
-                        pri_src,
-                        pri_pos,
-                    )),
-                ))?;
-            }
-        }
-    }
-    Ok(all_results)
-}
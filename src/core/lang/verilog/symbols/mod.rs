@@ -29,6 +29,7 @@ use crate::core::lang::sv::symbols::SystemVerilogSymbol;
 use crate::core::lang::sv::token::keyword::Keyword;
 use crate::core::lang::sv::token::token::SystemVerilogToken;
 use crate::core::lang::verilog::interface::{Port, PortList};
+use crate::core::lang::verilog::preprocess::{self, Define, IncludeLoader};
 use crate::core::lang::verilog::token::token::VerilogToken;
 use std::str::FromStr;
 
@@ -101,6 +102,8 @@ impl VerilogSymbol {
 #[derive(Debug, PartialEq)]
 pub struct VerilogParser {
     symbols: Vec<Symbol<VerilogSymbol>>,
+    includes: Vec<String>,
+    headers: Vec<String>,
 }
 
 impl VerilogParser {
@@ -112,21 +115,68 @@ impl VerilogParser {
                 .into_iter()
                 .filter_map(|f| if f.is_ok() { Some(f.unwrap()) } else { None })
                 .collect(),
+            includes: Vec::new(),
+            headers: Vec::new(),
         }
     }
 
     /// Reports an error if one is discovered in the list of symbols or in the tokenizing.
-    pub fn read(s: &str) -> Result<Self, VerilogError> {
-        let symbols = VerilogParser::parse(
-            VerilogTokenizer::from_str(&s)?
-                .into_tokens()
-                .into_iter()
-                .filter(|s| s.as_type().is_comment() == false)
-                .collect(),
-        );
+    ///
+    /// The source code is preprocessed with the macros from `defines` before
+    /// being parsed, where included headers are found with `load`.
+    pub fn read(
+        s: &str,
+        defines: &[Define],
+        load: &mut IncludeLoader,
+    ) -> Result<Self, VerilogError> {
+        let tokens = VerilogTokenizer::from_str(&s)?
+            .into_tokens()
+            .into_iter()
+            .filter(|s| s.as_type().is_comment() == false)
+            .collect();
+        let code = preprocess::preprocess(tokens, defines, load)?;
+        let includes = code.get_includes().clone();
+        let headers = code.get_headers().clone();
+        let symbols = VerilogParser::parse(code.into_tokens());
         let result: Result<Vec<Symbol<VerilogSymbol>>, VerilogError> =
             symbols.into_iter().collect();
-        Ok(Self { symbols: result? })
+        Ok(Self {
+            symbols: result?,
+            includes: includes,
+            headers: headers,
+        })
+    }
+
+    /// Reports an error if one is discovered in the list of symbols or in the tokenizing.
+    ///
+    /// The source code within every branch of a conditional compilation directive
+    /// is parsed, and included headers are not expanded.
+    pub fn read_every_branch(s: &str) -> Result<Self, VerilogError> {
+        let tokens = VerilogTokenizer::from_str(s)?
+            .into_tokens()
+            .into_iter()
+            .filter(|s| s.as_type().is_comment() == false)
+            .collect();
+        let code = preprocess::preprocess_every_branch(tokens)?;
+        let includes = code.get_includes().clone();
+        let symbols = VerilogParser::parse(code.into_tokens());
+        let result: Result<Vec<Symbol<VerilogSymbol>>, VerilogError> =
+            symbols.into_iter().collect();
+        Ok(Self {
+            symbols: result?,
+            includes: includes,
+            headers: Vec::new(),
+        })
+    }
+
+    /// Returns the paths of the files included by the source code.
+    pub fn get_includes(&self) -> &Vec<String> {
+        &self.includes
+    }
+
+    /// Returns the file paths of the headers that were expanded into the source code.
+    pub fn get_headers(&self) -> &Vec<String> {
+        &self.headers
     }
 
     pub fn into_symbols(self) -> Vec<VerilogSymbol> {
@@ -426,12 +476,12 @@ impl VerilogSymbol {
         tokens: &mut Peekable<I>,
         mut params: &mut ParamList,
         mut ports: &mut PortList,
-    ) -> Result<(RefSet, RefSet), VerilogError>
+    ) -> Result<(RefSet, Vec<CompoundIdentifier>), VerilogError>
     where
         I: Iterator<Item = Token<SystemVerilogToken>>,
     {
         let mut refs = RefSet::new();
-        let mut deps = RefSet::new();
+        let mut deps = Vec::new();
 
         while let Some(t) = tokens.next() {
             // expecting `endmodule`
@@ -544,8 +594,7 @@ impl VerilogSymbol {
 
         while let Some(t_next) = tokens.peek() {
             if t_next.locate().line() > next_line
-                && stmt.last().unwrap().as_type()
-                    != &SystemVerilogToken::Identifier(Identifier::Escaped(String::new()))
+                && stmt.last().unwrap().as_type().is_line_continuation() == false
             {
                 break;
             } else {
@@ -562,7 +611,7 @@ impl VerilogSymbol {
         params: Option<&mut ParamList>,
         ports: Option<&mut PortList>,
         refs: &mut RefSet,
-        deps: Option<&mut RefSet>,
+        deps: Option<&mut Vec<CompoundIdentifier>>,
     ) -> Result<(), VerilogError> {
         if stmt.is_empty() == true {
             return Ok(());
@@ -589,11 +638,14 @@ impl VerilogSymbol {
         }
 
         // try as a module instantiation
-        if let Some((dep, is_valid_mod)) = Self::as_module_instance(&stmt) {
+        if let Some((dep, is_valid_mod, count)) = Self::as_module_instance(&stmt) {
             // println!("detected dependency! {}", dep);
             if is_valid_mod == true && dep.is_system_name() == false {
                 if let Some(deps) = deps {
-                    deps.insert(CompoundIdentifier::new_minimal_verilog(dep.clone()));
+                    // record the module once for every instance in the statement
+                    (0..count).for_each(|_| {
+                        deps.push(CompoundIdentifier::new_minimal_verilog(dep.clone()))
+                    });
                 }
             }
             refs.insert(CompoundIdentifier::new_minimal_verilog(dep.clone()));
@@ -714,8 +766,8 @@ impl VerilogSymbol {
     }
 
     /// Returns the name of the module that is being instantiated in this statement, if
-    /// one exists.
-    fn as_module_instance(stmt: &Statement) -> Option<(&Identifier, bool)> {
+    /// one exists, along with the number of instances declared in the statement.
+    fn as_module_instance(stmt: &Statement) -> Option<(&Identifier, bool, usize)> {
         let mod_name = stmt.first()?.as_ref().as_identifier()?;
         // are there parameters defined
         let mut stmt_iter = stmt.iter().skip(1);
@@ -725,6 +777,7 @@ impl VerilogSymbol {
         let mut came_from_param_token = false;
         let mut has_port_decl = false;
         let mut has_port_body = false;
+        let mut instances = 0;
         while let Some(t) = stmt_iter.next() {
             // println!("{}", t.as_ref().to_string());
             // take the parameters
@@ -736,6 +789,7 @@ impl VerilogSymbol {
                         state = 1;
                     } else if t.as_ref().as_identifier().is_some() {
                         came_from_param_token = false;
+                        instances += 1;
                         state = 1;
                     } else if t.as_ref().check_delimiter(&Operator::Comma) {
                         state = 0;
@@ -798,7 +852,7 @@ impl VerilogSymbol {
             }
         }
         match state >= 0 && counter == 0 {
-            true => Some((mod_name, has_port_body)),
+            true => Some((mod_name, has_port_body, instances.max(1))),
             false => None,
         }
     }
@@ -45,9 +45,9 @@ pub struct Module {
     /// The set of names that were referenced in the entity.
     #[serde(skip_serializing)]
     refs: RefSet,
-    /// The set of references that were identified as components.
+    /// The references that were identified as components, listed once per instance.
     #[serde(skip_serializing)]
-    deps: RefSet,
+    deps: Vec<CompoundIdentifier>,
     #[serde(skip_serializing)]
     pos: Position,
     language: String,
@@ -62,6 +62,18 @@ impl Module {
         &self.pos
     }
 
+    pub fn get_parameters(&self) -> &ParamList {
+        &self.parameters
+    }
+
+    pub fn get_ports(&self) -> &PortList {
+        &self.ports
+    }
+
+    pub fn get_language(&self) -> &str {
+        &self.language
+    }
+
     pub fn get_refs(&self) -> &RefSet {
         &self.refs
     }
@@ -167,7 +179,7 @@ impl Module {
 }
 
 impl Module {
-    pub fn get_deps(&self) -> &RefSet {
+    pub fn get_deps(&self) -> &Vec<CompoundIdentifier> {
         &self.deps
     }
 
@@ -178,8 +190,10 @@ impl Module {
 
 impl Module {
     /// Returns the list of compound identifiers that were parsed from entity instantiations.
+    ///
+    /// An identifier is listed once for every time it is instantiated.
     pub fn get_edge_list_entities(&self) -> Vec<CompoundIdentifier> {
-        let mut list: Vec<CompoundIdentifier> = self.deps.iter().map(|f| f.clone()).collect();
+        let mut list: Vec<CompoundIdentifier> = self.deps.clone();
         list.sort();
         list
     }
@@ -38,6 +38,8 @@ pub enum VerilogToken {
     Keyword(Keyword),
     StringLiteral(String),
     Directive(String),
+    /// A backslash at the end of a line that joins it to the next line.
+    LineContinuation,
     EOF,
 }
 
@@ -54,6 +56,7 @@ impl Display for VerilogToken {
                 Self::Keyword(k) => k.to_string(),
                 Self::StringLiteral(s) => format!("\"{}\"", s.to_string()),
                 Self::Directive(d) => format!("`{}", d.to_string()),
+                Self::LineContinuation => String::from("\\"),
                 Self::EOF => String::new(),
             }
         )
@@ -76,6 +79,10 @@ impl VerilogToken {
         }
     }
 
+    pub fn is_line_continuation(&self) -> bool {
+        matches!(self, VerilogToken::LineContinuation)
+    }
+
     pub fn is_directive(&self) -> bool {
         match self {
             VerilogToken::Directive(_) => true,
@@ -322,11 +329,16 @@ impl VerilogToken {
 
     /// Captures a Verilog escaped identifier introduced with a \ character.
     /// The \ character is assumed to be `c0`. Collects characters until it encounters whitespace.
+    ///
+    /// A \ character followed directly by whitespace is a line continuation.
     pub fn consume_escaped_identifier(
         train: &mut TrainCar<impl Iterator<Item = char>>,
     ) -> Result<Self, VerilogError> {
         let word = Self::consume_value_pattern(train, None, char_set::is_not_whitespace)?;
-        Ok(Self::Identifier(Identifier::Escaped(word)))
+        match word.is_empty() {
+            true => Ok(Self::LineContinuation),
+            false => Ok(Self::Identifier(Identifier::Escaped(word))),
+        }
     }
 
     /// Captures a string literal.
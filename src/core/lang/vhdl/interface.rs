@@ -32,6 +32,19 @@ pub fn library_statement(lib: &Identifier) -> String {
     )
 }
 
+pub fn use_all_statement(lib: &Identifier, pkg: &Identifier) -> String {
+    format!(
+        "{} {}{}{}{}{}{}\n",
+        Keyword::Use.to_color(),
+        color(&lib.to_string(), ENTITY_NAME),
+        Delimiter::Dot.to_color(),
+        color(&pkg.to_string(), ENTITY_NAME),
+        Delimiter::Dot.to_color(),
+        Keyword::All.to_color(),
+        Delimiter::Terminator.to_color()
+    )
+}
+
 #[derive(Debug, PartialEq)]
 enum ColorTone {
     Color(ColoredString),
@@ -289,6 +302,15 @@ pub struct InterfaceDeclaration {
     bus_present: bool,
 }
 
+/// Writes the `tokens` as VHDL code, which is syntax highlighted when `highlight`
+/// is enabled.
+pub fn tokens_to_code(tokens: &Vec<VhdlToken>, highlight: bool) -> String {
+    match highlight {
+        true => tokens_to_string(tokens).to_string(),
+        false => tokens_to_string(tokens).into_all_bland(),
+    }
+}
+
 fn tokens_to_string(tokens: &Vec<VhdlToken>) -> ColorVec {
     let mut result = ColorVec::new();
     // determine which delimiters to not add trailing spaces to
@@ -306,7 +328,7 @@ fn tokens_to_string(tokens: &Vec<VhdlToken>) -> ColorVec {
     };
     // determine which delimiters to not add have whitespace preceed
     let no_preceeding_whitespace = |d: &Delimiter| match d {
-        Delimiter::DoubleStar | Delimiter::Comma => true,
+        Delimiter::DoubleStar | Delimiter::Comma | Delimiter::Terminator => true,
         _ => false,
     };
     // iterate through the tokens
@@ -314,7 +336,17 @@ fn tokens_to_string(tokens: &Vec<VhdlToken>) -> ColorVec {
     while let Some(t) = iter.next() {
         // determine if to add trailing space after the token
         let trailing_space = match t {
+            // separate a closing parenthesis from the words and assignments that follow it
+            VhdlToken::Delimiter(Delimiter::ParenR) => match iter.peek() {
+                Some(VhdlToken::Delimiter(d)) => {
+                    d == &Delimiter::VarAssign || d == &Delimiter::Arrow
+                }
+                Some(_) => true,
+                None => false,
+            },
             VhdlToken::Delimiter(d) => is_spaced_token(d),
+            // separate a keyword from an opening parenthesis
+            VhdlToken::Keyword(_) => true,
             _ => {
                 // make sure the next token is not a tight token (no-spaced)
                 if let Some(m) = iter.peek() {
@@ -342,6 +374,27 @@ fn tokens_to_string(tokens: &Vec<VhdlToken>) -> ColorVec {
 }
 
 impl InterfaceDeclaration {
+    pub fn get_identifier(&self) -> &Identifier {
+        &self.identifier
+    }
+
+    /// Returns the keyword that begins the declaration, such as `signal` or `type`.
+    pub fn get_initial_keyword(&self) -> Option<&Keyword> {
+        self.initial_keyword.as_ref()
+    }
+
+    pub fn get_mode(&self) -> Option<&Keyword> {
+        self.mode.0.as_ref()
+    }
+
+    pub fn get_datatype(&self) -> &Vec<VhdlToken> {
+        &self.datatype.0
+    }
+
+    pub fn get_default(&self) -> Option<&Vec<VhdlToken>> {
+        self.expr.0.as_ref().map(|e| &e.0)
+    }
+
     fn into_interface_string(&self, offset: usize) -> ColorVec {
         let mut result = ColorVec::new();
         // identifier
@@ -452,6 +505,10 @@ impl InterfaceDeclarations {
         self.0.len()
     }
 
+    pub fn iter(&self) -> std::slice::Iter<'_, InterfaceDeclaration> {
+        self.0.iter()
+    }
+
     /// Determines the length of the longest identifier.
     pub fn longest_identifier(&self) -> usize {
         let longest = self
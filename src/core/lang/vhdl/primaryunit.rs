@@ -18,7 +18,6 @@
 use super::super::lexer::Position;
 use super::subunit::SubUnit;
 use super::symbols::VhdlSymbol;
-use crate::core::lang;
 use crate::core::lang::reference::RefSet;
 use crate::core::lang::vhdl::symbols::VHDLParser;
 use crate::core::lang::vhdl::token::identifier::Identifier;
@@ -77,12 +76,22 @@ impl PrimaryUnit {
 
     /// Deserializes the data from a toml inline table.
     pub fn from_toml(tbl: &toml_edit::InlineTable) -> Option<Self> {
+        Self::from_parts(
+            tbl.get("identifier")?.as_str()?,
+            tbl.get("type")?.as_str()?,
+            "",
+        )
+    }
+
+    /// Creates a primary unit without a symbol from its `identifier`, `shape`, and
+    /// `source` file.
+    pub fn from_parts(identifier: &str, shape: &str, source: &str) -> Option<Self> {
         let unit = Unit {
-            name: Identifier::from_str(tbl.get("identifier")?.as_str()?).unwrap(),
+            name: Identifier::from_str(identifier).ok()?,
             symbol: None,
-            source: String::new(),
+            source: source.to_string(),
         };
-        let shape = match tbl.get("type")?.as_str()? {
+        let shape = match shape {
             "entity" => PrimaryShape::Entity,
             "package" => PrimaryShape::Package,
             "context" => PrimaryShape::Context,
@@ -156,13 +165,11 @@ impl Eq for Unit {}
 
 // use rayon::prelude::*;
 
-fn analyze(source_file: &str) -> Result<HashMap<Identifier, PrimaryUnit>, CodeFault> {
-    if crate::core::fileset::is_vhdl(&source_file) == false {
-        return Ok(HashMap::new());
-    }
-    // parse text into VHDL symbols
-    // println!("parsing vhdl: {}", &source_file);
-    let contents = lang::read_to_string(&source_file)?;
+/// Parses the `contents` of the `source_file` into its primary design units.
+pub fn parse_units(
+    source_file: &str,
+    contents: &str,
+) -> Result<HashMap<Identifier, PrimaryUnit>, CodeFault> {
     let symbols = match VHDLParser::read(&contents) {
         Ok(s) => s.into_symbols(),
         Err(e) => Err(CodeFault(Some(source_file.to_string()), Box::new(e)))?,
@@ -223,49 +230,6 @@ fn analyze(source_file: &str) -> Result<HashMap<Identifier, PrimaryUnit>, CodeFa
     Ok(pri_units)
 }
 
-pub fn collect_units(files: &Vec<String>) -> Result<HashMap<Identifier, PrimaryUnit>, CodeFault> {
-    let mut all_result: HashMap<Identifier, PrimaryUnit> = HashMap::new();
-    // iterate through all source files
-    let divided_results: Result<Vec<_>, _> = files
-        .iter()
-        .map(|source_file| analyze(source_file))
-        .collect();
-
-    for pri_unit in divided_results? {
-        for (_key, primary) in pri_unit {
-            let pri_src = PathBuf::from(primary.get_unit().get_source_file());
-            let pri_pos = primary
-                .get_unit()
-                .get_symbol()
-                .as_ref()
-                .unwrap()
-                .get_position()
-                .clone();
-            if let Some(dupe) = all_result.insert(primary.get_name().clone(), primary) {
-                return Err(CodeFault(
-                    None,
-                    Box::new(HdlNamingError::DuplicateIdentifier(
-                        dupe.get_name().to_string(),
-                        PathBuf::from(dupe.get_unit().get_source_file()),
-                        all_result
-                            .get(dupe.get_name())
-                            .unwrap()
-                            .get_unit()
-                            .get_symbol()
-                            .unwrap()
-                            .get_position()
-                            .clone(),
-                        pri_src,
-                        pri_pos,
-                    )),
-                ))?;
-            }
-        }
-    }
-
-    Ok(all_result)
-}
-
 #[derive(Debug)]
 pub enum HdlNamingError {
     DuplicateIdentifier(String, PathBuf, Position, PathBuf, Position),
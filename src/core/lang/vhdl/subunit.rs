@@ -55,10 +55,13 @@ impl SubUnit {
         });
         list.extend(self.get_edge_list_entities());
         list.sort();
+        list.dedup();
         list
     }
 
     /// Returns the list of compound identifiers that were parsed from entity instantiations.
+    ///
+    /// An identifier is listed once for every time it is instantiated.
     pub fn get_edge_list_entities(&self) -> Vec<&CompoundIdentifier> {
         let mut list = match self {
             Self::Architecture(arch) => arch.get_deps().into_iter().collect(),
@@ -16,7 +16,7 @@
 //
 
 use super::{Identifier, Position};
-use crate::core::lang::reference::RefSet;
+use crate::core::lang::reference::{CompoundIdentifier, RefSet};
 use serde_derive::Serialize;
 
 #[derive(Debug, PartialEq, Clone, Serialize)]
@@ -28,7 +28,7 @@ pub struct Architecture {
     #[serde(skip_serializing)]
     refs: RefSet,
     #[serde(skip_serializing)]
-    deps: RefSet,
+    deps: Vec<CompoundIdentifier>,
     #[serde(skip_serializing)]
     pos: Position,
 }
@@ -38,7 +38,7 @@ impl Architecture {
         name: Identifier,
         owner: Identifier,
         refs: RefSet,
-        deps: RefSet,
+        deps: Vec<CompoundIdentifier>,
         pos: Position,
     ) -> Self {
         Self {
@@ -72,7 +72,8 @@ impl Architecture {
         &self.refs
     }
 
-    pub fn get_deps(&self) -> &RefSet {
+    /// Accesses the instantiated units, listed once per instantiation.
+    pub fn get_deps(&self) -> &Vec<CompoundIdentifier> {
         &self.deps
     }
 
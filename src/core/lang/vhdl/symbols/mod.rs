@@ -23,7 +23,7 @@ use std::str::FromStr;
 use self::architecture::Architecture;
 use self::configuration::Configuration;
 use self::entity::Entity;
-use self::package::Package;
+use self::package::{Declaration, Package};
 use self::packagebody::PackageBody;
 
 use super::super::lexer::*;
@@ -32,6 +32,7 @@ use super::super::parser::*;
 use crate::core::lang::reference::{CompoundIdentifier, RefSet};
 use crate::core::lang::vhdl::interface::*;
 use crate::core::lang::vhdl::token::*;
+use crate::core::lang::LangIdentifier;
 
 pub mod architecture;
 pub mod configuration;
@@ -576,6 +577,9 @@ impl VhdlSymbol {
         // println!("{:?}", generics);
 
         // compose the declarative items
+        let mut declarations: Vec<Declaration> = Vec::new();
+        // a declaration that continues until an END statement (such as a record type)
+        let mut multiline: Option<Declaration> = None;
         while let Some(t) = tokens.peek() {
             // check for nested package declarations
             if t.as_type().check_keyword(&Keyword::Package) {
@@ -586,13 +590,34 @@ impl VhdlSymbol {
                 refs.extend(inner_pack.into_package().unwrap().into_refs());
             // grab component declarations
             } else if t.as_type().check_keyword(&Keyword::Component) {
-                let _comp = Self::parse_component(tokens);
-                // println!("component declared: {}", comp);
+                if let Ok(comp) = Self::parse_component(tokens) {
+                    if let LangIdentifier::Vhdl(name) = comp.get_suffix() {
+                        declarations.push(Declaration::component(name.clone()));
+                    }
+                }
             } else if t.as_type().check_keyword(&Keyword::End) {
-                Self::parse_statement(tokens);
-                break;
+                let (stmt, _) = Self::parse_statement(tokens);
+                match multiline.take() {
+                    // finish the multiline declaration
+                    Some(mut decl) => {
+                        decl.extend(&stmt);
+                        declarations.push(decl);
+                    }
+                    None => break,
+                }
             } else {
-                refs.extend(Self::parse_statement(tokens).1);
+                let (stmt, s_refs) = Self::parse_statement(tokens);
+                refs.extend(s_refs);
+                match &mut multiline {
+                    Some(decl) => decl.extend(&stmt),
+                    None => match Declaration::from_statement(&stmt) {
+                        Some(decl) => match decl.is_multiline() {
+                            true => multiline = Some(decl),
+                            false => declarations.push(decl),
+                        },
+                        None => (),
+                    },
+                }
             }
         }
 
@@ -611,7 +636,8 @@ impl VhdlSymbol {
             )
             .generics(Generics(InterfaceDeclarations::from_double_listed_tokens(
                 generics,
-            ))),
+            )))
+            .declarations(declarations),
         ))
     }
 
@@ -1447,12 +1473,14 @@ impl VhdlSymbol {
     /// Parses through a subprogram (procedure or function).
     ///
     /// Returns (`deps`, `refs`).
-    fn parse_subprogram<I>(tokens: &mut Peekable<I>) -> Result<(RefSet, RefSet), VhdlError>
+    fn parse_subprogram<I>(
+        tokens: &mut Peekable<I>,
+    ) -> Result<(RefSet, Vec<CompoundIdentifier>), VhdlError>
     where
         I: Iterator<Item = Token<VhdlToken>>,
     {
         let mut refs = RefSet::new();
-        let mut deps = RefSet::new();
+        let mut deps = Vec::new();
 
         let mut is_return_yet: bool = false;
         while let Some(t) = tokens.peek() {
@@ -1488,7 +1516,7 @@ impl VhdlSymbol {
     fn parse_declaration<I>(
         tokens: &mut Peekable<I>,
         eval_exit: &dyn Fn(&Statement) -> bool,
-    ) -> Result<(RefSet, RefSet), VhdlError>
+    ) -> Result<(RefSet, Vec<CompoundIdentifier>), VhdlError>
     where
         I: Iterator<Item = Token<VhdlToken>>,
     {
@@ -1499,7 +1527,7 @@ impl VhdlSymbol {
             return Err(VhdlError::Vague);
         }
         let mut refs = RefSet::new();
-        let mut deps = RefSet::new();
+        let mut deps = Vec::new();
 
         while let Some(t) = tokens.peek() {
             // println!("dec: {:?}", t);
@@ -1677,18 +1705,20 @@ impl VhdlSymbol {
     /// Builds statements and stops after finding the `END` keyword statement. If
     /// the `END` keyword statement is detected, it will have to pass the `eval_exit`
     /// function to properly exit scope. Assumes the last token consumed was `BEGIN`.
+    ///
+    /// The instantiated units are returned once per instantiation statement.
     fn parse_body<I>(
         tokens: &mut Peekable<I>,
         eval_exit: &dyn Fn(&Statement) -> bool,
         is_subprogram: bool,
-    ) -> (RefSet, RefSet)
+    ) -> (RefSet, Vec<CompoundIdentifier>)
     where
         I: Iterator<Item = Token<VhdlToken>>,
     {
         // collect names that were referenced
         let mut refs = RefSet::new();
         // collect component names
-        let mut deps = RefSet::new();
+        let mut deps = Vec::new();
         // println!("*--- statement section");
         while let Some(t) = tokens.peek() {
             if t.as_type().check_keyword(&Keyword::End) == true {
@@ -2601,6 +2631,44 @@ end architecture rtl;
         assert_eq!(syms[1].as_architecture().unwrap().get_refs().len(), 2);
     }
 
+    #[test]
+    fn architecture_lists_each_instance() {
+        let s = r#"
+architecture rtl of top is
+begin
+    u0: entity work.adder port map (a => x);
+    u1: entity work.adder port map (a => y);
+    gen: for i in 0 to 31 generate
+        u2: entity work.fa port map (a => z(i));
+    end generate;
+    u3: half port map (a => x);
+end architecture;
+"#;
+        let syms = VHDLParser::read(&s).unwrap().into_symbols();
+        let mut deps: Vec<String> = syms[0]
+            .as_architecture()
+            .unwrap()
+            .get_deps()
+            .iter()
+            .map(|d| d.to_string())
+            .collect();
+        deps.sort();
+        // each instantiation also lists its unit without the library, and instances
+        // inside a generate statement are counted once
+        assert_eq!(
+            deps,
+            vec![
+                "adder",
+                "adder",
+                "fa",
+                "half",
+                "work.adder",
+                "work.adder",
+                "work.fa"
+            ]
+        );
+    }
+
     #[test]
     fn test_entity_after_package() {
         let data = std::fs::read_to_string("./tests/t5/ent_after_pkg.vhd").unwrap();
@@ -2618,4 +2686,48 @@ end architecture rtl;
         // verify we captured all 3 sub-entities following procedures
         assert_eq!(syms[1].as_architecture().unwrap().get_refs().len(), 3);
     }
+
+    #[test]
+    fn package_declarations() {
+        let data = r#"
+package math_pkg is
+  constant WIDTH : natural := 8;
+  type pair_t is record
+    a : std_logic;
+    b : std_logic_vector(3 downto 0);
+  end record;
+  signal dbg : std_logic;
+  pure function clog2(n : natural) return natural;
+  component adder is
+    port (a, b : in std_logic; y : out std_logic);
+  end component;
+end package;
+
+entity after_pkg is end entity;
+"#;
+        let syms = VHDLParser::read(&data).unwrap().into_symbols();
+        // the record type does not end the package early
+        assert_eq!(syms.len(), 2);
+        let decls = syms[0].as_package().unwrap().get_declarations();
+        assert_eq!(
+            decls
+                .iter()
+                .map(|d| (*d.get_kind(), d.get_name().to_string()))
+                .collect::<Vec<(package::DeclarationKind, String)>>(),
+            vec![
+                (package::DeclarationKind::Constant, String::from("WIDTH")),
+                (package::DeclarationKind::Type, String::from("pair_t")),
+                (package::DeclarationKind::Function, String::from("clog2")),
+                (package::DeclarationKind::Component, String::from("adder")),
+            ]
+        );
+        assert_eq!(
+            decls[1].to_code(false),
+            "type pair_t is record a : std_logic; b : std_logic_vector(3 downto 0); end record"
+        );
+        assert_eq!(
+            decls[2].to_code(false),
+            "pure function clog2(n : natural) return natural"
+        );
+    }
 }
@@ -17,24 +17,154 @@
 
 use std::fmt::Display;
 
+use serde::ser::{Serialize, SerializeStruct, Serializer};
+use serde_derive::Serialize;
+
 use crate::core::lang::reference::RefSet;
+use crate::core::lang::vhdl::interface;
 
-use super::{packagebody::PackageBody, Generics, Identifier, Position};
+use super::{
+    packagebody::PackageBody, Generics, Identifier, Keyword, Position, Statement, VhdlToken,
+};
 
-#[derive(Debug, PartialEq)]
+#[derive(Debug, PartialEq, Serialize)]
 pub struct Package {
+    #[serde(rename = "identifier")]
     name: Identifier,
     generics: Generics,
+    declarations: Vec<Declaration>,
+    #[serde(skip_serializing)]
     body: Option<PackageBody>,
+    #[serde(skip_serializing)]
     refs: RefSet,
+    #[serde(skip_serializing)]
     pos: Position,
 }
 
+#[derive(Debug, PartialEq, Clone, Copy, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum DeclarationKind {
+    Constant,
+    Type,
+    Subtype,
+    Function,
+    Procedure,
+    Component,
+}
+
+impl Display for DeclarationKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}",
+            match self {
+                Self::Constant => "constants",
+                Self::Type => "types",
+                Self::Subtype => "subtypes",
+                Self::Function => "functions",
+                Self::Procedure => "procedures",
+                Self::Component => "components",
+            }
+        )
+    }
+}
+
+/// A publicly visible declaration within a package.
+#[derive(Debug, PartialEq)]
+pub struct Declaration {
+    kind: DeclarationKind,
+    name: Identifier,
+    code: Vec<VhdlToken>,
+}
+
+impl Declaration {
+    /// Creates a declaration from a statement within the package's declarative
+    /// part, if the statement declares an item that is visible to users of the package.
+    pub(super) fn from_statement(stmt: &Statement) -> Option<Self> {
+        let tokens: Vec<&VhdlToken> = stmt.as_types();
+        let mut iter = tokens
+            .iter()
+            .skip_while(|t| t.check_keyword(&Keyword::Pure) || t.check_keyword(&Keyword::Impure));
+        let kind = match iter.next()?.as_keyword()? {
+            Keyword::Constant => DeclarationKind::Constant,
+            Keyword::Type => DeclarationKind::Type,
+            Keyword::Subtype => DeclarationKind::Subtype,
+            Keyword::Function => DeclarationKind::Function,
+            Keyword::Procedure => DeclarationKind::Procedure,
+            _ => return None,
+        };
+        let name = iter.next()?.as_identifier()?.clone();
+        Some(Self {
+            kind: kind,
+            name: name,
+            code: tokens.into_iter().cloned().collect(),
+        })
+    }
+
+    /// Creates a declaration for a component named `name`.
+    pub(super) fn component(name: Identifier) -> Self {
+        Self {
+            kind: DeclarationKind::Component,
+            code: vec![
+                VhdlToken::Keyword(Keyword::Component),
+                VhdlToken::Identifier(name.clone()),
+            ],
+            name: name,
+        }
+    }
+
+    /// Appends the tokens of the `stmt` that continues this declaration, such as
+    /// the elements of a record type.
+    pub(super) fn extend(&mut self, stmt: &Statement) {
+        self.code
+            .push(VhdlToken::Delimiter(super::Delimiter::Terminator));
+        self.code.extend(stmt.as_types().into_iter().cloned());
+    }
+
+    /// Checks if the declaration continues across multiple statements until an
+    /// `END` statement.
+    pub(super) fn is_multiline(&self) -> bool {
+        self.kind == DeclarationKind::Type
+            && self
+                .code
+                .iter()
+                .any(|t| t.check_keyword(&Keyword::Record) || t.check_keyword(&Keyword::Protected))
+    }
+
+    pub fn get_kind(&self) -> &DeclarationKind {
+        &self.kind
+    }
+
+    pub fn get_name(&self) -> &Identifier {
+        &self.name
+    }
+
+    /// Writes the declaration as VHDL code, which is syntax highlighted when
+    /// `highlight` is enabled.
+    pub fn to_code(&self, highlight: bool) -> String {
+        interface::tokens_to_code(&self.code, highlight)
+    }
+}
+
+impl Serialize for Declaration {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let mut state = serializer.serialize_struct("Declaration", 3)?;
+        state.serialize_field("kind", &self.kind)?;
+        state.serialize_field("identifier", &self.name)?;
+        state.serialize_field("code", &self.to_code(false))?;
+        state.end()
+    }
+}
+
 impl Package {
     pub fn new(name: Identifier, refs: RefSet, pos: Position) -> Self {
         Self {
             name: name,
             generics: Generics::new(),
+            declarations: Vec::new(),
             body: None,
             refs: refs,
             pos: pos,
@@ -46,6 +176,11 @@ impl Package {
         self
     }
 
+    pub fn declarations(mut self, declarations: Vec<Declaration>) -> Self {
+        self.declarations = declarations;
+        self
+    }
+
     pub fn body(mut self, body: Option<PackageBody>) -> Self {
         self.body = body;
         self
@@ -57,6 +192,15 @@ impl Package {
         &self.name
     }
 
+    pub fn get_generics(&self) -> &Generics {
+        &self.generics
+    }
+
+    /// Accesses the publicly visible declarations of the package.
+    pub fn get_declarations(&self) -> &Vec<Declaration> {
+        &self.declarations
+    }
+
     /// Accesses the references for the entity.
     pub fn get_refs(&self) -> &RefSet {
         &self.refs
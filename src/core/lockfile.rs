@@ -190,6 +190,55 @@ pub mod v1 {
             })
         }
 
+        /// Creates a new lockfile where each `(old, new)` pair in `updates` swaps the
+        /// entry matching `old` for `new`. All other entries are kept as-is.
+        pub fn replace_entries(&self, updates: Vec<(&LockEntry, LockEntry)>) -> Self {
+            let mut entries: Vec<LockEntry> = self
+                .ip
+                .iter()
+                .map(|e| match updates.iter().find(|(old, _)| *old == e) {
+                    Some((_, new)) => new.clone(),
+                    None => e.clone(),
+                })
+                .collect();
+            // keep the entries sorted by name and then version
+            entries.sort_by(|x, y| match x.name.cmp(&y.name) {
+                std::cmp::Ordering::Equal => x.version.cmp(&y.version),
+                ord => ord,
+            });
+            Self {
+                version: LOCK_VERSION,
+                ip: entries,
+            }
+        }
+
+        /// Collects the version requirements placed on the `target` entry by every
+        /// entry that depends on it.
+        ///
+        /// The working ip's requirements are read from `root` instead of its
+        /// lockfile entry to reflect the latest manifest.
+        pub fn get_requirements<'a>(
+            &'a self,
+            target: &LockEntry,
+            root: &'a LockEntry,
+        ) -> Vec<&'a AnyVersion> {
+            self.ip
+                .iter()
+                .map(|e| match e.checksum.is_none() && e.name == root.name {
+                    true => root.get_deps(),
+                    false => e.get_deps(),
+                })
+                .flatten()
+                .filter(|d| {
+                    d.get_name() == target.get_name()
+                        && d.as_uuid()
+                            .as_ref()
+                            .map_or(true, |id| id == target.get_uuid())
+                })
+                .map(|d| d.get_version())
+                .collect()
+        }
+
         /// Returns an exact match of `target` and `version` from within the lockfile.
         pub fn get(&self, target: &PkgPart, version: &PartialVersion) -> Option<&LockEntry> {
             self.ip
@@ -218,7 +267,7 @@ pub mod v1 {
                     }
                 })
                 .collect();
-            match version::get_target_version(&version, &space) {
+            match version::get_target_version(&version, &space, &[]) {
                 Ok(v) => self.ip.iter().find(|f| &f.name == target && f.version == v),
                 Err(_) => None,
             }
@@ -258,7 +307,7 @@ pub mod v1 {
                         // check if this entry is a dev dependency
                         |p| match target.get_man().get_dev_deps().get(p.get_name()) {
                             Some(v) => {
-                                if version::is_compatible(v.get_version(), p.get_version()) {
+                                if v.get_version().matches(p.get_version()) {
                                     false
                                 } else {
                                     true
@@ -288,6 +337,8 @@ pub mod v1 {
         checksum: Option<Sha256Hash>,
         #[serde(flatten)]
         source: Option<Source>,
+        // @note: `commit` is only recorded for ips fetched over the built-in "git" protocol
+        commit: Option<String>,
         // @note: `path` is optional and only used if the dependency list uses a local ip
         path: Option<PathBuf>,
         dependencies: Vec<PartialIpSpec>,
@@ -314,6 +365,10 @@ pub mod v1 {
                     None
                 },
                 source: target.get_man().get_ip().get_source().cloned(),
+                commit: match is_local == true || target.get_mapping().is_relative() == true {
+                    true => None,
+                    false => Ip::read_cache_commit(target.get_root()),
+                },
                 dependencies: match target.get_man().get_deps_list(is_local, true).len() {
                     0 => Vec::new(),
                     _ => {
@@ -394,6 +449,10 @@ pub mod v1 {
                     None
                 },
                 source: ip.get_man().get_ip().get_source().cloned(),
+                commit: match is_working == true || ip.get_mapping().is_relative() == true {
+                    true => None,
+                    false => Ip::read_cache_commit(ip.get_root()),
+                },
                 dependencies: match ip.get_man().get_deps_list(is_working, true).len() {
                     0 => Vec::new(),
                     _ => {
@@ -539,6 +598,22 @@ pub mod v1 {
             self.source.as_ref()
         }
 
+        pub fn get_commit(&self) -> Option<&String> {
+            self.commit.as_ref()
+        }
+
+        /// Returns the source to fetch this entry from.
+        ///
+        /// Sources using the "git" protocol are pinned to the recorded commit so
+        /// the same files are fetched even if the tag or branch has since moved.
+        pub fn get_pinned_source(&self) -> Option<Source> {
+            let src = self.source.as_ref()?.clone();
+            match (src.is_git(), &self.commit) {
+                (true, Some(c)) => Some(src.tag(Some(c.clone()))),
+                _ => Some(src),
+            }
+        }
+
         pub fn get_name(&self) -> &IpName {
             &self.name
         }
@@ -20,8 +20,8 @@
 use crate::core::ip::IpSpec;
 use crate::core::lang::vhdl::token::Identifier;
 use crate::core::pkgid::PkgPart;
+use crate::core::source;
 use crate::core::source::Source;
-use crate::core::{source, version};
 use crate::error::Error;
 use crate::util::anyerror::{AnyError, Fault};
 use serde::de::{self, MapAccess, Visitor};
@@ -29,6 +29,7 @@ use serde_derive::{Deserialize, Serialize};
 use std::fmt::{self, Display};
 use std::path::PathBuf;
 use std::{collections::HashMap, str::FromStr};
+use toml_edit::{Document, InlineTable, Item, Table, Value};
 
 use super::ip::Ip;
 use super::lang::vhdl::token::identifier::Identifier as VhdlIdentifier;
@@ -37,7 +38,7 @@ use super::uuid::Uuid;
 
 pub type IpName = PkgPart;
 pub type IpVersion = crate::core::version::Version;
-pub type DepVersion = crate::core::version::PartialVersion;
+pub type DepVersion = crate::core::version::VersionReq;
 
 #[derive(Serialize, Debug, PartialEq)]
 #[serde(deny_unknown_fields, transparent)]
@@ -200,6 +201,7 @@ type Dependencies = HashMap<IpName, Dependency>;
 pub const IP_MANIFEST_FILE: &str = "Orbit.toml";
 pub const ORBIT_SUM_FILE: &str = ".orbit-checksum";
 pub const ORBIT_METADATA_FILE: &str = ".orbit-metadata";
+pub const ORBIT_COMMIT_FILE: &str = ".orbit-commit";
 
 const DEPENDENCIES_KEY: &str = "dependencies";
 
@@ -265,7 +267,7 @@ impl FromFile for Manifest {
                     )?;
                     // verify the ip loaded has the correct version assigned by the user
                     let ip_version = ip.get_man().get_ip().get_version();
-                    if version::is_compatible(dep.get_version(), ip_version) == false {
+                    if dep.get_version().matches(ip_version) == false {
                         return Err(Error::DependencyIpRelativeBadVersion(
                             dep.get_version().clone(),
                             ip_version.clone(),
@@ -284,7 +286,7 @@ impl FromFile for Manifest {
                 }
             }
             // verify there are no cycles in the ip dependency graph
-            if name == &local_name && version::is_compatible(dep.get_version(), &local_version) {
+            if name == &local_name && dep.get_version().matches(&local_version) {
                 return Err(Error::CyclicDependencyIp(local_name))?;
             }
         }
@@ -315,6 +317,7 @@ impl Manifest {
                 public: None,
                 library: None,
                 readme: None,
+                deprecated: None,
                 include: None,
                 exclude: None,
                 authors: None,
@@ -466,6 +469,145 @@ impl Display for Manifest {
     }
 }
 
+const DEV_DEPENDENCIES_KEY: &str = "dev-dependencies";
+const IP_KEY: &str = "ip";
+const VERSION_KEY: &str = "version";
+
+/// A manifest file that can be edited in place while keeping its formatting
+/// and comments.
+#[derive(Debug)]
+pub struct ManifestDocument {
+    document: Document,
+}
+
+impl FromStr for ManifestDocument {
+    type Err = toml::de::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        // verify all keys are valid during deserializing
+        let _: Manifest = toml::from_str(s)?;
+        Ok(Self {
+            document: s.parse::<Document>().unwrap(),
+        })
+    }
+}
+
+impl FromFile for ManifestDocument {
+    fn from_file(path: &PathBuf) -> Result<Self, Fault> {
+        // open file
+        let contents = std::fs::read_to_string(path)?;
+        // parse toml syntax
+        match Self::from_str(&contents) {
+            Ok(r) => Ok(r),
+            Err(e) => {
+                return Err(AnyError(format!(
+                    "failed to parse {} file at path {:?}: {}",
+                    IP_MANIFEST_FILE, path, e
+                )))?
+            }
+        }
+    }
+}
+
+impl Display for ManifestDocument {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.document)
+    }
+}
+
+impl ManifestDocument {
+    fn table_key(is_dev: bool) -> &'static str {
+        match is_dev {
+            true => DEV_DEPENDENCIES_KEY,
+            false => DEPENDENCIES_KEY,
+        }
+    }
+
+    /// Sets the entry for the dependency `name` in the "dependencies" table, or
+    /// the "dev-dependencies" table when `is_dev` is `true`.
+    ///
+    /// Creates the table if it does not exist. An entry for `name` in the other
+    /// table is removed.
+    pub fn add_dependency(
+        &mut self,
+        name: &IpName,
+        version: &DepVersion,
+        path: Option<&str>,
+        uuid: Option<&Uuid>,
+        is_dev: bool,
+    ) -> () {
+        // remove the entry from the other table to avoid duplicate keys
+        if let Some(table) = self.document.get_mut(Self::table_key(!is_dev)) {
+            if let Some(table) = table.as_table_like_mut() {
+                table.remove(name.as_ref());
+            }
+        }
+        // create table if it does not exist
+        let key = Self::table_key(is_dev);
+        if self.document.contains_key(key) == false {
+            self.document.insert(key, Item::Table(Table::new()));
+        }
+        // use the short form for entries that only require a version
+        let value = match path.is_none() && uuid.is_none() {
+            true => Value::from(version.to_string()),
+            false => {
+                let mut entry = InlineTable::new();
+                if let Some(p) = path {
+                    entry.insert("path", Value::from(p));
+                }
+                entry.insert("version", Value::from(version.to_string()));
+                if let Some(id) = uuid {
+                    entry.insert("uuid", Value::from(id.to_string()));
+                }
+                InlineTable::fmt(&mut entry);
+                Value::InlineTable(entry)
+            }
+        };
+        // insert/overwrite into the table
+        self.document
+            .get_mut(key)
+            .unwrap()
+            .as_table_like_mut()
+            .unwrap()
+            .insert(name.as_ref(), Item::Value(value));
+    }
+
+    /// Removes the entry for the dependency `name` from the "dependencies" and
+    /// "dev-dependencies" tables.
+    ///
+    /// Returns `false` if neither table has an entry for `name`.
+    pub fn remove_dependency(&mut self, name: &IpName) -> bool {
+        let mut removed = false;
+        for key in [DEPENDENCIES_KEY, DEV_DEPENDENCIES_KEY] {
+            if let Some(table) = self.document.get_mut(key) {
+                if let Some(table) = table.as_table_like_mut() {
+                    removed |= table.remove(name.as_ref()).is_some();
+                }
+            }
+        }
+        removed
+    }
+
+    /// Sets the ip's version in the "ip" table.
+    ///
+    /// Any comment trailing the existing value is kept.
+    pub fn set_version(&mut self, version: &IpVersion) -> () {
+        let item = &mut self.document[IP_KEY][VERSION_KEY];
+        let decor = item.as_value().map(|v| v.decor().clone());
+        *item = Item::Value(Value::from(version.to_string()));
+        if let Some(d) = decor {
+            *item.as_value_mut().unwrap().decor_mut() = d;
+        }
+    }
+
+    /// Writes the `document` to the `dest` path.
+    pub fn write(&mut self, dest: &PathBuf) -> Result<(), Fault> {
+        let contents = self.document.to_string();
+        std::fs::write(dest, contents)?;
+        Ok(())
+    }
+}
+
 fn vec_is_empty<T>(field: &Vec<T>) -> bool {
     field.is_empty()
 }
@@ -499,6 +641,8 @@ pub struct Package {
     exclude: Option<Vec<String>>,
     /// Filepath to the project's README.
     readme: Option<PathBuf>,
+    /// Message explaining why the ip should no longer be used
+    deprecated: Option<String>,
     /// Ignore this field and never use it for any processing
     #[serde(skip_serializing_if = "map_is_empty", default)]
     metadata: HashMap<String, toml::Value>,
@@ -550,6 +694,10 @@ impl Package {
         &self.readme
     }
 
+    pub fn get_deprecated(&self) -> &Option<String> {
+        &self.deprecated
+    }
+
     pub fn get_include(&self) -> &Option<Vec<String>> {
         &self.include
     }
@@ -765,6 +913,24 @@ mod test {
             );
         }
 
+        #[test]
+        fn ut_version_requirements() {
+            let man: Manifest = toml::from_str(EX8).unwrap();
+            let gates = man
+                .dependencies
+                .get(&PkgPart::from_str("gates").unwrap())
+                .unwrap();
+            assert_eq!(gates.get_version().to_string(), ">=1.2.0, <1.5");
+            assert_eq!(
+                gates
+                    .get_version()
+                    .matches(&IpVersion::new().major(1).minor(4).micro(2)),
+                true
+            );
+            let text = man.to_string();
+            assert_eq!(text, EX8);
+        }
+
         #[test]
         #[should_panic]
         fn ut_source_missing_url() {
@@ -775,8 +941,109 @@ mod test {
             };
         }
     }
+
+    mod edit {
+        use super::*;
+
+        #[test]
+        fn ut_add_dependency() {
+            let mut doc = ManifestDocument::from_str(EX9).unwrap();
+            doc.add_dependency(
+                &PkgPart::from_str("gates").unwrap(),
+                &DepVersion::from_str("2.1").unwrap(),
+                None,
+                None,
+                false,
+            );
+            doc.add_dependency(
+                &PkgPart::from_str("uart").unwrap(),
+                &DepVersion::from_str("0.3").unwrap(),
+                Some("../uart"),
+                None,
+                true,
+            );
+            assert_eq!(doc.to_string(), EX9_ADD);
+            // the result is still a valid manifest
+            assert_eq!(Manifest::from_str(&doc.to_string()).is_ok(), true);
+        }
+
+        #[test]
+        fn ut_add_dependency_moves_table() {
+            let mut doc = ManifestDocument::from_str(EX3).unwrap();
+            doc.add_dependency(
+                &PkgPart::from_str("top-builder").unwrap(),
+                &DepVersion::from_str("1.1").unwrap(),
+                None,
+                Some(&Uuid::from_str("0000000000000000000000000").unwrap()),
+                false,
+            );
+            let man = Manifest::from_str(&doc.to_string()).unwrap();
+            assert_eq!(man.get_dev_deps().len(), 0);
+            let dep = man
+                .get_deps()
+                .get(&PkgPart::from_str("top-builder").unwrap())
+                .unwrap();
+            assert_eq!(dep.get_version().to_string(), "1.1");
+            assert_eq!(dep.as_uuid().is_some(), true);
+        }
+
+        #[test]
+        fn ut_remove_dependency() {
+            let mut doc = ManifestDocument::from_str(EX9_ADD).unwrap();
+            assert_eq!(
+                doc.remove_dependency(&PkgPart::from_str("uart").unwrap()),
+                true
+            );
+            assert_eq!(
+                doc.remove_dependency(&PkgPart::from_str("uart").unwrap()),
+                false
+            );
+            assert_eq!(
+                doc.remove_dependency(&PkgPart::from_str("gates").unwrap()),
+                true
+            );
+            assert_eq!(doc.to_string(), format!("{}\n[dev-dependencies]\n", EX9));
+        }
+
+        #[test]
+        fn ut_set_version() {
+            let mut doc = ManifestDocument::from_str(EX10).unwrap();
+            doc.set_version(&IpVersion::from_str("1.3.0-beta").unwrap());
+            assert_eq!(doc.to_string(), EX10.replace("1.2.4", "1.3.0-beta"));
+        }
+    }
 }
 
+const EX9: &str = r#"[ip]
+name = "lab4"
+uuid = "0000000000000000000000000"
+version = "0.1.0"
+
+[dependencies]
+# keep this comment
+mux = "1.0" # and this comment
+"#;
+
+const EX10: &str = r#"[ip]
+name = "lab5"
+uuid = "0000000000000000000000000"
+version = "1.2.4" # released
+"#;
+
+const EX9_ADD: &str = r#"[ip]
+name = "lab4"
+uuid = "0000000000000000000000000"
+version = "0.1.0"
+
+[dependencies]
+# keep this comment
+mux = "1.0" # and this comment
+gates = "2.1"
+
+[dev-dependencies]
+uart = { path = "../uart", version = "0.3" }
+"#;
+
 const EX1: &str = r#"[ip]
 name = "gates"
 uuid = "0000000000000000000000000"
@@ -848,5 +1115,14 @@ version = "1.20.0"
 source = { protocol = "ktsp" }
 "#;
 
+const EX8: &str = r#"[ip]
+name = "lab3"
+uuid = "0000000000000000000000000"
+version = "0.2.0"
+
+[dependencies]
+gates = ">=1.2.0, <1.5"
+"#;
+
 const ERR1: &str = r#"[ip]
 "#;
@@ -21,6 +21,7 @@ pub mod catalog;
 pub mod channel;
 pub mod config;
 pub mod context;
+pub mod export;
 pub mod fileset;
 pub mod ip;
 pub mod iparchive;
@@ -33,6 +34,8 @@ pub mod protocol;
 pub mod source;
 pub mod swap;
 pub mod target;
+#[cfg(test)]
+pub mod testing;
 pub mod uuid;
 pub mod version;
 pub mod visibility;
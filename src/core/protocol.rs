@@ -21,11 +21,14 @@
 use crate::core::swap;
 use crate::core::target::Process;
 use serde_derive::{Deserialize, Serialize};
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::str::FromStr;
 
 pub type Protocols = Vec<Protocol>;
 
+/// Name of the built-in protocol that fetches ips from git repositories.
+pub const GIT_PROTOCOL: &str = "git";
+
 #[derive(Debug, PartialEq, Serialize, Deserialize, Clone)]
 #[serde(deny_unknown_fields)]
 pub struct Protocol {
@@ -78,13 +81,17 @@ impl Process for Protocol {
     }
 }
 
-use crate::commands::orbit::UpgradeError;
-use crate::commands::orbit::RESPONSE_OKAY;
+use crate::core::config::DownloadSettings;
+use crate::core::manifest;
+use crate::core::manifest::IP_MANIFEST_FILE;
+use crate::error::Error as OrbitError;
+use crate::error::LastError;
 use crate::util::anyerror::Fault;
-use curl::easy::Easy;
-use std::io::Write;
-use tempfile;
-use zip::ZipArchive;
+use crate::util::compress::ArchiveFormat;
+use crate::util::http;
+use std::fs::File;
+use std::io::{Read, Seek, SeekFrom};
+use std::process::Command;
 
 use super::swap::StrSwapTable;
 
@@ -109,12 +116,26 @@ impl Protocol {
 
     /// Creates a string to display a list of plugins.
     ///
-    /// The string lists the plugins in alphabetical order by `name`.
+    /// The string lists the plugins in alphabetical order by `name`. The built-in
+    /// "git" protocol is listed unless a user-defined protocol shares its name.
     pub fn list_protocols(protos: &mut [&&Protocol]) -> String {
+        let mut lines: Vec<(&str, String)> = protos
+            .iter()
+            .map(|p| (p.get_name(), p.quick_info()))
+            .collect();
+        if protos.iter().any(|p| p.get_name() == GIT_PROTOCOL) == false {
+            lines.push((
+                GIT_PROTOCOL,
+                format!(
+                    "{:<16}{}",
+                    GIT_PROTOCOL, "Clone ips from git repositories (built-in)"
+                ),
+            ));
+        }
+        lines.sort_by(|a, b| a.0.cmp(b.0));
         let mut list = String::from("Protocols:\n");
-        protos.sort_by(|a: &&&Protocol, b| a.name.cmp(&b.name));
-        for proto in protos {
-            list += &format!("  {}\n", proto.quick_info());
+        for (_, info) in lines {
+            list += &format!("  {}\n", info);
         }
         list
     }
@@ -130,43 +151,136 @@ impl Protocol {
 
     /// Performs the default behavior for a protocol.
     ///
-    /// This will attempt to download the url as a zip file and extract it to
-    /// its queue directory.
-    pub fn single_download(url: &str, dst: &PathBuf) -> Result<(), Fault> {
-        let mut body_bytes = Vec::new();
-        {
-            let mut easy = Easy::new();
-            easy.url(&url).unwrap();
-            easy.follow_location(true).unwrap();
-            {
-                let mut transfer = easy.transfer();
-                transfer
-                    .write_function(|data| {
-                        body_bytes.extend_from_slice(data);
-                        Ok(data.len())
-                    })
-                    .unwrap();
-
-                transfer.perform()?;
-            }
-            let rc = easy.response_code()?;
-            if rc != RESPONSE_OKAY {
-                return Err(Box::new(UpgradeError::FailedConnection(
-                    url.to_string(),
-                    rc,
-                )));
-            }
+    /// This will attempt to download the url as a zip, tar, or tar.gz archive and
+    /// extract it to its queue directory. The archive's format is detected from its
+    /// contents and then from the url's extension. When the archive holds its files
+    /// under a single top-level directory, that directory is removed so the ip's
+    /// root is the queue directory.
+    ///
+    /// The archive is streamed according to the download `settings` to a file
+    /// kept within the `downloads` directory, so an interrupted transfer resumes
+    /// the next time the same url is downloaded. The file is removed once it is
+    /// extracted or found to be unusable.
+    pub fn single_download(
+        url: &str,
+        dst: &PathBuf,
+        downloads: &PathBuf,
+        settings: &DownloadSettings,
+    ) -> Result<(), Fault> {
+        let archive = http::partial_path(downloads, url);
+        if let Some(parent) = archive.parent() {
+            std::fs::create_dir_all(parent)?;
         }
-        // place the bytes into a file
-        let mut temp_file = tempfile::tempfile()?;
-        temp_file.write_all(&body_bytes)?;
-        let mut zip_archive = ZipArchive::new(temp_file)?;
+        http::download_to_file(url, &archive, settings, &[])?;
 
-        // decompress the zip file to the queue
-        zip_archive.extract(&dst)?;
+        let result = Self::unpack(&archive, url, dst);
+        // failing to clean up the archive should not hide the outcome of unpacking
+        std::fs::remove_file(&archive).ok();
+        result
+    }
+
+    /// Extracts the downloaded `archive` from `url` into the queue `dst`.
+    fn unpack(archive: &Path, url: &str, dst: &PathBuf) -> Result<(), Fault> {
+        // read enough of the archive to recognize its format
+        let mut file = File::open(archive)?;
+        let mut head = Vec::new();
+        (&mut file).take(512).read_to_end(&mut head)?;
+        file.seek(SeekFrom::Start(0))?;
+
+        // decompress the archive to the queue
+        match ArchiveFormat::detect(&head, url) {
+            Some(format) => format.extract(file, dst)?,
+            None => return Err(OrbitError::DownloadUnknownArchive(url.to_string()))?,
+        }
+        if dst.join(IP_MANIFEST_FILE).exists() == false {
+            Self::strip_top_level_dir(dst)?;
+        }
+
+        // verify an ip exists within the archive
+        if manifest::find_file(dst, IP_MANIFEST_FILE, false)?.is_empty() == true {
+            return Err(OrbitError::DownloadMissingManifest(url.to_string()))?;
+        }
+        Ok(())
+    }
 
+    /// Moves the contents of the only entry in `dir` up into `dir` when that entry
+    /// is a directory.
+    fn strip_top_level_dir(dir: &PathBuf) -> Result<(), Fault> {
+        let entries = std::fs::read_dir(dir)?.collect::<Result<Vec<_>, _>>()?;
+        if entries.len() != 1 || entries[0].path().is_dir() == false {
+            return Ok(());
+        }
+        // rename the nested directory first in case a child shares its name
+        let nested = dir.join(".orbit-nested");
+        std::fs::rename(entries[0].path(), &nested)?;
+        for entry in std::fs::read_dir(&nested)? {
+            let entry = entry?;
+            std::fs::rename(entry.path(), dir.join(entry.file_name()))?;
+        }
+        std::fs::remove_dir(nested)?;
         Ok(())
     }
+
+    /// Performs the built-in behavior for the "git" protocol.
+    ///
+    /// This will clone the repository at `url` into its queue directory and check
+    /// out the `tag`, which may name a tag, branch, or commit. The repository's
+    /// default branch is used when no `tag` is provided. The git history is removed
+    /// from the queue after checking out the files.
+    ///
+    /// Returns the full hash of the commit that was checked out.
+    pub fn git_download(url: &str, tag: Option<&str>, dst: &PathBuf) -> Result<String, Fault> {
+        Self::git(url, dst, &["clone", "--quiet", "--no-checkout", url, "."])?;
+        let commit = match tag {
+            Some(t) => {
+                // prefer local refs (tags, default branch, commits) before remote branches
+                let local = format!("{}^{{commit}}", t);
+                let remote = format!("origin/{}^{{commit}}", t);
+                match Self::git(url, dst, &["rev-parse", "--verify", "--quiet", &local]) {
+                    Ok(c) => c,
+                    Err(_) => {
+                        match Self::git(url, dst, &["rev-parse", "--verify", "--quiet", &remote]) {
+                            Ok(c) => c,
+                            Err(_) => {
+                                return Err(OrbitError::GitProtocolFailed(
+                                    url.to_string(),
+                                    LastError(format!("no tag, branch, or commit named \"{}\"", t)),
+                                ))?
+                            }
+                        }
+                    }
+                }
+            }
+            None => Self::git(url, dst, &["rev-parse", "HEAD"])?,
+        };
+        Self::git(url, dst, &["checkout", "--quiet", "--detach", &commit])?;
+        // only the files at the commit are needed for the ip
+        std::fs::remove_dir_all(dst.join(".git"))?;
+        Ok(commit)
+    }
+
+    /// Runs git with the `args` in the directory `cwd` and returns its trimmed
+    /// standard output.
+    ///
+    /// Errors with git's message if the process does not exit successfully.
+    fn git(url: &str, cwd: &PathBuf, args: &[&str]) -> Result<String, Fault> {
+        let output = match Command::new("git").current_dir(cwd).args(args).output() {
+            Ok(o) => o,
+            Err(e) => {
+                return Err(OrbitError::GitProtocolFailed(
+                    url.to_string(),
+                    LastError(e.to_string()),
+                ))?
+            }
+        };
+        match output.status.success() {
+            true => Ok(String::from_utf8_lossy(&output.stdout).trim().to_string()),
+            false => Err(OrbitError::GitProtocolFailed(
+                url.to_string(),
+                LastError(String::from_utf8_lossy(&output.stderr).trim().to_string()),
+            ))?,
+        }
+    }
 }
 
 impl std::fmt::Display for Protocol {
@@ -251,6 +365,86 @@ args = ["~/scripts/download.bash"]
         );
     }
 
+    #[test]
+    fn strip_single_top_level_dir() {
+        let tmp = tempfile::tempdir().unwrap();
+        let dir = tmp.path().to_path_buf();
+        // a nested child may share the name of its parent directory
+        std::fs::create_dir_all(dir.join("gates/gates")).unwrap();
+        std::fs::write(dir.join("gates/Orbit.toml"), "").unwrap();
+        Protocol::strip_top_level_dir(&dir).unwrap();
+        assert_eq!(dir.join("Orbit.toml").exists(), true);
+        assert_eq!(dir.join("gates").is_dir(), true);
+        assert_eq!(dir.join(".orbit-nested").exists(), false);
+
+        // multiple top-level entries are kept as-is
+        Protocol::strip_top_level_dir(&dir).unwrap();
+        assert_eq!(dir.join("Orbit.toml").exists(), true);
+        assert_eq!(dir.join("gates").is_dir(), true);
+    }
+
+    /// Runs git in `dir` for setting up test repositories.
+    fn run_git(dir: &PathBuf, args: &[&str]) -> String {
+        let output = Command::new("git")
+            .current_dir(dir)
+            .args(["-c", "user.name=orbit", "-c", "user.email=orbit@localhost"])
+            .args(args)
+            .output()
+            .unwrap();
+        assert_eq!(output.status.success(), true);
+        String::from_utf8_lossy(&output.stdout).trim().to_string()
+    }
+
+    #[test]
+    fn git_download_checks_out_tag() {
+        let repo_dir = tempfile::tempdir().unwrap();
+        let repo = repo_dir.path().to_path_buf();
+        run_git(&repo, &["init", "--quiet"]);
+        std::fs::write(repo.join("a.vhd"), "-- 1.0.0").unwrap();
+        run_git(&repo, &["add", "."]);
+        run_git(&repo, &["commit", "--quiet", "-m", "first"]);
+        run_git(&repo, &["tag", "1.0.0"]);
+        let tagged = run_git(&repo, &["rev-parse", "HEAD"]);
+        run_git(&repo, &["checkout", "--quiet", "-b", "dev"]);
+        std::fs::write(repo.join("a.vhd"), "-- dev").unwrap();
+        run_git(&repo, &["commit", "--quiet", "-am", "second"]);
+        let dev = run_git(&repo, &["rev-parse", "HEAD"]);
+        let url = repo.to_str().unwrap();
+
+        // tags resolve to the commit they point to
+        let queue_dir = tempfile::tempdir().unwrap();
+        let queue = queue_dir.path().to_path_buf();
+        let commit = Protocol::git_download(url, Some("1.0.0"), &queue).unwrap();
+        assert_eq!(commit, tagged);
+        assert_eq!(
+            std::fs::read_to_string(queue.join("a.vhd")).unwrap(),
+            "-- 1.0.0"
+        );
+        assert_eq!(queue.join(".git").exists(), false);
+
+        // branches and commits are accepted as the tag
+        let queue_dir = tempfile::tempdir().unwrap();
+        let queue = queue_dir.path().to_path_buf();
+        assert_eq!(
+            Protocol::git_download(url, Some("dev"), &queue).unwrap(),
+            dev
+        );
+        let queue_dir = tempfile::tempdir().unwrap();
+        let queue = queue_dir.path().to_path_buf();
+        assert_eq!(
+            Protocol::git_download(url, Some(&tagged), &queue).unwrap(),
+            tagged
+        );
+
+        // unknown refs fail
+        let queue_dir = tempfile::tempdir().unwrap();
+        let queue = queue_dir.path().to_path_buf();
+        assert_eq!(
+            Protocol::git_download(url, Some("2.0.0"), &queue).is_err(),
+            true
+        );
+    }
+
     #[test]
     fn series_of_protocols() {
         let contents = format!("{0}{1}\n{0}{2}", "[[protocol]]", P_1, P_2);
@@ -15,6 +15,7 @@
 //  along with this program.  If not, see <http://www.gnu.org/licenses/>.
 //
 
+use super::protocol::GIT_PROTOCOL;
 use super::swap::{self, StrSwapTable};
 use crate::util::anyerror::AnyError;
 use serde::ser::SerializeMap;
@@ -83,6 +84,11 @@ impl Source {
         self.protocol.is_none()
     }
 
+    /// Checks if the source is set to use the "git" protocol.
+    pub fn is_git(&self) -> bool {
+        self.protocol.as_deref() == Some(GIT_PROTOCOL)
+    }
+
     pub fn as_option(&self) -> Option<&Source> {
         match &self.valid {
             true => Some(&self),
@@ -17,6 +17,7 @@
 
 use crate::core::context::Context;
 use crate::core::fileset::Style;
+use crate::core::lang::verilog::preprocess::Define;
 use crate::error::Error;
 use crate::util::anyerror::Fault;
 use crate::util::filesystem;
@@ -46,6 +47,7 @@ pub struct Target {
     args: Option<Vec<String>>,
     fileset: Option<Filesets>,
     plans: Option<Vec<Scheme>>,
+    defines: Option<Vec<Define>>,
 }
 
 impl Target {
@@ -67,6 +69,11 @@ impl Target {
         self.fileset.as_ref()
     }
 
+    /// Returns the macros to define when preprocessing Verilog and SystemVerilog files.
+    pub fn get_defines(&self) -> &[Define] {
+        self.defines.as_deref().unwrap_or(&[])
+    }
+
     pub fn coordinate_plan(&self, plan: &Option<Scheme>) -> Result<Scheme, Error> {
         match plan {
             Some(p) => {
@@ -161,6 +168,7 @@ impl std::fmt::Display for Target {
             root: self.root.clone(),
             fileset: self.fileset.clone(),
             plans: self.plans.clone(),
+            defines: self.defines.clone(),
         };
 
         write!(f, "{}", toml::to_string_pretty(&refreshed_target).unwrap())
@@ -290,6 +298,19 @@ fileset.text = "*.txt"
 name = "ffi"
 command = "bash"
 args = ["~/scripts/download.bash"]    
+"#;
+
+    const P_3: &str = r#"
+name = "vivado"
+command = "python"
+args = ["./scripts/vivado.py"]
+plans = ["json", "tsv"]
+"#;
+
+    const P_4: &str = r#"
+name = "iverilog"
+command = "python"
+defines = ["SIM", "WIDTH=8"]
 "#;
 
     #[test]
@@ -312,6 +333,7 @@ args = ["~/scripts/download.bash"]
                     ),
                     (String::from("text"), Style::from_str("*.txt").unwrap()),
                 ])),
+                defines: None,
                 root: None,
             }
         );
@@ -326,11 +348,43 @@ args = ["~/scripts/download.bash"]
                 description: None,
                 plans: None,
                 fileset: None,
+                defines: None,
                 root: None,
             }
         );
     }
 
+    #[test]
+    fn from_toml_string_with_defines() {
+        let plug = Target::from_str(P_4).unwrap();
+        assert_eq!(
+            plug.get_defines(),
+            &[
+                Define::from_str("SIM").unwrap(),
+                Define::from_str("WIDTH=8").unwrap()
+            ]
+        );
+        assert_eq!(
+            Target::from_str(P_2).unwrap().get_defines().is_empty(),
+            true
+        );
+        assert_eq!(
+            Target::from_str("name = \"a\"\ncommand = \"b\"\ndefines = [\"8BIT\"]").is_err(),
+            true
+        );
+    }
+
+    #[test]
+    fn from_toml_string_with_plans() {
+        let plug = Target::from_str(P_3).unwrap();
+        assert_eq!(plug.plans, Some(vec![Scheme::Json, Scheme::Tsv]));
+        assert_eq!(plug.coordinate_plan(&None).unwrap(), Scheme::Json);
+        assert_eq!(
+            plug.coordinate_plan(&Some(Scheme::Tsv)).unwrap(),
+            Scheme::Tsv
+        );
+    }
+
     #[test]
     fn series_of_plugins() {
         let contents = format!("{0}{1}\n{0}{2}", "[[plugin]]", P_1, P_2);
@@ -0,0 +1,147 @@
+//
+//  Copyright (C) 2022-2025  Chase Ruskin
+//
+//  This program is free software: you can redistribute it and/or modify
+//  it under the terms of the GNU General Public License as published by
+//  the Free Software Foundation, either version 3 of the License, or
+//  (at your option) any later version.
+//
+//  This program is distributed in the hope that it will be useful,
+//  but WITHOUT ANY WARRANTY; without even the implied warranty of
+//  MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+//  GNU General Public License for more details.
+//
+//  You should have received a copy of the GNU General Public License
+//  along with this program.  If not, see <http://www.gnu.org/licenses/>.
+//
+
+//! Fixtures for the manifests, lockfiles, catalogs, and ip directories shared
+//! across unit tests.
+
+use crate::core::catalog::{Catalog, IpLevel, PointerSlot};
+use crate::core::ip::Ip;
+use crate::core::ippointer::{IpPointer, ORBIT_YANK_FILE};
+use crate::core::lockfile::LockFile;
+use crate::core::manifest::{Manifest, IP_MANIFEST_FILE};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+
+/// The checksum recorded for every locked dependency.
+const CHECKSUM: &str = "3154ff1a3d0148a724ec57b9bd382e1e1592d31bd496cb8448697b959a5ba26e";
+
+/// An ip recorded in a lockfile as `(name, uuid, version, dependencies)`.
+pub type Locked<'a> = (&'a str, &'a str, &'a str, &'a [&'a str]);
+
+/// Writes the text of a manifest for the ip `name` that requires each
+/// `(name, version)` in `deps`.
+pub fn manifest_text(name: &str, uuid: &str, version: &str, deps: &[(&str, &str)]) -> String {
+    deps.iter().fold(
+        format!(
+            "[ip]\nname = \"{}\"\nversion = \"{}\"\nuuid = \"{}\"\n\n[dependencies]\n",
+            name, version, uuid
+        ),
+        |acc, (dep, req)| acc + &format!("{} = \"{}\"\n", dep, req),
+    )
+}
+
+/// Creates the manifest for the ip `name` without any dependencies.
+pub fn create_manifest(name: &str, uuid: &str, version: &str) -> Manifest {
+    Manifest::from_str(&manifest_text(name, uuid, version, &[])).unwrap()
+}
+
+/// Creates an ip that is only known by its manifest.
+pub fn create_ip(name: &str, uuid: &str, version: &str) -> Ip {
+    Ip::from(IpPointer::from(create_manifest(name, uuid, version)))
+}
+
+/// Creates a catalog where each `(name, uuid, version)` is available.
+pub fn create_catalog<'a>(ips: &[(&str, &str, &str)]) -> Catalog<'a> {
+    let mut catalog = Catalog::new();
+    for (name, uuid, version) in ips {
+        let ip = create_ip(name, uuid, version);
+        let id = ip.get_uuid().clone();
+        match catalog.inner_mut().get_mut(&id) {
+            Some(lvl) => lvl.add_available(ip),
+            None => {
+                let mut lvl = IpLevel::new();
+                lvl.add_available(ip);
+                catalog.inner_mut().insert(id, lvl);
+            }
+        }
+    }
+    catalog
+}
+
+/// Writes the text of a lockfile for the `working` ip and its locked `deps`.
+pub fn lockfile_text(working: Locked, deps: &[Locked]) -> String {
+    let entry = |(name, uuid, version, reqs): &Locked, checksum: Option<&str>| {
+        format!(
+            "\n[[ip]]\nname = \"{}\"\nversion = \"{}\"\nuuid = \"{}\"\n{}dependencies = [{}]\n",
+            name,
+            version,
+            uuid,
+            checksum.map_or(String::new(), |c| format!("checksum = \"{}\"\n", c)),
+            reqs.iter()
+                .map(|r| format!("\"{}\"", r))
+                .collect::<Vec<String>>()
+                .join(", ")
+        )
+    };
+    deps.iter().fold(
+        String::from("version = 1\n") + &entry(&working, None),
+        |acc, dep| acc + &entry(dep, Some(CHECKSUM)),
+    )
+}
+
+/// Creates a lockfile for the `working` ip and its locked `deps`.
+pub fn create_lockfile(working: Locked, deps: &[Locked]) -> LockFile {
+    LockFile::from_str(&lockfile_text(working, deps)).unwrap()
+}
+
+/// Writes an ip with the `manifest` text to `dir`, defining an entity for each
+/// name in `units`.
+pub fn write_ip(dir: &Path, manifest: &str, units: &[&str]) {
+    fs::create_dir_all(dir).unwrap();
+    fs::write(dir.join(IP_MANIFEST_FILE), manifest).unwrap();
+    for unit in units {
+        fs::write(
+            dir.join(format!("{}.vhd", unit)),
+            format!("entity {} is end entity;\n", unit),
+        )
+        .unwrap();
+    }
+}
+
+/// Writes the `manifest` text to its pointer's directory within the channel at
+/// `root`, returning that directory.
+pub fn write_pointer(root: &Path, manifest: &str) -> PathBuf {
+    let man = Manifest::from_str(manifest).unwrap();
+    let dir = root.join(
+        PointerSlot::new(
+            man.get_ip().get_name(),
+            man.get_ip().get_uuid(),
+            man.get_ip().get_version(),
+        )
+        .to_channel_dir(),
+    );
+    write_ip(&dir, manifest, &[]);
+    dir
+}
+
+/// Adds the ip `(name, uuid, version)` to the `catalog` as yanked from the
+/// channel at `root`.
+pub fn yank_version(catalog: &mut Catalog, root: &Path, name: &str, uuid: &str, version: &str) {
+    let dir = write_pointer(root, &manifest_text(name, uuid, version, &[]));
+    fs::write(dir.join(ORBIT_YANK_FILE), "").unwrap();
+    let ip = Ip::from(IpPointer::read(dir).unwrap());
+    let id = ip.get_uuid().clone();
+    match catalog.inner_mut().get_mut(&id) {
+        Some(lvl) => lvl.add_available(ip),
+        None => {
+            let mut lvl = IpLevel::new();
+            lvl.add_available(ip);
+            catalog.inner_mut().insert(id, lvl);
+        }
+    }
+}
@@ -91,19 +91,20 @@ pub fn is_compatible(pv: &PartialVersion, ver: &Version) -> bool {
 
 /// Finds the most compatible version matching `ver` among the possible `space`.
 ///
+/// Versions listed in `yanked` are skipped unless `ver` exactly requests one
+/// of them.
+///
 /// Errors if no version was found.
 pub fn get_target_version<'a>(
     ver: &AnyVersion,
     space: &'a Vec<&Version>,
+    yanked: &[Version],
 ) -> Result<Version, Fault> {
     // find the specified version for the given ip
     let mut latest_version: Option<&Version> = None;
     space
         .into_iter()
-        .filter(|f| match &ver {
-            AnyVersion::Specific(v) => crate::core::version::is_compatible(v, f),
-            AnyVersion::Latest => true,
-        })
+        .filter(|f| ver.matches(f) && (ver.is_exact() == true || yanked.contains(f) == false))
         .for_each(|tag| {
             if latest_version.is_none() || *tag > latest_version.as_ref().unwrap() {
                 latest_version = Some(tag);
@@ -121,6 +122,7 @@ pub fn get_target_version<'a>(
 #[derive(Debug, Eq, Hash, Clone, PartialEq, Ord, PartialOrd)]
 pub enum AnyVersion {
     Specific(PartialVersion),
+    Requirement(VersionReq),
     Latest,
 }
 
@@ -129,6 +131,7 @@ impl std::fmt::Display for AnyVersion {
         match self {
             Self::Latest => write!(f, "latest"),
             Self::Specific(v) => write!(f, "{}", v),
+            Self::Requirement(r) => write!(f, "{}", r),
         }
     }
 }
@@ -139,7 +142,7 @@ impl std::str::FromStr for AnyVersion {
         if crate::util::strcmp::cmp_ascii_ignore_case(s, "latest") {
             Ok(Self::Latest)
         } else {
-            Ok(Self::Specific(PartialVersion::from_str(s)?))
+            Ok(Self::from(VersionReq::from_str(s)?))
         }
     }
 }
@@ -155,6 +158,34 @@ impl AnyVersion {
     pub fn is_latest(&self) -> bool {
         self == &Self::Latest
     }
+
+    /// Checks if this version can only be fulfilled by a single full version.
+    pub fn is_exact(&self) -> bool {
+        match self {
+            Self::Specific(v) => v.is_fully_qualified(),
+            Self::Requirement(r) => r.as_exact_version().is_some(),
+            Self::Latest => false,
+        }
+    }
+
+    /// Checks if the full version `ver` fulfills this version.
+    pub fn matches(&self, ver: &Version) -> bool {
+        match self {
+            Self::Specific(v) => is_compatible(v, ver),
+            Self::Requirement(r) => r.matches(ver),
+            Self::Latest => true,
+        }
+    }
+}
+
+impl From<VersionReq> for AnyVersion {
+    /// Keeps a requirement of a single bare partial version as a specific version.
+    fn from(value: VersionReq) -> Self {
+        match value.as_partial_version() {
+            Some(v) => Self::Specific(v.clone()),
+            None => Self::Requirement(value),
+        }
+    }
 }
 
 impl From<&Version> for AnyVersion {
@@ -240,7 +271,6 @@ impl PartialVersion {
     }
 
     /// Checks if there are 3 specified version numbers.
-    #[cfg(test)]
     pub fn is_fully_qualified(&self) -> bool {
         self.minor.is_some() && self.micro.is_some()
     }
@@ -601,6 +631,264 @@ impl Display for Version {
     }
 }
 
+/// The operator that determines how a [Comparator] restricts a version.
+#[derive(Debug, PartialEq, PartialOrd, Clone, Eq, Ord, Hash)]
+pub enum Op {
+    /// A bare partial version (`1.2`), which has the same major version and
+    /// any specified minor and micro versions.
+    Compatible,
+    /// `=1.2.3`
+    Exact,
+    /// `>1.2.3`
+    Greater,
+    /// `>=1.2.3`
+    GreaterEq,
+    /// `<1.2.3`
+    Less,
+    /// `<=1.2.3`
+    LessEq,
+    /// `~1.2.3`
+    Tilde,
+    /// `^1.2.3`
+    Caret,
+}
+
+impl Op {
+    fn as_str(&self) -> &str {
+        match self {
+            Self::Compatible => "",
+            Self::Exact => "=",
+            Self::Greater => ">",
+            Self::GreaterEq => ">=",
+            Self::Less => "<",
+            Self::LessEq => "<=",
+            Self::Tilde => "~",
+            Self::Caret => "^",
+        }
+    }
+}
+
+/// A single operator and partial version pair within a [VersionReq].
+#[derive(Debug, PartialEq, PartialOrd, Clone, Eq, Ord, Hash)]
+pub struct Comparator {
+    op: Op,
+    version: PartialVersion,
+}
+
+impl Comparator {
+    /// Fills the missing levels of the partial version with zeros.
+    fn to_lower_bound(&self) -> Version {
+        Version::from(self.version.clone())
+    }
+
+    /// Checks if `ver` shares the same levels as the ones specified in the partial version.
+    fn is_same_levels(&self, ver: &Version) -> bool {
+        self.version.major == ver.major
+            && self.version.minor.map_or(true, |m| m == ver.minor)
+            && self.version.micro.map_or(true, |p| p == ver.micro)
+    }
+
+    /// Checks if the full version `ver` fulfills this comparator.
+    pub fn matches(&self, ver: &Version) -> bool {
+        let pv = &self.version;
+        let lower = self.to_lower_bound();
+        match self.op {
+            Op::Compatible => is_compatible(pv, ver),
+            Op::Exact => match pv.label {
+                Some(_) => ver == &lower,
+                None => self.is_same_levels(ver),
+            },
+            Op::Greater => match (pv.minor, pv.micro) {
+                (Some(_), Some(_)) => ver > &lower,
+                (Some(m), None) => (ver.major, ver.minor) > (pv.major, m),
+                _ => ver.major > pv.major,
+            },
+            Op::GreaterEq => ver >= &lower,
+            Op::Less => ver < &lower,
+            Op::LessEq => match (pv.minor, pv.micro) {
+                (Some(_), Some(_)) => ver <= &lower,
+                (Some(m), None) => (ver.major, ver.minor) <= (pv.major, m),
+                _ => ver.major <= pv.major,
+            },
+            Op::Tilde => {
+                ver >= &lower && ver.major == pv.major && pv.minor.map_or(true, |m| m == ver.minor)
+            }
+            Op::Caret => {
+                ver >= &lower
+                    && match (pv.major, pv.minor, pv.micro) {
+                        (0, Some(0), Some(p)) => ver.major == 0 && ver.minor == 0 && ver.micro == p,
+                        (0, Some(m), _) => ver.major == 0 && ver.minor == m,
+                        _ => ver.major == pv.major,
+                    }
+            }
+        }
+    }
+}
+
+impl FromStr for Comparator {
+    type Err = VersionError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim();
+        // operators sharing a prefix must be checked for the longest match first
+        let op = [
+            Op::GreaterEq,
+            Op::LessEq,
+            Op::Greater,
+            Op::Less,
+            Op::Exact,
+            Op::Tilde,
+            Op::Caret,
+        ]
+        .into_iter()
+        .find(|op| s.starts_with(op.as_str()))
+        .unwrap_or(Op::Compatible);
+        Ok(Self {
+            version: PartialVersion::from_str(&s[op.as_str().len()..])?,
+            op: op,
+        })
+    }
+}
+
+impl Display for Comparator {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}{}", self.op.as_str(), self.version)
+    }
+}
+
+/// A version requirement for a dependency, which is a comma-separated list of
+/// comparators that must all be fulfilled (`>=1.2.0, <1.5`).
+#[derive(Debug, PartialEq, PartialOrd, Clone, Eq, Ord, Hash)]
+pub struct VersionReq {
+    comparators: Vec<Comparator>,
+}
+
+impl VersionReq {
+    /// Checks if the full version `ver` fulfills every comparator.
+    ///
+    /// A version with a label is only considered if at least one comparator
+    /// explicitly specifies a label on that same version.
+    pub fn matches(&self, ver: &Version) -> bool {
+        if ver.has_label() == true
+            && self.comparators.iter().any(|c| {
+                c.op == Op::Compatible
+                    || (c.version.has_label() == true
+                        && c.to_lower_bound().label(None) == ver.clone().label(None))
+            }) == false
+        {
+            return false;
+        }
+        self.comparators.iter().all(|c| c.matches(ver))
+    }
+
+    /// References the partial version if the requirement is only a bare
+    /// partial version.
+    pub fn as_partial_version(&self) -> Option<&PartialVersion> {
+        match self.comparators.len() {
+            1 => {
+                let c = self.comparators.first().unwrap();
+                match c.op {
+                    Op::Compatible => Some(&c.version),
+                    _ => None,
+                }
+            }
+            _ => None,
+        }
+    }
+
+    /// Returns the version if the requirement is a single exact fully qualified
+    /// version (`=1.2.3`).
+    pub fn as_exact_version(&self) -> Option<&PartialVersion> {
+        match self.comparators.len() {
+            1 => {
+                let c = self.comparators.first().unwrap();
+                match c.op {
+                    Op::Exact if c.version.is_fully_qualified() == true => Some(&c.version),
+                    _ => None,
+                }
+            }
+            _ => None,
+        }
+    }
+}
+
+impl From<PartialVersion> for VersionReq {
+    fn from(value: PartialVersion) -> Self {
+        Self {
+            comparators: vec![Comparator {
+                op: Op::Compatible,
+                version: value,
+            }],
+        }
+    }
+}
+
+impl FromStr for VersionReq {
+    type Err = VersionError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim();
+        if s.is_empty() {
+            return Err(VersionError::EmptyVersion);
+        }
+        let comparators = s
+            .split(',')
+            .map(|c| match c.trim().is_empty() {
+                true => Err(VersionError::EmptyComparator),
+                false => Comparator::from_str(c),
+            })
+            .collect::<Result<Vec<Comparator>, VersionError>>()?;
+        Ok(Self {
+            comparators: comparators,
+        })
+    }
+}
+
+impl Display for VersionReq {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let list: Vec<String> = self.comparators.iter().map(|c| c.to_string()).collect();
+        write!(f, "{}", list.join(", "))
+    }
+}
+
+impl<'de> Deserialize<'de> for VersionReq {
+    fn deserialize<D>(deserializer: D) -> Result<VersionReq, D::Error>
+    where
+        D: de::Deserializer<'de>,
+    {
+        struct LayerVisitor;
+
+        impl<'de> de::Visitor<'de> for LayerVisitor {
+            type Value = VersionReq;
+
+            fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+                formatter.write_str("a version requirement")
+            }
+
+            fn visit_str<E>(self, v: &str) -> Result<Self::Value, E>
+            where
+                E: de::Error,
+            {
+                match VersionReq::from_str(v) {
+                    Ok(v) => Ok(v),
+                    Err(e) => Err(de::Error::custom(e)),
+                }
+            }
+        }
+
+        deserializer.deserialize_map(LayerVisitor)
+    }
+}
+
+impl Serialize for VersionReq {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.serialize_str(&self.to_string())
+    }
+}
+
 #[derive(Debug, PartialEq)]
 pub enum VersionError {
     EmptyVersion,
@@ -612,6 +900,7 @@ pub enum VersionError {
     InvalidDigit(ParseIntError),
     EmptyLabel,
     InvalidChar(char),
+    EmptyComparator,
 }
 
 impl std::error::Error for VersionError {}
@@ -632,6 +921,7 @@ impl Display for VersionError {
             InvalidChar(c) => write!(f, "invalid character '{}' in version label", c),
             ExtraLevels(l) => write!(f, "too many version positions; found {} expected 3", l),
             InvalidDigit(_) => write!(f, "invalid digit in version"),
+            EmptyComparator => write!(f, "empty comparator in version requirement"),
         }
     }
 }
@@ -1023,4 +1313,175 @@ mod test {
         assert_eq!(v0.in_domain(&v1), true);
         assert_eq!(v1.in_domain(&v0), true);
     }
+
+    mod version_req {
+        use super::*;
+
+        fn v(s: &str) -> Version {
+            Version::from_str(s).unwrap()
+        }
+
+        fn req(s: &str) -> VersionReq {
+            VersionReq::from_str(s).unwrap()
+        }
+
+        #[test]
+        fn from_str() {
+            assert_eq!(req(">=1.2.0, <1.5").to_string(), ">=1.2.0, <1.5");
+            assert_eq!(req("  ~1.2 ").to_string(), "~1.2");
+            assert_eq!(
+                req("1.2").as_partial_version(),
+                Some(&PartialVersion::new().major(1).minor(2))
+            );
+            assert_eq!(req("=1.2").as_partial_version(), None);
+            assert_eq!(VersionReq::from_str(""), Err(VersionError::EmptyVersion));
+            assert_eq!(
+                VersionReq::from_str(">=1.2,"),
+                Err(VersionError::EmptyComparator)
+            );
+            assert_eq!(VersionReq::from_str(">=").is_err(), true);
+            assert_eq!(VersionReq::from_str("!1.2").is_err(), true);
+        }
+
+        #[test]
+        fn matches_compatible() {
+            let r = req("1.2");
+            assert_eq!(r.matches(&v("1.2.9")), true);
+            assert_eq!(r.matches(&v("1.3.0")), false);
+            assert_eq!(r.matches(&v("2.2.0")), false);
+        }
+
+        #[test]
+        fn matches_range() {
+            let r = req(">=1.2.0, <1.5");
+            assert_eq!(r.matches(&v("1.1.9")), false);
+            assert_eq!(r.matches(&v("1.2.0")), true);
+            assert_eq!(r.matches(&v("1.4.12")), true);
+            assert_eq!(r.matches(&v("1.5.0")), false);
+
+            let r = req(">1.4.2, <2");
+            assert_eq!(r.matches(&v("1.4.2")), false);
+            assert_eq!(r.matches(&v("1.4.3")), true);
+            assert_eq!(r.matches(&v("1.9.0")), true);
+            assert_eq!(r.matches(&v("2.0.0")), false);
+
+            let r = req(">1.2");
+            assert_eq!(r.matches(&v("1.2.7")), false);
+            assert_eq!(r.matches(&v("1.3.0")), true);
+
+            let r = req("<=1.2");
+            assert_eq!(r.matches(&v("1.2.7")), true);
+            assert_eq!(r.matches(&v("1.3.0")), false);
+        }
+
+        #[test]
+        fn matches_exact() {
+            let r = req("=1.2.3");
+            assert_eq!(r.matches(&v("1.2.3")), true);
+            assert_eq!(r.matches(&v("1.2.4")), false);
+
+            let r = req("=1.2");
+            assert_eq!(r.matches(&v("1.2.4")), true);
+            assert_eq!(r.matches(&v("1.3.0")), false);
+        }
+
+        #[test]
+        fn matches_tilde() {
+            let r = req("~1.2.3");
+            assert_eq!(r.matches(&v("1.2.2")), false);
+            assert_eq!(r.matches(&v("1.2.3")), true);
+            assert_eq!(r.matches(&v("1.2.9")), true);
+            assert_eq!(r.matches(&v("1.3.0")), false);
+
+            let r = req("~1");
+            assert_eq!(r.matches(&v("1.9.0")), true);
+            assert_eq!(r.matches(&v("2.0.0")), false);
+        }
+
+        #[test]
+        fn matches_caret() {
+            let r = req("^1.2.3");
+            assert_eq!(r.matches(&v("1.2.2")), false);
+            assert_eq!(r.matches(&v("1.9.0")), true);
+            assert_eq!(r.matches(&v("2.0.0")), false);
+
+            let r = req("^0.3");
+            assert_eq!(r.matches(&v("0.3.0")), true);
+            assert_eq!(r.matches(&v("0.3.7")), true);
+            assert_eq!(r.matches(&v("0.4.0")), false);
+
+            let r = req("^0.0.3");
+            assert_eq!(r.matches(&v("0.0.3")), true);
+            assert_eq!(r.matches(&v("0.0.4")), false);
+        }
+
+        #[test]
+        fn matches_label() {
+            let r = req(">=1.2.0");
+            assert_eq!(r.matches(&v("1.3.0-beta")), false);
+
+            let r = req(">=1.3.0-alpha");
+            assert_eq!(r.matches(&v("1.3.0-beta")), true);
+            assert_eq!(r.matches(&v("1.4.0-beta")), false);
+            assert_eq!(r.matches(&v("1.4.0")), true);
+        }
+
+        #[test]
+        fn any_version() {
+            assert_eq!(
+                AnyVersion::from_str("1.2").unwrap(),
+                AnyVersion::Specific(PartialVersion::new().major(1).minor(2))
+            );
+            assert_eq!(
+                AnyVersion::from_str("^1.2").unwrap(),
+                AnyVersion::Requirement(req("^1.2"))
+            );
+            let space = vec![v("1.2.0"), v("1.4.2"), v("1.4.3"), v("1.9.1"), v("2.0.0")];
+            let space: Vec<&Version> = space.iter().collect();
+            assert_eq!(
+                get_target_version(&AnyVersion::from_str(">1.4.2, <2").unwrap(), &space, &[])
+                    .unwrap(),
+                v("1.9.1")
+            );
+            assert_eq!(
+                get_target_version(&AnyVersion::from_str("~1.4").unwrap(), &space, &[]).unwrap(),
+                v("1.4.3")
+            );
+            assert_eq!(
+                get_target_version(&AnyVersion::from_str(">=3").unwrap(), &space, &[]).is_err(),
+                true
+            );
+        }
+
+        #[test]
+        fn target_version_skips_yanked() {
+            let space = vec![v("1.2.0"), v("1.4.2"), v("1.4.3"), v("2.0.0")];
+            let space: Vec<&Version> = space.iter().collect();
+            let yanked = vec![v("1.4.3")];
+            assert_eq!(
+                get_target_version(&AnyVersion::from_str("1").unwrap(), &space, &yanked).unwrap(),
+                v("1.4.2")
+            );
+            assert_eq!(
+                get_target_version(&AnyVersion::Latest, &space, &yanked).unwrap(),
+                v("2.0.0")
+            );
+            // exact versions (such as lockfile entries) still resolve to yanked versions
+            assert_eq!(
+                get_target_version(&AnyVersion::from_str("1.4.3").unwrap(), &space, &yanked)
+                    .unwrap(),
+                v("1.4.3")
+            );
+            assert_eq!(
+                get_target_version(&AnyVersion::from_str("=1.4.3").unwrap(), &space, &yanked)
+                    .unwrap(),
+                v("1.4.3")
+            );
+            assert_eq!(
+                get_target_version(&AnyVersion::from_str("~1.4.3").unwrap(), &space, &yanked)
+                    .is_err(),
+                true
+            );
+        }
+    }
 }
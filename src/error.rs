@@ -23,9 +23,10 @@ use crate::core::{
     ip::IpSpec,
     lang::{lexer::Position, LangIdentifier},
     pkgid::PkgPart,
-    version::{AnyVersion, PartialVersion, Version},
+    version::{AnyVersion, Version, VersionReq},
     visibility::Visibility,
 };
+use crate::util::seqalin;
 
 #[derive(Debug, PartialEq, thiserror::Error)]
 pub enum Error {
@@ -67,10 +68,14 @@ pub enum Error {
     ProtocolProcFailed(LastError),
     #[error("no protocol named {0:?}")]
     ProtocolNotFound(String),
+    #[error("failed to fetch {0:?} over git: {1}")]
+    GitProtocolFailed(String, LastError),
     #[error("failed to modify configuration: {0}")]
     ConfigNotSaved(LastError),
     #[error("configuration field {0:?} does not store a list")]
     ConfigFieldNotList(String),
+    #[error("unsupported key \"{0}\" cannot be edited{1}")]
+    ConfigUnsupportedKey(String, Hint),
     #[error("failed to process value {1:?} for configuration field \"include\" at {0:?}: {2}")]
     ConfigIncludeFailed(String, String, LastError),
     #[error("failed to load configuration file at {0:?}: {1}")]
@@ -100,7 +105,7 @@ pub enum Error {
     #[error("failed to parse ip name: {0}")]
     IpNameParseFailed(LastError),
     #[error("listed version {0} does not match ip's actual version {1}")]
-    DependencyIpRelativeBadVersion(PartialVersion, Version),
+    DependencyIpRelativeBadVersion(VersionReq, Version),
     #[error("listed name {0} does not match ip's actual name {1}")]
     DependencyIpRelativeBadName(PkgPart, PkgPart),
     #[error("failed to load lockfile: {0}")]
@@ -121,8 +126,40 @@ pub enum Error {
     EntryUnknownIp(IpSpec),
     #[error("cannot disambiguate between {0} ips downloaded{1}")]
     DownloadFoundManyIps(usize, Hint),
+    #[error("failed to detect the archive format of the download from {0:?} (expected a zip, tar, or tar.gz archive)")]
+    DownloadUnknownArchive(String),
+    #[error("failed to find an Orbit.toml file in the archive downloaded from {0:?}")]
+    DownloadMissingManifest(String),
+    #[error("failed to download {0:?} (attempts: {1}): {2}")]
+    DownloadFailed(String, u32, LastError),
     #[error("lockfile is missing or out of date{0}")]
     PublishMissingLockfile(Hint),
+    #[error("lockfile is missing or out of date{0}")]
+    LockfileOutOfDate(Hint),
+    #[error("ip \"{0}\" is not a dependency listed in the lockfile")]
+    UpdateUnknownEntry(PkgPart),
+    #[error("ip {0} requires {1} which is not in the lockfile{2}")]
+    UpdateRequiresLock(IpSpec, String, Hint),
+    #[error("ip \"{0}\" is not a dependency listed in the manifest")]
+    DependencyNotFound(PkgPart),
+    #[error(
+        "option \"--uuid\" cannot be used with \"--path\" because the ip is found by its path"
+    )]
+    AddUuidWithPath,
+    #[error(
+        "ip {0} cannot be removed because it is installed in the read-only shared cache {1:?}"
+    )]
+    RemoveSharedInstall(IpSpec, PathBuf),
+    #[error("manifest lists dependencies that are never used: {0}{1}")]
+    UnusedDependencies(String, Hint),
+    #[error("new version {0} must be greater than the current version {1}")]
+    VersionNotIncreasing(Version, Version),
+    #[error("failed to create git tag: {0}")]
+    VersionTagFailed(LastError),
+    #[error("failed to create git tag: uncommitted changes to {0}")]
+    VersionUncommittedChanges(String),
+    #[error("no unit or ip named \"{0}\" exists in the dependency graph{1}")]
+    TreeInvertNotFound(String, Hint),
     #[error("the ip manifest's source field is required to publish, but is undefined")]
     PublishMissingSource,
     #[error("ip {0} is already published to at least one of the specified channels")]
@@ -145,8 +182,16 @@ pub enum Error {
     ChannelPathNotDir(PathBuf),
     #[error("ip has \"{0}\" listed as a relative dependency")]
     PublishRelativeDepExists(PkgPart),
+    #[error("version {1} breaks the interface of version {0} without a major version increment:\n\n{2}{3}")]
+    PublishBreakingChanges(Version, Version, String, Hint),
+    #[error(
+        "unable to compare interfaces with version {0} because its source code is not available{1}"
+    )]
+    PublishPreviousUnavailable(Version, Hint),
     #[error("failed to pass publish checkpoint: {0}")]
     PublishFailedCheckpoint(LastError),
+    #[error("ip {0} is not published to any of the channels {1}")]
+    YankNotPublished(IpSpec, String),
     #[error("cyclic dependency with local ip \"{0}\"")]
     CyclicDependencyIp(PkgPart),
     #[error("failed to get uuid for ip \"{0}\" due to missing or corrupted lockfile{1}")]
@@ -218,10 +263,38 @@ pub enum Hint {
     IncludeAllInPlan,
     SpecifyIpSpecForDownload,
     MakeLock,
+    ForceLock,
     PublishWithReady,
+    PublishAllowBreaking,
+    RemoveUnusedDeps,
     RegenerateLockfile,
     ShowVersions,
     ShowConfigFiles,
+    ShowConfigData,
+    ShowIpTree,
+    DidYouMean(Vec<String>),
+}
+
+impl Hint {
+    /// The most suggestions to display for a single hint.
+    const MAX_SUGGESTIONS: usize = 3;
+
+    /// Suggests the words from `bank` that are closest to `word`.
+    ///
+    /// Returns the `fallback` hint when no word is close enough.
+    pub fn did_you_mean<T: AsRef<str>>(word: &str, bank: &[T], fallback: Hint) -> Hint {
+        // allow longer words to tolerate more typos
+        let threshold = word.len() / 3 + 2;
+        let words: Vec<String> = seqalin::rank_min_edit_strs(word, bank, threshold)
+            .into_iter()
+            .take(Self::MAX_SUGGESTIONS)
+            .map(|w| w.to_string())
+            .collect();
+        match words.is_empty() {
+            true => fallback,
+            false => Self::DidYouMean(words),
+        }
+    }
 }
 
 impl Display for Hint {
@@ -231,6 +304,17 @@ impl Display for Hint {
                 "use `orbit view {0} --units` to display available units",
                 spec
             )),
+            Self::DidYouMean(words) => Some(match words.len() {
+                1 => format!("did you mean \"{}\"?", words[0]),
+                _ => format!(
+                    "did you mean one of {}?",
+                    words
+                        .iter()
+                        .map(|w| format!("\"{}\"", w))
+                        .collect::<Vec<String>>()
+                        .join(", ")
+                ),
+            }),
             _ => None,
         };
         let message = match self {
@@ -259,12 +343,18 @@ impl Display for Hint {
                 "consider providing the ip specification for the requested ip to download"
             }
             Self::MakeLock => "use `orbit lock` to generate the latest lockfile for this ip",
+            Self::ForceLock => "use `orbit lock --force` to resolve the lockfile again",
             Self::PublishWithReady => "use the \"--ready\" flag to publish the ip to its channels",
+            Self::PublishAllowBreaking => "use `orbit version major` to increment the major version or use the \"--allow-breaking\" flag to publish anyway",
+            Self::RemoveUnusedDeps => "use `orbit rm <ip>` to remove a dependency from the manifest",
             Self::RegenerateLockfile => "verify the ip's lockfile exists and is up to date",
             Self::ShowVersions => "use `orbit view <ip> --versions` to see all known versions",
             Self::ShowConfigFiles => {
                 "use `orbit config --list` to see the list of current configuration files"
             }
+            Self::ShowConfigData => "use `orbit config` to see the current configuration data",
+            Self::ShowIpTree => "use `orbit tree --ip` to see the ips in the dependency graph",
+            Self::DidYouMean(_) => mixed_prompt.as_ref().unwrap(),
             Self::PublishSyncRemote => {
                 "check that the local ip's contents matches the source's contents"
             }
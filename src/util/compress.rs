@@ -15,7 +15,8 @@
 //  along with this program.  If not, see <http://www.gnu.org/licenses/>.
 //
 
-//! Abstraction layer for writing a directory to a zip file
+//! Abstraction layer for writing a directory to a zip file and for extracting
+//! downloaded archives.
 //!
 //! Reference: https://github.com/zip-rs/zip/blob/master/examples/write_dir.rs
 
@@ -100,3 +101,128 @@ pub fn write_zip_dir(src_dir: &PathBuf, dst_file: &PathBuf) -> zip::result::ZipR
 
     Ok(())
 }
+
+use super::anyerror::Fault;
+use flate2::read::GzDecoder;
+use tar::Archive;
+use zip::ZipArchive;
+
+/// The kinds of archives that can be extracted from a download.
+#[derive(Debug, PartialEq)]
+pub enum ArchiveFormat {
+    Zip,
+    Tar,
+    TarGz,
+}
+
+impl ArchiveFormat {
+    /// Determines the format from the leading bytes of the archive's contents,
+    /// and then from the file extension of its `name` if the contents are not
+    /// recognized.
+    pub fn detect(bytes: &[u8], name: &str) -> Option<Self> {
+        if bytes.starts_with(b"PK\x03\x04") || bytes.starts_with(b"PK\x05\x06") {
+            return Some(Self::Zip);
+        }
+        if bytes.starts_with(&[0x1f, 0x8b]) {
+            return Some(Self::TarGz);
+        }
+        if bytes.len() >= 262 && &bytes[257..262] == b"ustar" {
+            return Some(Self::Tar);
+        }
+        // ignore any query or fragment when the name is a url
+        let name = name.split(['?', '#']).next().unwrap_or(name).to_lowercase();
+        if name.ends_with(".zip") == true {
+            Some(Self::Zip)
+        } else if name.ends_with(".tar.gz") == true || name.ends_with(".tgz") == true {
+            Some(Self::TarGz)
+        } else if name.ends_with(".tar") == true {
+            Some(Self::Tar)
+        } else {
+            None
+        }
+    }
+
+    /// Unpacks the archive read from `reader` into the directory `dst`.
+    pub fn extract<R: Read + Seek>(&self, reader: R, dst: &Path) -> Result<(), Fault> {
+        match self {
+            Self::Zip => ZipArchive::new(reader)?.extract(dst)?,
+            Self::Tar => Archive::new(reader).unpack(dst)?,
+            Self::TarGz => Archive::new(GzDecoder::new(reader)).unpack(dst)?,
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use flate2::write::GzEncoder;
+    use flate2::Compression;
+
+    /// Creates a tar archive holding a single file at `path` with `contents`.
+    fn tar_bytes(path: &str, contents: &str) -> Vec<u8> {
+        let mut builder = tar::Builder::new(Vec::new());
+        let mut header = tar::Header::new_gnu();
+        header.set_size(contents.len() as u64);
+        header.set_mode(0o644);
+        header.set_cksum();
+        builder
+            .append_data(&mut header, path, contents.as_bytes())
+            .unwrap();
+        builder.into_inner().unwrap()
+    }
+
+    #[test]
+    fn detect_archive_format() {
+        let tar = tar_bytes("gates/Orbit.toml", "");
+        let mut gz = GzEncoder::new(Vec::new(), Compression::default());
+        gz.write_all(&tar).unwrap();
+        let tar_gz = gz.finish().unwrap();
+
+        // contents take priority over the extension
+        assert_eq!(
+            ArchiveFormat::detect(b"PK\x03\x04", "a.tar"),
+            Some(ArchiveFormat::Zip)
+        );
+        assert_eq!(
+            ArchiveFormat::detect(&tar_gz, "a.zip"),
+            Some(ArchiveFormat::TarGz)
+        );
+        assert_eq!(
+            ArchiveFormat::detect(&tar, "download"),
+            Some(ArchiveFormat::Tar)
+        );
+        // extensions are used for unrecognized contents
+        assert_eq!(
+            ArchiveFormat::detect(b"", "https://a.io/x.TGZ?raw=1"),
+            Some(ArchiveFormat::TarGz)
+        );
+        assert_eq!(
+            ArchiveFormat::detect(b"", "x.tar"),
+            Some(ArchiveFormat::Tar)
+        );
+        assert_eq!(
+            ArchiveFormat::detect(b"", "x.zip#main"),
+            Some(ArchiveFormat::Zip)
+        );
+        assert_eq!(ArchiveFormat::detect(b"<html>", "https://a.io/x"), None);
+    }
+
+    #[test]
+    fn extract_tar_gz() {
+        let mut gz = GzEncoder::new(Vec::new(), Compression::default());
+        gz.write_all(&tar_bytes("gates-1.0.0/Orbit.toml", "[ip]"))
+            .unwrap();
+        let bytes = gz.finish().unwrap();
+
+        let dir = tempfile::tempdir().unwrap();
+        ArchiveFormat::detect(&bytes, "")
+            .unwrap()
+            .extract(std::io::Cursor::new(&bytes), dir.path())
+            .unwrap();
+        assert_eq!(
+            std::fs::read_to_string(dir.path().join("gates-1.0.0/Orbit.toml")).unwrap(),
+            "[ip]"
+        );
+    }
+}
@@ -17,6 +17,7 @@
 
 /// Basic graph data structure
 /// - source: http://smallcultfollowing.com/babysteps/blog/2015/04/06/modeling-graphs-in-rust-using-vector-indices/
+use std::collections::HashMap;
 use std::collections::HashSet;
 
 use super::graphmap::GraphMap;
@@ -306,32 +307,156 @@ impl<V, E> Graph<V, E> {
 
     /// Recursively generates the in-order y-down list of nodes to print with their
     /// corresponding twig style and level of indentation.
-    fn recurse_treeview(&self, target: NodeIndex, level: Twig) -> Vec<(Twig, NodeIndex)> {
+    ///
+    /// When `inverted` is set, the successors are selected as the branches instead
+    /// of the predecessors. Branches are not expanded for nodes `limit` levels
+    /// below the root. When `visited` is given, only a node's first appearance is
+    /// expanded and every later appearance is left as a single line.
+    fn recurse_treeview(
+        &self,
+        target: NodeIndex,
+        level: Twig,
+        depth: usize,
+        inverted: bool,
+        limit: Option<usize>,
+        mut visited: Option<&mut HashSet<NodeIndex>>,
+    ) -> Vec<(Twig, NodeIndex)> {
         let mut traversal = Vec::new();
         // add target to the list
         traversal.push((level.clone(), target));
-        // select predecessors
-        let mut tunnels = self.predecessors(target).peekable();
+        // stop expanding at the depth limit
+        if limit.is_some_and(|l| depth >= l) == true {
+            return traversal;
+        }
+        // stop expanding branches that were already expanded
+        if let Some(v) = visited.as_deref_mut() {
+            if v.insert(target) == false {
+                return traversal;
+            }
+        }
+        // select predecessors (or successors)
+        let branches: Vec<NodeIndex> = match inverted {
+            false => self.predecessors(target).collect(),
+            true => self.successors(target).collect(),
+        };
+        let mut tunnels = branches.into_iter().peekable();
         while let Some(n) = tunnels.next() {
             // remember the order and parent branch type
             let twig_type = match tunnels.peek() {
                 Some(_) => Twig::MidBranch(Some(Box::new(level.clone()))),
                 None => Twig::EndLeaf(Some(Box::new(level.clone()))),
             };
-            traversal.append(&mut self.recurse_treeview(n, twig_type));
+            traversal.append(&mut self.recurse_treeview(
+                n,
+                twig_type,
+                depth + 1,
+                inverted,
+                limit,
+                visited.as_deref_mut(),
+            ));
         }
         traversal
     }
 
     /// Creates the in-order y-down list of nodes to display with their
     /// corresponding indentation depth and twig style.
-    pub fn treeview(&self, target: NodeIndex) -> Vec<(Twig, NodeIndex)> {
-        self.recurse_treeview(target, Twig::EndLeaf(None))
+    ///
+    /// Nodes more than `limit` levels below the `target` are omitted.
+    pub fn treeview(&self, target: NodeIndex, limit: Option<usize>) -> Vec<(Twig, NodeIndex)> {
+        self.recurse_treeview(target, Twig::EndLeaf(None), 0, false, limit, None)
+    }
+
+    /// Creates the in-order y-down list of nodes that depend on the `target`, with
+    /// their corresponding indentation depth and twig style.
+    ///
+    /// Nodes more than `limit` levels below the `target` are omitted.
+    pub fn inverted_treeview(
+        &self,
+        target: NodeIndex,
+        limit: Option<usize>,
+    ) -> Vec<(Twig, NodeIndex)> {
+        self.recurse_treeview(target, Twig::EndLeaf(None), 0, true, limit, None)
+    }
+
+    /// Creates the treeview of the `target` where only the first appearance of a
+    /// node keeps its branches and every later appearance is collapsed into a
+    /// single line.
+    ///
+    /// Each node is paired with its total number of instances below the `target`,
+    /// where `weight` returns the number of times a parent holds one of its
+    /// branches. Nodes more than `limit` levels below the `target` are omitted.
+    pub fn compress_treeview(
+        &self,
+        target: NodeIndex,
+        inverted: bool,
+        limit: Option<usize>,
+        weight: &dyn Fn(NodeIndex, NodeIndex) -> usize,
+    ) -> Vec<(Twig, NodeIndex, usize)> {
+        let mut visited = HashSet::new();
+        let tree = self.recurse_treeview(
+            target,
+            Twig::EndLeaf(None),
+            0,
+            inverted,
+            limit,
+            Some(&mut visited),
+        );
+        let counts = self.count_instances(target, inverted, weight);
+        tree.into_iter()
+            .map(|(twig, n)| {
+                let count = *counts.get(&n).unwrap_or(&1);
+                (twig, n, count)
+            })
+            .collect()
     }
 
-    /// Removes duplicate branches from the treeview and replaces them with labels.
-    pub fn compress_treeview(&self, _tree: &Vec<(Twig, NodeIndex)>) -> Vec<(Twig, NodeIndex)> {
-        todo!()
+    /// Computes the number of instances of every node reachable from the `target`.
+    ///
+    /// The count of a node is the sum over each of its parents of the parent's
+    /// count multiplied by the `weight` of their edge. The nodes are visited in
+    /// topological order so each edge is only evaluated once.
+    fn count_instances(
+        &self,
+        target: NodeIndex,
+        inverted: bool,
+        weight: &dyn Fn(NodeIndex, NodeIndex) -> usize,
+    ) -> HashMap<NodeIndex, usize> {
+        let branches = |n: NodeIndex| -> Vec<NodeIndex> {
+            match inverted {
+                false => self.predecessors(n).collect(),
+                true => self.successors(n).collect(),
+            }
+        };
+        // count the parents of each reachable node
+        let mut parents = HashMap::<NodeIndex, usize>::new();
+        let mut reached = HashSet::<NodeIndex>::new();
+        let mut processing = vec![target];
+        reached.insert(target);
+        while let Some(n) = processing.pop() {
+            for b in branches(n) {
+                *parents.entry(b).or_insert(0) += 1;
+                if reached.insert(b) == true {
+                    processing.push(b);
+                }
+            }
+        }
+        // accumulate the counts once all of a node's parents are resolved
+        let mut counts = HashMap::<NodeIndex, usize>::new();
+        counts.insert(target, 1);
+        let mut ready = vec![target];
+        while let Some(n) = ready.pop() {
+            let count = *counts.get(&n).unwrap();
+            for b in branches(n) {
+                let total = counts.entry(b).or_insert(0);
+                *total = total.saturating_add(count.saturating_mul(weight(n, b)));
+                let remaining = parents.get_mut(&b).unwrap();
+                *remaining -= 1;
+                if *remaining == 0 {
+                    ready.push(b);
+                }
+            }
+        }
+        counts
     }
 }
 
@@ -432,6 +557,17 @@ pub enum Twig {
 }
 
 impl Twig {
+    /// Computes the number of levels between the current `self` and the root.
+    pub fn get_depth(&self) -> usize {
+        let mut depth = 0;
+        let mut x = self;
+        while let Some(n) = x.get_upper() {
+            depth += 1;
+            x = n;
+        }
+        depth
+    }
+
     /// Accesses what type of node was the parent to the current `self`.
     pub fn get_upper(&self) -> Option<&Twig> {
         match self {
@@ -555,7 +691,7 @@ mod test {
     fn treeview() {
         let mut g = binary_tree();
         g.add_edge(4, 2, ());
-        let tree = g.treeview(0);
+        let tree = g.treeview(0, None);
         assert_eq!(
             tree_to_string(&tree),
             "\
@@ -602,7 +738,7 @@ mod test {
 
         graph.add_edge(h, g, ());
 
-        let tree = graph.treeview(z);
+        let tree = graph.treeview(z, None);
         assert_eq!(
             tree_to_string(&tree),
             "\
@@ -621,6 +757,146 @@ mod test {
 "
         );
     }
+
+    #[test]
+    fn inverted_treeview() {
+        let mut g = binary_tree();
+        g.add_edge(4, 2, ());
+        let tree = g.inverted_treeview(6, None);
+        assert_eq!(
+            tree_to_string(&tree),
+            "\
+6
+└─ 4
+   ├─ 2
+   │  └─ 1
+   │     └─ 0
+   └─ 0
+"
+        );
+    }
+
+    #[test]
+    fn treeview_depth_limit() {
+        let mut g = binary_tree();
+        g.add_edge(4, 2, ());
+        let tree = g.treeview(0, Some(2));
+        assert_eq!(
+            tree_to_string(&tree),
+            "\
+0
+├─ 4
+│  ├─ 6
+│  └─ 5
+└─ 1
+   ├─ 3
+   └─ 2
+"
+        );
+    }
+
+    /// Transforms a compressed tree into a string for easier verification.
+    fn compressed_to_string(t: &Vec<(Twig, usize, usize)>) -> String {
+        t.iter()
+            .fold(String::new(), |acc, (twig, n, count)| match count {
+                1 => acc + &format!("{}{}\n", twig, n),
+                _ => acc + &format!("{}{} (x{})\n", twig, n, count),
+            })
+    }
+
+    #[test]
+    fn compress_treeview() {
+        let mut g = binary_tree();
+        g.add_edge(4, 2, ());
+        let compressed = g.compress_treeview(0, false, None, &|_, _| 1);
+        assert_eq!(
+            compressed_to_string(&compressed),
+            "\
+0
+├─ 4 (x2)
+│  ├─ 6 (x2)
+│  └─ 5 (x2)
+└─ 1
+   ├─ 3
+   └─ 2
+      └─ 4 (x2)
+"
+        );
+    }
+
+    #[test]
+    fn compress_treeview_counts_instances() {
+        let mut g = binary_tree();
+        g.add_edge(4, 2, ());
+        // 1 holds 2 instances of 2, 2 holds 3 instances of 4, and 4 holds 4 instances of 6
+        let weight = |parent: usize, branch: usize| match (parent, branch) {
+            (1, 2) => 2,
+            (2, 4) => 3,
+            (4, 6) => 4,
+            _ => 1,
+        };
+        let compressed = g.compress_treeview(0, false, None, &weight);
+        assert_eq!(
+            compressed_to_string(&compressed),
+            "\
+0
+├─ 4 (x7)
+│  ├─ 6 (x28)
+│  └─ 5 (x7)
+└─ 1
+   ├─ 3
+   └─ 2 (x2)
+      └─ 4 (x7)
+"
+        );
+        // the counts do not depend on the depth limit
+        let compressed = g.compress_treeview(0, false, Some(1), &weight);
+        assert_eq!(
+            compressed_to_string(&compressed),
+            "\
+0
+├─ 4 (x7)
+└─ 1
+"
+        );
+    }
+
+    #[test]
+    fn compress_treeview_expands_below_depth_limit() {
+        let mut g = Graph::<(), ()>::new();
+        let n0 = g.add_node(());
+        let n1 = g.add_node(());
+        let n2 = g.add_node(());
+        let n3 = g.add_node(());
+        g.add_edge(n2, n0, ());
+        g.add_edge(n1, n0, ());
+        g.add_edge(n2, n1, ());
+        g.add_edge(n3, n2, ());
+        // 2 is first reached at the depth limit, so its branches are expanded later
+        let compressed = g.compress_treeview(n0, false, Some(2), &|_, _| 1);
+        assert_eq!(
+            compressed_to_string(&compressed),
+            "\
+0
+├─ 1
+│  └─ 2 (x2)
+└─ 2 (x2)
+   └─ 3 (x2)
+"
+        );
+    }
+
+    #[test]
+    fn twig_depth() {
+        let g = binary_tree();
+        let depths: Vec<usize> = g
+            .treeview(0, None)
+            .iter()
+            .map(|t| t.0.get_depth())
+            .collect();
+        assert_eq!(depths, vec![0, 1, 2, 2, 1, 2, 2]);
+    }
+
     /* --ascii version
     0
     \─ 1
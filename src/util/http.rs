@@ -0,0 +1,553 @@
+//
+//  Copyright (C) 2022-2025  Chase Ruskin
+//
+//  This program is free software: you can redistribute it and/or modify
+//  it under the terms of the GNU General Public License as published by
+//  the Free Software Foundation, either version 3 of the License, or
+//  (at your option) any later version.
+//
+//  This program is distributed in the hope that it will be useful,
+//  but WITHOUT ANY WARRANTY; without even the implied warranty of
+//  MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+//  GNU General Public License for more details.
+//
+//  You should have received a copy of the GNU General Public License
+//  along with this program.  If not, see <http://www.gnu.org/licenses/>.
+//
+
+//! Transfers files over the network according to the user's download settings.
+//!
+//! Transfers are streamed to disk and retried with an increasing delay when
+//! they fail from a network issue. A retried transfer resumes from the bytes
+//! already written by the previous attempt, and files kept at a [partial_path]
+//! resume from the bytes written by a previous run. An HTTP(S) transfer only
+//! resumes when the server still identifies the contents by the same validator
+//! (its ETag or Last-Modified date), which is kept in a file next to the bytes.
+
+use crate::commands::orbit::UpgradeError;
+use crate::core::config::DownloadSettings;
+use crate::error::Error;
+use crate::error::LastError;
+use crate::util::anyerror::Fault;
+use crate::util::sha256;
+use colored::Colorize;
+use curl::easy::{Easy, List};
+use std::cell::{Cell, RefCell};
+use std::fs::{self, OpenOptions};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+/// The directory within the downloads path that keeps unfinished transfers.
+const PARTIAL_DIR: &str = ".partial";
+
+/// The extension appended to a file's name to keep the validator of its contents.
+const VALIDATOR_EXT: &str = "validator";
+
+/// Cancel a transfer that receives less than this many bytes per second ...
+const LOW_SPEED_LIMIT: u32 = 1;
+/// ... for this long.
+const LOW_SPEED_TIME: Duration = Duration::from_secs(60);
+
+/// The reason a single transfer attempt failed.
+#[derive(Debug)]
+enum Failure {
+    Transfer(curl::Error),
+    Status(u32),
+    Disk(std::io::Error),
+}
+
+impl Failure {
+    /// Checks if another attempt could succeed.
+    fn is_retryable(&self) -> bool {
+        match self {
+            Self::Transfer(e) => {
+                e.is_operation_timedout()
+                    || e.is_couldnt_connect()
+                    || e.is_couldnt_resolve_host()
+                    || e.is_couldnt_resolve_proxy()
+                    || e.is_recv_error()
+                    || e.is_send_error()
+                    || e.is_partial_file()
+                    || e.is_got_nothing()
+                    || e.is_ssl_connect_error()
+                    || e.is_http2_error()
+                    || e.is_range_error()
+            }
+            Self::Status(rc) => is_retryable_status(*rc),
+            Self::Disk(_) => false,
+        }
+    }
+
+    /// Checks if the bytes already written cannot be resumed from.
+    fn is_range_rejected(&self) -> bool {
+        match self {
+            Self::Transfer(e) => e.is_range_error(),
+            Self::Status(rc) => *rc == 416,
+            Self::Disk(_) => false,
+        }
+    }
+
+    fn into_fault(self, url: &str, attempts: u32) -> Fault {
+        match self {
+            Self::Transfer(e) => Box::new(Error::DownloadFailed(
+                url.to_string(),
+                attempts,
+                LastError(e.to_string()),
+            )),
+            Self::Status(rc) => Box::new(UpgradeError::FailedConnection(url.to_string(), rc)),
+            Self::Disk(e) => Box::new(e),
+        }
+    }
+}
+
+impl std::fmt::Display for Failure {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Transfer(e) => write!(f, "{}", e),
+            Self::Status(rc) => write!(f, "server responded with status {}", rc),
+            Self::Disk(e) => write!(f, "{}", e),
+        }
+    }
+}
+
+/// Checks if a server's response `status` indicates a temporary problem.
+fn is_retryable_status(status: u32) -> bool {
+    status == 408 || status == 416 || status == 429 || status >= 500
+}
+
+/// Checks if a transfer with the response `status` succeeded.
+///
+/// Protocols other than HTTP(S), such as `file://`, do not report a status.
+fn is_success_status(url: &str, status: u32) -> bool {
+    (200..300).contains(&status) || (status == 0 && url.starts_with("http") == false)
+}
+
+/// Returns the proxy to use for `url`, preferring the configured `proxy` over
+/// the standard environment variables read by `env`.
+fn resolve_proxy<F>(url: &str, proxy: Option<&String>, env: F) -> Option<String>
+where
+    F: Fn(&str) -> Option<String>,
+{
+    if let Some(p) = proxy {
+        return Some(p.clone());
+    }
+    let keys: &[&str] = match url.to_lowercase().starts_with("https://") {
+        true => &["https_proxy", "HTTPS_PROXY", "all_proxy", "ALL_PROXY"],
+        // the uppercase variant is ignored for http to match curl's behavior
+        false => &["http_proxy", "all_proxy", "ALL_PROXY"],
+    };
+    keys.iter()
+        .filter_map(|k| env(k))
+        .find(|v| v.is_empty() == false)
+}
+
+/// Returns the hosts that skip the proxy, preferring the configured `no_proxy`
+/// over the standard environment variables read by `env`.
+fn resolve_no_proxy<F>(no_proxy: Option<&String>, env: F) -> Option<String>
+where
+    F: Fn(&str) -> Option<String>,
+{
+    if let Some(n) = no_proxy {
+        return Some(n.clone());
+    }
+    ["no_proxy", "NO_PROXY"].iter().find_map(|k| env(k))
+}
+
+/// Returns the file within the `downloads` directory that holds the bytes
+/// transferred so far for `url`.
+///
+/// The file is named by the checksum of the url, so downloading the same url
+/// again resumes from where an interrupted run stopped.
+pub fn partial_path(downloads: &Path, url: &str) -> PathBuf {
+    downloads
+        .join(PARTIAL_DIR)
+        .join(sha256::compute_sha256(url.as_bytes()).to_string())
+}
+
+/// Returns the file next to `dst` that keeps the validator of its contents.
+fn validator_path(dst: &Path) -> PathBuf {
+    let mut name = dst.file_name().unwrap_or_default().to_os_string();
+    name.push(".");
+    name.push(VALIDATOR_EXT);
+    dst.with_file_name(name)
+}
+
+/// Reads the validator from a response `header`, if it has one that can be
+/// sent back in an `If-Range` request.
+///
+/// Returns the header's name and value. Weak ETags cannot be used to resume a
+/// transfer.
+fn read_validator(header: &str) -> Option<(String, String)> {
+    let (name, value) = header.split_once(':')?;
+    let name = name.trim().to_lowercase();
+    let value = value.trim();
+    match name.as_str() {
+        "etag" if value.starts_with("W/") == false => Some((name, value.to_string())),
+        "last-modified" => Some((name, value.to_string())),
+        _ => None,
+    }
+}
+
+/// Creates a handle for `url` configured by the download `settings`.
+///
+/// Redirects are only followed when `follow` is `true`.
+fn create_handle(
+    url: &str,
+    settings: &DownloadSettings,
+    headers: &[&str],
+    follow: bool,
+) -> Result<Easy, curl::Error> {
+    let mut easy = Easy::new();
+    easy.url(url)?;
+    easy.follow_location(follow)?;
+    if let Some(t) = settings.get_connect_timeout() {
+        easy.connect_timeout(t)?;
+    }
+    if let Some(t) = settings.get_timeout() {
+        easy.timeout(t)?;
+    }
+    // treat a stalled connection as a failed transfer
+    easy.low_speed_limit(LOW_SPEED_LIMIT)?;
+    easy.low_speed_time(LOW_SPEED_TIME)?;
+    let env = |k: &str| std::env::var(k).ok();
+    if let Some(p) = resolve_proxy(url, settings.get_proxy(), env) {
+        easy.proxy(&p)?;
+    }
+    if let Some(n) = resolve_no_proxy(settings.get_no_proxy(), env) {
+        easy.noproxy(&n)?;
+    }
+    if let Some(ca) = settings.get_ca_bundle() {
+        easy.cainfo(ca)?;
+    }
+    if headers.is_empty() == false {
+        let mut list = List::new();
+        for h in headers {
+            list.append(h)?;
+        }
+        easy.http_headers(list)?;
+    }
+    Ok(easy)
+}
+
+/// Performs a single attempt to write the contents at `url` to the end of the
+/// file `dst`, requesting only the bytes not already written.
+///
+/// For HTTP(S), the bytes already written are only resumed from if the server
+/// still has the validator stored next to `dst`. Otherwise, the server sends
+/// the entire contents and `dst` is written again from the start.
+fn transfer(
+    url: &str,
+    dst: &Path,
+    settings: &DownloadSettings,
+    headers: &[&str],
+    follow: bool,
+) -> Result<(), Failure> {
+    let is_http = url.starts_with("http");
+    let sidecar = validator_path(dst);
+    let mut written = fs::metadata(dst).map(|m| m.len()).unwrap_or(0);
+    let mut headers: Vec<String> = headers.iter().map(|h| h.to_string()).collect();
+    if written > 0 && is_http == true {
+        match fs::read_to_string(&sidecar) {
+            // the range is requested directly so a changed file is sent in full
+            Ok(validator) if validator.trim().is_empty() == false => {
+                headers.push(format!("Range: bytes={}-", written));
+                headers.push(format!("If-Range: {}", validator.trim()));
+            }
+            // the bytes cannot be verified to belong to the same contents
+            _ => {
+                fs::write(dst, "").map_err(Failure::Disk)?;
+                written = 0;
+            }
+        }
+    }
+    let headers: Vec<&str> = headers.iter().map(|h| h.as_str()).collect();
+    let mut easy = create_handle(url, settings, &headers, follow).map_err(Failure::Transfer)?;
+    if written > 0 && is_http == false {
+        easy.resume_from(written).map_err(Failure::Transfer)?;
+    }
+    let mut file = OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(dst)
+        .map_err(Failure::Disk)?;
+
+    // track the status and validator of the latest response (redirects send several)
+    let status = Cell::new(0);
+    let validator: RefCell<Option<(String, String)>> = RefCell::new(None);
+    let mut disk_error = None;
+    let mut restarted = false;
+    let mut saved = false;
+    let result = {
+        let mut transfer = easy.transfer();
+        transfer
+            .header_function(|header| {
+                let header = match std::str::from_utf8(header) {
+                    Ok(h) => h,
+                    Err(_) => return true,
+                };
+                if let Some(code) = Some(header)
+                    .filter(|h| h.starts_with("HTTP/"))
+                    .and_then(|h| h.split_whitespace().nth(1))
+                    .and_then(|c| c.parse().ok())
+                {
+                    status.set(code);
+                    validator.replace(None);
+                } else if let Some((name, value)) = read_validator(header) {
+                    // prefer the ETag over the Last-Modified date
+                    let mut current = validator.borrow_mut();
+                    if current.as_ref().is_none_or(|(n, _)| n != "etag") {
+                        *current = Some((name, value));
+                    }
+                }
+                true
+            })
+            .map_err(Failure::Transfer)?;
+        transfer
+            .write_function(|data| {
+                // discard the bodies of error responses
+                if status.get() != 0 && (200..300).contains(&status.get()) == false {
+                    return Ok(data.len());
+                }
+                // remember which contents are being written before writing them
+                if is_http == true && saved == false {
+                    saved = true;
+                    let value = validator.borrow().as_ref().map(|(_, v)| v.clone());
+                    let stored = match value {
+                        Some(v) => fs::write(&sidecar, v),
+                        None => fs::remove_file(&sidecar).or_else(|e| match e.kind() {
+                            std::io::ErrorKind::NotFound => Ok(()),
+                            _ => Err(e),
+                        }),
+                    };
+                    if let Err(e) = stored {
+                        disk_error = Some(e);
+                        return Ok(0);
+                    }
+                }
+                // the server ignored the range request and is sending everything
+                if written > 0 && status.get() == 200 && restarted == false {
+                    restarted = true;
+                    if let Err(e) = file.set_len(0) {
+                        disk_error = Some(e);
+                        return Ok(0);
+                    }
+                }
+                match file.write_all(data) {
+                    Ok(()) => Ok(data.len()),
+                    Err(e) => {
+                        disk_error = Some(e);
+                        // returning fewer bytes than received aborts the transfer
+                        Ok(0)
+                    }
+                }
+            })
+            .map_err(Failure::Transfer)?;
+        transfer.perform()
+    };
+    if let Some(e) = disk_error {
+        return Err(Failure::Disk(e));
+    }
+    result.map_err(Failure::Transfer)?;
+    let rc = easy.response_code().map_err(Failure::Transfer)?;
+    match is_success_status(url, rc) {
+        true => Ok(()),
+        false => Err(Failure::Status(rc)),
+    }
+}
+
+/// Downloads the contents at `url` into the file `dst`.
+///
+/// Any bytes already in `dst` are resumed from, such as those left by a
+/// previous run at the url's [partial_path]. Transfers that fail due to network
+/// issues are retried according to the download `settings`. The file is kept
+/// when the retries run out so a later download can resume, and is removed if
+/// the download fails for any other reason.
+pub fn download_to_file(
+    url: &str,
+    dst: &Path,
+    settings: &DownloadSettings,
+    headers: &[&str],
+) -> Result<(), Fault> {
+    download(url, dst, settings, headers, true)
+}
+
+/// Downloads the contents at `url` into memory.
+///
+/// Redirects are only followed when `follow` is `true`. This function is
+/// intended for small responses; see [download_to_file] for retrieving
+/// archives.
+pub fn download_to_bytes(
+    url: &str,
+    settings: &DownloadSettings,
+    headers: &[&str],
+    follow: bool,
+) -> Result<Vec<u8>, Fault> {
+    let dir = tempfile::tempdir()?;
+    let dst = dir.path().join("response");
+    download(url, &dst, settings, headers, follow)?;
+    Ok(fs::read(&dst)?)
+}
+
+/// Downloads the contents at `url` into the file `dst`, retrying any transfers
+/// that fail due to network issues.
+fn download(
+    url: &str,
+    dst: &Path,
+    settings: &DownloadSettings,
+    headers: &[&str],
+    follow: bool,
+) -> Result<(), Fault> {
+    let retries = settings.get_retries();
+    let mut attempt = 0;
+    loop {
+        let failure = match transfer(url, dst, settings, headers, follow) {
+            Ok(()) => {
+                fs::remove_file(validator_path(dst)).ok();
+                return Ok(());
+            }
+            Err(f) => f,
+        };
+        // the previous bytes are unusable so start over
+        if failure.is_retryable() == false || failure.is_range_rejected() == true {
+            fs::remove_file(dst).ok();
+            fs::remove_file(validator_path(dst)).ok();
+        }
+        if failure.is_retryable() == false || attempt >= retries {
+            return Err(failure.into_fault(url, attempt + 1));
+        }
+        let delay = settings.get_retry_delay(attempt);
+        attempt += 1;
+        println!(
+            "{} failed to download {:?}: {}; retrying in {}s ({}/{}) ...",
+            "warning:".yellow(),
+            url,
+            failure,
+            delay.as_secs(),
+            attempt,
+            retries
+        );
+        std::thread::sleep(delay);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::collections::HashMap;
+
+    #[test]
+    fn proxy_resolution() {
+        let vars: HashMap<&str, &str> = HashMap::from([
+            ("HTTPS_PROXY", "http://corp:8080"),
+            ("http_proxy", "http://plain:80"),
+            ("NO_PROXY", "localhost,.internal"),
+        ]);
+        let env = |k: &str| vars.get(k).map(|v| v.to_string());
+
+        assert_eq!(
+            resolve_proxy("https://a.io/x.zip", None, env),
+            Some(String::from("http://corp:8080"))
+        );
+        assert_eq!(
+            resolve_proxy("http://a.io/x.zip", None, env),
+            Some(String::from("http://plain:80"))
+        );
+        // the configuration takes precedence over the environment
+        let proxy = String::from("http://mine:3128");
+        assert_eq!(
+            resolve_proxy("https://a.io/x.zip", Some(&proxy), env),
+            Some(proxy.clone())
+        );
+        assert_eq!(resolve_proxy("https://a.io/x.zip", None, |_| None), None);
+
+        assert_eq!(
+            resolve_no_proxy(None, env),
+            Some(String::from("localhost,.internal"))
+        );
+        let no_proxy = String::from("*");
+        assert_eq!(
+            resolve_no_proxy(Some(&no_proxy), env),
+            Some(no_proxy.clone())
+        );
+    }
+
+    #[test]
+    fn response_status() {
+        assert_eq!(is_success_status("https://a.io", 200), true);
+        assert_eq!(is_success_status("https://a.io", 206), true);
+        assert_eq!(is_success_status("https://a.io", 404), false);
+        assert_eq!(is_success_status("https://a.io", 0), false);
+        assert_eq!(is_success_status("file:///tmp/a.zip", 0), true);
+
+        assert_eq!(is_retryable_status(503), true);
+        assert_eq!(is_retryable_status(429), true);
+        assert_eq!(is_retryable_status(404), false);
+        assert_eq!(is_retryable_status(403), false);
+    }
+
+    #[test]
+    fn download_local_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let src = dir.path().join("src.txt");
+        fs::write(&src, "orbit").unwrap();
+        let url = format!("file://{}", src.display());
+
+        let bytes = download_to_bytes(&url, &DownloadSettings::new(), &[], true).unwrap();
+        assert_eq!(bytes, b"orbit");
+
+        // missing files are not retried and leave nothing behind
+        let dst = dir.path().join("dst.txt");
+        let missing = format!("file://{}", dir.path().join("none.txt").display());
+        assert_eq!(
+            download_to_file(&missing, &dst, &DownloadSettings::new(), &[]).is_err(),
+            true
+        );
+        assert_eq!(dst.exists(), false);
+    }
+
+    #[test]
+    fn resume_partial_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let src = dir.path().join("src.txt");
+        fs::write(&src, "hello orbit").unwrap();
+        let url = format!("file://{}", src.display());
+
+        // the same url is always kept at the same location
+        let downloads = dir.path().join("downloads");
+        let dst = partial_path(&downloads, &url);
+        assert_eq!(dst, partial_path(&downloads, &url));
+        assert_ne!(dst, partial_path(&downloads, "file:///other.txt"));
+        assert_eq!(dst.starts_with(downloads.join(PARTIAL_DIR)), true);
+
+        // only the bytes missing from a previous run are transferred
+        fs::create_dir_all(dst.parent().unwrap()).unwrap();
+        fs::write(&dst, "HELLO").unwrap();
+        download_to_file(&url, &dst, &DownloadSettings::new(), &[]).unwrap();
+        assert_eq!(fs::read_to_string(&dst).unwrap(), "HELLO orbit");
+    }
+
+    #[test]
+    fn response_validator() {
+        assert_eq!(
+            read_validator("ETag: \"33a64df5\"\r\n"),
+            Some((String::from("etag"), String::from("\"33a64df5\"")))
+        );
+        assert_eq!(
+            read_validator("last-modified: Wed, 21 Oct 2015 07:28:00 GMT\r\n"),
+            Some((
+                String::from("last-modified"),
+                String::from("Wed, 21 Oct 2015 07:28:00 GMT")
+            ))
+        );
+        // weak validators cannot resume a transfer
+        assert_eq!(read_validator("ETag: W/\"33a64df5\"\r\n"), None);
+        assert_eq!(read_validator("Content-Length: 12\r\n"), None);
+
+        let dst = PathBuf::from("downloads/.partial/abc");
+        assert_eq!(
+            validator_path(&dst),
+            PathBuf::from("downloads/.partial/abc.validator")
+        );
+    }
+}
@@ -22,6 +22,7 @@ pub mod environment;
 pub mod filesystem;
 pub mod graph;
 pub mod graphmap;
+pub mod http;
 pub mod overdetsys;
 pub mod prompt;
 pub mod seqalin;
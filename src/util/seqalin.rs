@@ -93,6 +93,33 @@ pub fn sel_min_edit_str<'a, T: AsRef<str>>(
     }
 }
 
+/// Given a word `s` and a known set of words `bank`, collect every word with an
+/// edit distance to the given word below the `threshold`, ordered from closest
+/// to farthest.
+///
+/// Words with equal distances are ordered alphabetically. Unlike [sel_min_edit_str],
+/// the words are compared without case sensitivity.
+pub fn rank_min_edit_strs<'a, T: AsRef<str>>(
+    s: &str,
+    bank: &'a [T],
+    threshold: Cost,
+) -> Vec<&'a str> {
+    let s = s.to_lowercase();
+    let mut ranks: Vec<(Cost, &str)> = bank
+        .iter()
+        .map(|f| {
+            (
+                sequence_alignment(&s, &f.as_ref().to_lowercase(), 1, 1),
+                f.as_ref(),
+            )
+        })
+        .filter(|(c, _)| *c < threshold)
+        .collect();
+    ranks.sort();
+    ranks.dedup();
+    ranks.into_iter().map(|(_, w)| w).collect()
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
@@ -125,4 +152,28 @@ mod test {
         assert_eq!(sel_min_edit_str("cck", &bank, 3), Some("check"));
         assert_eq!(sel_min_edit_str("digt", &bank, 3), Some("digit"));
     }
+
+    #[test]
+    fn rank_closest_words() {
+        let bank: Vec<&str> = vec![];
+        assert_eq!(rank_min_edit_strs("word", &bank, 3).is_empty(), true);
+
+        let bank: Vec<&str> = vec!["run", "check", "build", "plan", "config", "play", "digit"];
+        assert_eq!(rank_min_edit_strs("pla", &bank, 3), vec!["plan", "play"]);
+        assert_eq!(rank_min_edit_strs("word", &bank, 3).is_empty(), true);
+        // case sensitivity is not applied
+        assert_eq!(rank_min_edit_strs("BUILD", &bank, 3), vec!["build"]);
+        // closer words are ranked first
+        let bank: Vec<&str> = vec!["grates", "gates", "gate"];
+        assert_eq!(
+            rank_min_edit_strs("gats", &bank, 3),
+            vec!["gate", "gates", "grates"]
+        );
+
+        let bank = vec!["and_gate_1_4_3", "or_gate_1_4_3", "nand_gate_1_4_3"];
+        assert_eq!(
+            rank_min_edit_strs("and_gat_1_4_3", &bank, 6),
+            vec!["and_gate_1_4_3", "nand_gate_1_4_3", "or_gate_1_4_3"]
+        );
+    }
 }